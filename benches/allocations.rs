@@ -0,0 +1,64 @@
+//! Counts the heap allocations that sending commands makes, against a minimal server on localhost.
+//! 
+//! Run with `cargo bench --bench allocations`.
+
+use std::{io::{Read, Write}, net::{TcpListener, TcpStream}, thread};
+
+use mc_rcon::{RconClient, ResponseBuffer};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const ITERATIONS: u64 = 1000;
+const RESPONSE: &[u8] = b"There are 0 of a max of 20 players online: ";
+
+/// Answers every packet with [`RESPONSE`] until the client disconnects, without allocating.
+fn serve(mut stream: TcpStream) {
+  let mut header = [0; 12];
+  let mut payload = [0; 4096];
+  let mut out = [0; 12 + RESPONSE.len() + 2];
+  out[0..4].copy_from_slice(&(10 + RESPONSE.len() as i32).to_le_bytes());
+  out[12..12 + RESPONSE.len()].copy_from_slice(RESPONSE);
+  while stream.read_exact(&mut header).is_ok() {
+    let length = i32::from_le_bytes(header[0..4].try_into().unwrap()) as usize;
+    if stream.read_exact(&mut payload[..length - 8]).is_err() {
+      break
+    }
+    let response_type: i32 = if header[8..12] == 3i32.to_le_bytes() { 2 } else { 0 };
+    out[4..8].copy_from_slice(&header[4..8]);
+    out[8..12].copy_from_slice(&response_type.to_le_bytes());
+    if stream.write_all(&out).is_err() {
+      break
+    }
+  }
+}
+
+/// Returns the number of allocations that `f` makes.
+fn count_allocations(f: impl FnOnce()) -> u64 {
+  let before = dhat::HeapStats::get().total_blocks;
+  f();
+  dhat::HeapStats::get().total_blocks - before
+}
+
+fn main() {
+  let _profiler = dhat::Profiler::builder().testing().build();
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let addr = listener.local_addr().unwrap();
+  thread::spawn(move || serve(listener.accept().unwrap().0));
+  let client = RconClient::connect(addr).unwrap();
+  client.log_in("password").unwrap();
+  
+  let send_command = count_allocations(|| {
+    for _ in 0..ITERATIONS {
+      client.send_command("list").unwrap();
+    }
+  });
+  let mut buf = ResponseBuffer::new();
+  let send_command_into = count_allocations(|| {
+    for _ in 0..ITERATIONS {
+      client.send_command_into("list", &mut buf).unwrap();
+    }
+  });
+  println!("send_command:      {:.2} allocations per command", send_command as f64 / ITERATIONS as f64);
+  println!("send_command_into: {:.2} allocations per command", send_command_into as f64 / ITERATIONS as f64);
+}
\ No newline at end of file
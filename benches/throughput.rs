@@ -0,0 +1,51 @@
+//! Measures command round trips against a [`ScriptedServer`] on localhost.
+//! 
+//! Run with `cargo bench --features testing --bench throughput`,
+//! or with `cargo bench --features testing --bench throughput -- --test` to run each benchmark once as a smoke test.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use mc_rcon::{testing::ScriptedServer, RconClient};
+
+const LARGE_RESPONSE_LEN: usize = 64 * 1024;
+
+/// Connects a logged-in client to a new scripted server.
+fn connect() -> (RconClient, ScriptedServer) {
+  let server = ScriptedServer::new("password", |command| match command {
+    "large" => "a".repeat(LARGE_RESPONSE_LEN),
+    "seed" => "Seed: [-1137927873379713691]".to_owned(),
+    _ => "There are 0 of a max of 20 players online: ".to_owned()
+  }).unwrap();
+  let client = RconClient::connect(server.local_addr()).unwrap();
+  client.log_in("password").unwrap();
+  (client, server)
+}
+
+fn round_trip(c: &mut Criterion) {
+  let (client, _server) = connect();
+  c.bench_function("round_trip", |b| b.iter(|| client.send_command("list").unwrap()));
+}
+
+fn many_commands(c: &mut Criterion) {
+  let (client, _server) = connect();
+  let commands = vec!["list"; 1000];
+  let mut group = c.benchmark_group("many_commands");
+  group.throughput(Throughput::Elements(commands.len() as u64));
+  group.sample_size(20);
+  // the client waits for each response before sending the next command
+  group.bench_function("sequential_1000", |b| b.iter(|| client.send_commands_sequential(&commands)));
+  group.finish();
+}
+
+fn reassembly(c: &mut Criterion) {
+  let (client, _server) = connect();
+  let mut group = c.benchmark_group("reassembly");
+  group.throughput(Throughput::Bytes(LARGE_RESPONSE_LEN as u64));
+  group.bench_function("64_kib", |b| b.iter(|| {
+    let response = client.send_command("large").unwrap();
+    assert_eq!(response.len(), LARGE_RESPONSE_LEN);
+  }));
+  group.finish();
+}
+
+criterion_group!(benches, round_trip, many_commands, reassembly);
+criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,49 @@
+//! Sends the same command to several servers at once, with one client per server.
+//! 
+//! Run with `cargo run --example concurrent_servers [ADDRESS...]`.
+//! Without any addresses, this connects to five servers on localhost, on ports 25575 to 25579.
+//! The password is read from the `RCON_PASSWORD` environment variable.
+//! 
+//! Each `RconClient` sends one command at a time, so sending to several servers at once takes one client per server.
+//! A client can be shared between threads by reference, so `std::thread::scope` can use them without moving them.
+
+use std::{env, error::Error, thread};
+
+use mc_rcon::{CommandError, RconClient};
+
+fn main() -> Result<(), Box<dyn Error>> {
+  let mut addrs: Vec<String> = env::args().skip(1).collect();
+  if addrs.is_empty() {
+    addrs = (25575..25580).map(|port| format!("localhost:{}", port)).collect();
+  }
+  let password = env::var("RCON_PASSWORD").unwrap_or_default();
+  
+  // connecting and logging in happen in parallel too, since either may take a while
+  let clients: Vec<(&str, RconClient)> = thread::scope(|scope| {
+    let handles: Vec<_> = addrs.iter().map(|addr| scope.spawn(|| -> Result<RconClient, Box<dyn Error + Send + Sync>> {
+      let client = RconClient::connect(addr.as_str())?;
+      client.log_in(&*password)?;
+      Ok(client)
+    })).collect();
+    addrs.iter().zip(handles).filter_map(|(addr, handle)| match handle.join().expect("connecting thread panicked") {
+      Ok(client) => Some((addr.as_str(), client)),
+      Err(e) => {
+        println!("{}: failed to connect: {}", addr, e);
+        None
+      }
+    }).collect()
+  });
+  
+  let seeds: Vec<Result<String, CommandError>> = thread::scope(|scope| {
+    let handles: Vec<_> = clients.iter().map(|(_, client)| scope.spawn(|| client.send_command("seed"))).collect();
+    handles.into_iter().map(|handle| handle.join().expect("sending thread panicked")).collect()
+  });
+  
+  for ((addr, _), seed) in clients.iter().zip(seeds) {
+    match seed {
+      Ok(seed) => println!("{}: {}", addr, seed),
+      Err(e) => println!("{}: failed to send command: {}", addr, e)
+    }
+  }
+  Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+//! Feeds arbitrary bytes to a client as the server's side of the connection.
+//! 
+//! The first byte chooses the client's options (see [`options`]), and the rest is what the server sends.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mc_rcon_fuzz::{exchange, options};
+
+fuzz_target!(|data: &[u8]| {
+  if let Some((&first, server_output)) = data.split_first() {
+    let (dialect, terminator_mode) = options(first);
+    exchange(server_output, dialect, terminator_mode);
+  }
+});
\ No newline at end of file
@@ -0,0 +1,78 @@
+//! Feeds a client a sequence of packets with arbitrary headers, payloads, and terminators as the server's side of the connection.
+//! 
+//! Unlike `fuzz_target_1`, this generates mostly well-framed packets, so it spends its time on the client's reassembly logic
+//! rather than on rejecting garbage. Each packet is also checked against `RconPacket::decode`.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mc_rcon::RconPacket;
+use mc_rcon_fuzz::{exchange, options};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+  
+  options: u8,
+  packets: Vec<Packet>
+  
+}
+
+#[derive(Debug, Arbitrary)]
+struct Packet {
+  
+  /// The value of the length field, or `None` for the packet's actual length.
+  length: Option<i32>,
+  id: Id,
+  packet_type: i32,
+  payload: Vec<u8>,
+  /// The number of null bytes after the payload, which is 2 in a well-formed packet.
+  terminator_len: u8
+  
+}
+
+/// Packet ids, biased towards the ones the client actually uses.
+#[derive(Debug, Arbitrary)]
+enum Id {
+  
+  /// The id of the client's nth packet.
+  Request(u8),
+  /// The id of an authentication failure.
+  AuthFailure,
+  Other(i32)
+  
+}
+
+impl Packet {
+  
+  fn encode(&self, out: &mut Vec<u8>) {
+    let terminator = vec![0; usize::from(self.terminator_len % 4)];
+    let actual_len = 8 + self.payload.len() + terminator.len();
+    let length = self.length.unwrap_or(actual_len as i32);
+    let id = match self.id {
+      Id::Request(n) => i32::from(n),
+      Id::AuthFailure => -1,
+      Id::Other(id) => id
+    };
+    let start = out.len();
+    out.extend_from_slice(&length.to_le_bytes());
+    out.extend_from_slice(&id.to_le_bytes());
+    out.extend_from_slice(&self.packet_type.to_le_bytes());
+    out.extend_from_slice(&self.payload);
+    out.extend_from_slice(&terminator);
+    // decoding must never panic, and must accept exactly the well-formed packets it could have encoded
+    if let Ok(packet) = RconPacket::decode(&out[start..]) {
+      assert_eq!(packet.encode(), out[start..]);
+    }
+  }
+  
+}
+
+fuzz_target!(|input: Input| {
+  let mut server_output = Vec::new();
+  for packet in &input.packets {
+    packet.encode(&mut server_output);
+  }
+  let (dialect, terminator_mode) = options(input.options);
+  exchange(&server_output, dialect, terminator_mode);
+});
\ No newline at end of file
@@ -0,0 +1,51 @@
+//! Shared code for the fuzz targets, which run an [`RconClient`] against a server that sends arbitrary bytes.
+
+use std::{io::{self, Write}, net::{Shutdown, TcpListener, TcpStream}, thread, time::Duration};
+
+use mc_rcon::{Dialect, RconClient, TerminatorMode};
+use socket2::SockRef;
+
+/// Connects a client to a server that sends `server_output` (regardless of what the client sends) and then closes the connection,
+/// and has the client log in and send a few commands.
+/// 
+/// Errors are expected, since the server's output is almost always nonsense; only panics and hangs are bugs.
+pub fn exchange(server_output: &[u8], dialect: Dialect, terminator_mode: TerminatorMode) {
+  let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind server");
+  let addr = listener.local_addr().expect("server has no address");
+  let server_output = server_output.to_vec();
+  let server = thread::spawn(move || -> io::Result<()> {
+    let (mut stream, _) = listener.accept()?;
+    reset_on_close(&stream)?;
+    stream.write_all(&server_output)?;
+    stream.shutdown(Shutdown::Write)?;
+    // read until the client disconnects, so that the client sees the end of the output rather than a reset
+    io::copy(&mut stream, &mut io::sink())?;
+    Ok(())
+  });
+  
+  let mut client = RconClient::connect_with_dialect(addr, dialect).expect("could not connect to server");
+  client.set_terminator_mode(terminator_mode);
+  if client.log_in("password").is_ok() {
+    let _ = client.send_command("list");
+    if let Ok(fragments) = client.send_command_stream("help") {
+      fragments.for_each(drop);
+    }
+    let _ = client.send_command("seed");
+  }
+  let _ = reset_on_close(&client.into_parts());
+  // the server failing is fine (for example, if the client disconnected before reading everything), but it must not panic
+  let _ = server.join().expect("server panicked");
+}
+
+/// Makes closing `stream` reset the connection, rather than leaving it in `TIME_WAIT`,
+/// so that millions of runs do not exhaust the local ports.
+fn reset_on_close(stream: &TcpStream) -> io::Result<()> {
+  SockRef::from(stream).set_linger(Some(Duration::ZERO))
+}
+
+/// Chooses a dialect and terminator mode from the low bits of `byte`, so that fuzzers can explore every combination.
+pub fn options(byte: u8) -> (Dialect, TerminatorMode) {
+  let dialect = if byte & 1 == 0 { Dialect::Minecraft } else { Dialect::Source };
+  let terminator_mode = if byte & 2 == 0 { TerminatorMode::Strict } else { TerminatorMode::Lenient };
+  (dialect, terminator_mode)
+}
\ No newline at end of file
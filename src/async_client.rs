@@ -0,0 +1,546 @@
+use std::{future::Future, io::{self, IoSlice}, time::{Duration, Instant}};
+#[cfg(feature = "tokio")]
+use std::{pin::Pin, task::{ready, Context, Poll}};
+
+use futures_lite::{future, stream, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, Stream};
+
+use crate::{cooldown_remaining, decode_payload, fragments::take_utf8, header_bytes, id_after, incoming_body_len, is_closed, sanitize, split_packet_body, write_raw_packet, CommandError, CommandPacket, ControlCharacters, LogInError, LogInPacket, LogInWith, OutBuf, PacketKind, RawPacketHeader, SendError, TerminatorMode, DEFAULT_LOG_IN_COOLDOWN, DEFAULT_MAX_RESPONSE_FRAGMENTS, DEFAULT_SENTINEL_COMMAND, COMMAND_TYPE, HEADER_LEN, I32_LEN, MAX_EAGER_BODY_LEN, MAX_INCOMING_PAYLOAD_LEN, MAX_OUTGOING_PAYLOAD_LEN};
+
+/// An asynchronous RCON client over any connection implementing the [`futures-io`](https://docs.rs/futures-io) traits,
+/// available with the `futures-io` feature.
+/// 
+/// This speaks the same protocol as [`RconClient`](crate::RconClient), including reassembling fragmented responses with the
+/// [sentinel command](AsyncRconClient::set_sentinel_command) (up to [`DEFAULT_MAX_RESPONSE_FRAGMENTS`](crate::DEFAULT_MAX_RESPONSE_FRAGMENTS) packets), and rejects [control characters](ControlCharacters::Reject) the same way by default.
+/// Its methods take `&mut self`, so each client sends one command at a time;
+/// to share a client between tasks, put it behind an asynchronous lock.
+/// 
+/// With the `async-smol` feature, [`SmolRconClient`] can connect to a server directly.
+/// Any other connection can be wrapped with [`new`](AsyncRconClient::new), for example an in-memory one:
+/// ```
+/// # use std::{error::Error, io::{self, Read, Write}};
+/// # use futures_lite::io::AssertAsync;
+/// # use mc_rcon::AsyncRconClient;
+/// # 
+/// # // a connection that reads scripted responses and discards anything written to it
+/// # struct Duplex(io::Cursor<Vec<u8>>);
+/// # impl Read for Duplex { fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) } }
+/// # impl Write for Duplex { fn write(&mut self, buf: &[u8]) -> io::Result<usize> { Ok(buf.len()) } fn flush(&mut self) -> io::Result<()> { Ok(()) } }
+/// # fn packet(id: i32, packet_type: i32, payload: &[u8]) -> Vec<u8> {
+/// #   [&(payload.len() as i32 + 10).to_le_bytes()[..], &id.to_le_bytes(), &packet_type.to_le_bytes(), payload, b"\0\0"].concat()
+/// # }
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # futures_lite::future::block_on(async {
+/// // the server accepts the password, answers a fragmented response, then the sentinel, then ends the session
+/// let responses = [
+///   packet(0, 2, b""),
+///   packet(1, 0, &[b'a'; 4096]), packet(1, 0, b"bc"), packet(2, 0, b"Seed: [42]"),
+///   packet(-1, 0, b"")
+/// ].concat();
+/// let mut client = AsyncRconClient::new(AssertAsync::new(Duplex(io::Cursor::new(responses))));
+/// client.log_in("SuperSecurePassword").await?;
+/// assert_eq!(client.send_command("banlist").await?.len(), 4098);
+/// assert!(client.send_command("seed").await.is_err());
+/// assert!(!client.is_logged_in());
+/// #   Ok(())
+/// # })
+/// # }
+/// ```
+/// 
+/// # Cancellation
+/// 
+/// If a future returned by this client is dropped part way through an exchange with the server
+/// (for example, because it lost a race against a timeout), part of the server's response may be left unread,
+/// so the client can no longer tell which bytes belong to which response.
+/// Rather than misreading later responses, the client is then poisoned:
+/// every later attempt to log in or send a command fails with [`LogInError::ConnectionPoisoned`] or [`CommandError::ConnectionPoisoned`],
+/// and a new connection is needed.
+/// The same happens if an exchange fails part way through because of an I/O error.
+/// Futures dropped before they start sending, or after they complete, do not poison the client.
+/// ```
+/// # use std::{error::Error, io::{self, Read}, pin::Pin, task::{Context, Poll}};
+/// # use futures_lite::{future, AsyncRead, AsyncWrite};
+/// # use mc_rcon::{AsyncRconClient, CommandError};
+/// # 
+/// # // a connection that reads scripted responses, waits forever once they run out, and discards anything written to it
+/// # struct Partial(io::Cursor<Vec<u8>>);
+/// # impl AsyncRead for Partial {
+/// #   fn poll_read(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+/// #     match self.0.read(buf) { Ok(0) => Poll::Pending, read => Poll::Ready(read) }
+/// #   }
+/// # }
+/// # impl AsyncWrite for Partial {
+/// #   fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> { Poll::Ready(Ok(buf.len())) }
+/// #   fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> { Poll::Ready(Ok(())) }
+/// #   fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> { Poll::Ready(Ok(())) }
+/// # }
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # future::block_on(async {
+/// // the server accepts the password, then sends only the start of a response
+/// let responses = [&[10, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0][..], &[20, 0, 0, 0, 1, 0]].concat();
+/// let mut client = AsyncRconClient::new(Partial(io::Cursor::new(responses)));
+/// client.log_in("SuperSecurePassword").await?;
+/// assert!(future::poll_once(client.send_command("seed")).await.is_none()); // gives up part way through the response
+/// assert_eq!(client.send_command("seed").await, Err(CommandError::ConnectionPoisoned));
+/// #   Ok(())
+/// # })
+/// # }
+/// ```
+/// 
+#[cfg_attr(feature = "async-smol", doc = "[`SmolRconClient`]: crate::SmolRconClient")]
+#[cfg_attr(not(feature = "async-smol"), doc = "[`SmolRconClient`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.SmolRconClient.html")]
+#[derive(Debug)]
+pub struct AsyncRconClient<S> {
+  
+  stream: S,
+  next_id: i32,
+  logged_in: bool,
+  poisoned: bool,
+  sentinel_command: String,
+  terminator_mode: TerminatorMode,
+  log_in_cooldown: Duration,
+  last_bad_password: Option<Instant>
+  
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncRconClient<S> {
+  
+  /// Construct an `AsyncRconClient` that speaks RCON over the given connection, which must not have been used yet.
+  pub fn new(stream: S) -> AsyncRconClient<S> {
+    AsyncRconClient { stream, next_id: 0, logged_in: false, poisoned: false, sentinel_command: DEFAULT_SENTINEL_COMMAND.to_owned(), terminator_mode: TerminatorMode::default(), log_in_cooldown: DEFAULT_LOG_IN_COOLDOWN, last_bad_password: None }
+  }
+  
+  /// Consumes this client and returns the underlying connection.
+  /// 
+  /// Note that the connection is in an unknown state relative to the RCON protocol,
+  /// as with [`RconClient::into_parts`](crate::RconClient::into_parts).
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+  
+  /// Returns whether this client is logged in.
+  pub fn is_logged_in(&self) -> bool {
+    self.logged_in
+  }
+  
+  /// Returns whether an exchange with the server was interrupted part way through, leaving this client unusable.
+  /// See [Cancellation](AsyncRconClient#cancellation) for details.
+  pub fn is_poisoned(&self) -> bool {
+    self.poisoned
+  }
+  
+  /// Returns the command this client uses to detect the end of a fragmented response.
+  /// See [`RconClient::set_sentinel_command`](crate::RconClient::set_sentinel_command) for details.
+  pub fn sentinel_command(&self) -> &str {
+    &self.sentinel_command
+  }
+  
+  /// Sets the command this client uses to detect the end of a fragmented response,
+  /// which is [`DEFAULT_SENTINEL_COMMAND`] by default.
+  /// See [`RconClient::set_sentinel_command`](crate::RconClient::set_sentinel_command) for details.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `command` is longer than [`MAX_OUTGOING_PAYLOAD_LEN`].
+  pub fn set_sentinel_command(&mut self, command: String) {
+    assert!(command.len() <= MAX_OUTGOING_PAYLOAD_LEN, "sentinel command must be no longer than {} bytes", MAX_OUTGOING_PAYLOAD_LEN);
+    self.sentinel_command = command;
+  }
+  
+  /// Returns how this client treats the null terminator and padding at the end of each packet it receives.
+  /// See [`TerminatorMode`] for details.
+  pub fn terminator_mode(&self) -> TerminatorMode {
+    self.terminator_mode
+  }
+  
+  /// Sets how this client treats the null terminator and padding at the end of each packet it receives,
+  /// which is [`TerminatorMode::Strict`] by default.
+  /// See [`TerminatorMode`] for details.
+  pub fn set_terminator_mode(&mut self, mode: TerminatorMode) {
+    self.terminator_mode = mode;
+  }
+  
+  /// Returns how long this client refuses to log in again after the server rejects its password.
+  /// See [`RconClient::set_log_in_cooldown`](crate::RconClient::set_log_in_cooldown) for details.
+  pub fn log_in_cooldown(&self) -> Duration {
+    self.log_in_cooldown
+  }
+  
+  /// Sets how long this client refuses to log in again after the server rejects its password,
+  /// which is [`DEFAULT_LOG_IN_COOLDOWN`](crate::DEFAULT_LOG_IN_COOLDOWN) by default.
+  /// See [`RconClient::set_log_in_cooldown`](crate::RconClient::set_log_in_cooldown) for details.
+  pub fn set_log_in_cooldown(&mut self, cooldown: Duration) {
+    self.log_in_cooldown = cooldown;
+  }
+  
+  /// Attempts to log into the server with the given password.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::log_in`](crate::RconClient::log_in).
+  pub async fn log_in(&mut self, password: impl LogInWith) -> Result<(), LogInError> {
+    if self.logged_in {
+      Err(LogInError::AlreadyLoggedIn)?
+    }
+    self.send_log_in(password.password()).await
+  }
+  
+  /// Logs into the server again with the given password, whether or not this client is already logged in.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::reauth`](crate::RconClient::reauth).
+  pub async fn reauth(&mut self, password: impl LogInWith) -> Result<(), LogInError> {
+    let result = self.send_log_in(password.password()).await;
+    if let Err(LogInError::BadPassword) = result {
+      self.logged_in = false;
+    }
+    result
+  }
+  
+  async fn send_log_in(&mut self, password: &str) -> Result<(), LogInError> {
+    if let Some(retry_after) = cooldown_remaining(self.last_bad_password, self.log_in_cooldown) {
+      Err(LogInError::Throttled { retry_after })?
+    }
+    let (good_auth, _) = self.send(LogInPacket, password.as_bytes()).await?;
+    if good_auth {
+      self.logged_in = true;
+      self.last_bad_password = None;
+      Ok(())
+    } else {
+      self.last_bad_password = Some(Instant::now());
+      Err(LogInError::BadPassword)
+    }
+  }
+  
+  /// Sends the given command to the server and returns its response.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::send_command`](crate::RconClient::send_command).
+  pub async fn send_command(&mut self, command: &str) -> Result<String, CommandError> {
+    if !self.logged_in {
+      Err(CommandError::NotLoggedIn)?
+    }
+    let (good_auth, payload) = match self.send(CommandPacket, command.as_bytes()).await {
+      Ok(response) => response,
+      Err(e) => {
+        if matches!(&e, SendError::IO(e) if is_closed(e)) {
+          self.logged_in = false; // the session ends with the connection
+        }
+        Err(e)?
+      }
+    };
+    if good_auth {
+      Ok(decode_payload(payload)?)
+    } else {
+      self.logged_in = false;
+      Err(CommandError::NotLoggedIn)
+    }
+  }
+  
+  /// Sends the given command to the server and returns a stream over the packets of its response as they arrive.
+  /// 
+  /// This works like [`RconClient::send_command_stream`](crate::RconClient::send_command_stream),
+  /// except that if the stream is dropped before it ends, the rest of the response is left unread,
+  /// so this client is [poisoned](AsyncRconClient#cancellation).
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use futures_lite::StreamExt;
+  /// # use mc_rcon::SmolRconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # futures_lite::future::block_on(async {
+  /// let mut client = SmolRconClient::connect("localhost:25575").await?;
+  /// client.log_in("SuperSecurePassword").await?;
+  /// let mut fragments = Box::pin(client.send_command_stream("help").await?);
+  /// while let Some(fragment) = fragments.next().await {
+  ///   print!("{}", fragment?);
+  /// }
+  /// #   Ok(())
+  /// # })
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](AsyncRconClient::send_command).
+  /// Errors that occur before the first packet of the response arrives are returned by this method,
+  /// and any later ones by the stream, which then ends.
+  pub async fn send_command_stream(&mut self, command: &str) -> Result<impl Stream<Item = Result<String, CommandError>> + '_, CommandError> {
+    if !self.logged_in {
+      Err(CommandError::NotLoggedIn)?
+    }
+    let command = sanitize(command.as_bytes(), ControlCharacters::default())?;
+    if command.len() > MAX_OUTGOING_PAYLOAD_LEN {
+      Err(CommandError::CommandTooLong)?
+    }
+    if self.poisoned {
+      Err(CommandError::ConnectionPoisoned)?
+    }
+    
+    // cleared once the whole response has been read, as in send
+    self.poisoned = true;
+    let id = self.get_next_id();
+    write_packet(&mut self.stream, id, COMMAND_TYPE, &command).await?;
+    let (header, payload) = read_response(&mut self.stream, &CommandPacket, self.terminator_mode).await?;
+    if header.id == -1 {
+      self.poisoned = false;
+      self.logged_in = false;
+      Err(CommandError::NotLoggedIn)?
+    } else if header.id != id {
+      Err(io::Error::new(io::ErrorKind::InvalidData, CommandPacket::INVLID_RESPONSE_ID_ERROR))?
+    }
+    
+    let sentinel_id = if payload.len() >= MAX_INCOMING_PAYLOAD_LEN {
+      let sentinel_id = self.get_next_id();
+      write_packet(&mut self.stream, sentinel_id, COMMAND_TYPE, self.sentinel_command.as_bytes()).await?;
+      Some(sentinel_id)
+    } else {
+      self.poisoned = false;
+      None
+    };
+    let fragments = AsyncFragments { client: self, id, sentinel_id, first: Some(payload), partial: Vec::new(), packets: 1 };
+    Ok(stream::unfold(fragments, |mut fragments| async move {
+      let fragment = fragments.next().await?;
+      Some((fragment, fragments))
+    }))
+  }
+  
+  /// Sends the given command to the server and returns its response, unless `timer` completes first.
+  /// 
+  /// `timer` bounds the whole exchange, including reassembling a fragmented response,
+  /// and can come from any runtime, such as `async_io::Timer::at(deadline)` or `tokio::time::sleep(timeout)`.
+  /// [`SmolRconClient`] also has [`send_command_deadline`],
+  /// which makes the timer itself.
+  /// 
+  /// If the timer completes part way through the exchange, the rest of the response may still arrive later,
+  /// so this client is [poisoned](AsyncRconClient#cancellation).
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](AsyncRconClient::send_command),
+  /// or with [`CommandError::TimedOut`] if `timer` completes first.
+  /// 
+  #[cfg_attr(feature = "async-smol", doc = "[`SmolRconClient`]: crate::SmolRconClient")]
+  #[cfg_attr(feature = "async-smol", doc = "[`send_command_deadline`]: AsyncRconClient::send_command_deadline")]
+  #[cfg_attr(not(feature = "async-smol"), doc = "[`SmolRconClient`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.SmolRconClient.html")]
+  #[cfg_attr(not(feature = "async-smol"), doc = "[`send_command_deadline`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.AsyncRconClient.html#method.send_command_deadline")]
+  pub async fn send_command_until(&mut self, command: &str, timer: impl Future<Output = ()>) -> Result<String, CommandError> {
+    future::or(self.send_command(command), async {
+      timer.await;
+      Err(CommandError::TimedOut)
+    }).await
+  }
+  
+  fn get_next_id(&mut self) -> i32 {
+    // negative ids are skipped so that authentication failures can always be identified
+    let id = self.next_id;
+    self.next_id = id_after(id);
+    id
+  }
+  
+  /// Sends a packet and reads the whole response to it, returning whether the client is authenticated along with the response.
+  async fn send<K: PacketKind>(&mut self, kind: K, payload: &[u8]) -> Result<(bool, Vec<u8>), SendError> {
+    let payload = &*sanitize(payload, ControlCharacters::default())?;
+    if payload.len() > MAX_OUTGOING_PAYLOAD_LEN {
+      Err(SendError::PayloadTooLong)?
+    }
+    if self.poisoned {
+      Err(SendError::ConnectionPoisoned)?
+    }
+    
+    // this is only cleared once the whole exchange completes, so it stays set if this future is dropped or fails part way through
+    self.poisoned = true;
+    let out_id = self.get_next_id();
+    write_packet(&mut self.stream, out_id, K::TYPE, payload).await?;
+    let (in_header, mut payload_buf) = read_response(&mut self.stream, &kind, self.terminator_mode).await?;
+    let in_id = in_header.id;
+    
+    let good_auth = if in_id == -1 {
+      false
+    } else if in_id == out_id {
+      true
+    } else {
+      Err(io::Error::new(io::ErrorKind::InvalidData, K::INVLID_RESPONSE_ID_ERROR))?
+    };
+    
+    if K::ACCEPTS_LONG_RESPONSES && payload_buf.len() >= MAX_INCOMING_PAYLOAD_LEN {
+      let cap_id = self.get_next_id();
+      write_packet(&mut self.stream, cap_id, K::TYPE, self.sentinel_command.as_bytes()).await?;
+      let mut packets = 1;
+      loop {
+        let (inner_header, mut inner_payload_buf) = read_response(&mut self.stream, &kind, self.terminator_mode).await?;
+        let inner_in_id = inner_header.id;
+        
+        if inner_in_id == cap_id {
+          break
+        } else if inner_in_id == in_id {
+          if packets >= DEFAULT_MAX_RESPONSE_FRAGMENTS {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "response has too many fragments"))?
+          }
+          packets += 1;
+          payload_buf.append(&mut inner_payload_buf);
+        } else if inner_in_id == -1 {
+          self.logged_in = false;
+          Err(io::Error::new(io::ErrorKind::InvalidData, "client became deauthenticated between packets"))?
+        } else {
+          Err(io::Error::new(io::ErrorKind::InvalidData, K::INVLID_RESPONSE_ID_ERROR))?
+        }
+      }
+    }
+    
+    self.poisoned = false;
+    Ok((good_auth, payload_buf))
+  }
+  
+}
+
+/// Reads the raw bytes of the connection, bypassing the RCON protocol handling of this client.
+/// 
+/// This and the [`tokio::io::AsyncWrite`] implementation, available with the `tokio` feature,
+/// allow the connection to be used with `tokio` combinators, such as framing it with [`RconCodec`](crate::RconCodec).
+/// Anything read or written this way is not tracked by this client,
+/// so mixing it with [`send_command`](AsyncRconClient::send_command) can leave them out of step.
+#[cfg(feature = "tokio")]
+impl<S: AsyncRead + Unpin> tokio::io::AsyncRead for AsyncRconClient<S> {
+  
+  fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut tokio::io::ReadBuf<'_>) -> Poll<io::Result<()>> {
+    let read = ready!(Pin::new(&mut self.get_mut().stream).poll_read(cx, buf.initialize_unfilled()))?;
+    buf.advance(read);
+    Poll::Ready(Ok(()))
+  }
+  
+}
+
+/// Writes raw bytes to the connection, bypassing the RCON protocol handling of this client.
+/// See the [`tokio::io::AsyncRead`] implementation for details.
+#[cfg(feature = "tokio")]
+impl<S: AsyncWrite + Unpin> tokio::io::AsyncWrite for AsyncRconClient<S> {
+  
+  fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+    Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+  }
+  
+  fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+  }
+  
+  fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.get_mut().stream).poll_close(cx)
+  }
+  
+}
+
+/// The state of a stream returned by [`AsyncRconClient::send_command_stream`].
+struct AsyncFragments<'a, S> {
+  
+  client: &'a mut AsyncRconClient<S>,
+  id: i32,
+  sentinel_id: Option<i32>,
+  first: Option<Vec<u8>>,
+  partial: Vec<u8>,
+  packets: u32
+  
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncFragments<'_, S> {
+  
+  async fn next(&mut self) -> Option<Result<String, CommandError>> {
+    let payload = match self.first.take() {
+      Some(payload) => Some(payload),
+      None => match self.read_fragment().await {
+        Ok(payload) => payload,
+        Err(e) => {
+          self.sentinel_id = None; // the client stays poisoned, since the rest of the response is unaccounted for
+          return Some(Err(e))
+        }
+      }
+    };
+    let last = match payload {
+      Some(payload) => {
+        self.partial.extend_from_slice(&payload);
+        self.sentinel_id.is_none()
+      }
+      None if !self.partial.is_empty() => true,
+      None => return None
+    };
+    match take_utf8(&mut self.partial, last) {
+      Ok(text) => Some(Ok(text)),
+      Err(e) => {
+        // the stream ends after an error, so discard the rest of the response now
+        self.partial.clear();
+        while let Ok(Some(_)) = self.read_fragment().await {}
+        self.sentinel_id = None;
+        Some(Err(e.into()))
+      }
+    }
+  }
+  
+  /// Reads the next packet of the response, or returns `None` once the response to the sentinel arrives.
+  async fn read_fragment(&mut self) -> Result<Option<Vec<u8>>, CommandError> {
+    let Some(sentinel_id) = self.sentinel_id else {
+      return Ok(None)
+    };
+    let (header, payload) = read_response(&mut self.client.stream, &CommandPacket, self.client.terminator_mode).await?;
+    if header.id == sentinel_id {
+      self.sentinel_id = None;
+      self.client.poisoned = false;
+      Ok(None)
+    } else if header.id == self.id {
+      if self.packets >= DEFAULT_MAX_RESPONSE_FRAGMENTS {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "response has too many fragments"))?
+      }
+      self.packets += 1;
+      Ok(Some(payload))
+    } else if header.id == -1 {
+      self.client.logged_in = false;
+      Err(io::Error::new(io::ErrorKind::InvalidData, "client became deauthenticated between packets"))?
+    } else {
+      Err(io::Error::new(io::ErrorKind::InvalidData, CommandPacket::INVLID_RESPONSE_ID_ERROR))?
+    }
+  }
+  
+}
+
+async fn write_packet<S: AsyncWrite + Unpin>(stream: &mut S, id: i32, packet_type: i32, payload: &[u8]) -> io::Result<()> {
+  let length = i32::try_from(HEADER_LEN + payload.len()).expect("payload is too long");
+  let header = RawPacketHeader { length, id, packet_type };
+  // written in one go for the same reason as RconClient::write_packet, falling back to one buffer after a short write
+  let header_bytes = header_bytes(&header);
+  let written = loop {
+    match stream.write_vectored(&[IoSlice::new(&header_bytes), IoSlice::new(payload), IoSlice::new(b"\0\0")]).await {
+      Ok(0) => Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write packet"))?,
+      Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+      result => break result?
+    }
+  };
+  if written < header_bytes.len() + payload.len() + 2 {
+    let mut out_buf = OutBuf::new();
+    write_raw_packet(&mut out_buf, &header, payload)?;
+    stream.write_all(&out_buf[written..]).await?;
+  }
+  stream.flush().await
+}
+
+async fn read_response<S: AsyncRead + Unpin, K: PacketKind>(stream: &mut S, kind: &K, mode: TerminatorMode) -> io::Result<(RawPacketHeader, Vec<u8>)> {
+  let mut length_bytes = [0; I32_LEN];
+  stream.read_exact(&mut length_bytes).await?;
+  let length = i32::from_le_bytes(length_bytes);
+  let body_len = incoming_body_len(length, mode)?;
+  let mut payload = vec![0; body_len.min(MAX_EAGER_BODY_LEN)];
+  stream.read_exact(&mut payload).await?;
+  if body_len > payload.len() {
+    let eager_len = payload.len();
+    stream.take((body_len - eager_len) as u64).read_to_end(&mut payload).await?;
+    if payload.len() < body_len {
+      Err(io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"))?
+    }
+  }
+  let header = split_packet_body(length, &mut payload, 0, mode)?;
+  if header.packet_type != kind.response_type() {
+    Err(io::Error::new(io::ErrorKind::InvalidData, K::INVALID_RESPONSE_TYPE_ERROR))?
+  }
+  Ok((header, payload))
+}
\ No newline at end of file
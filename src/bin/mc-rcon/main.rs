@@ -0,0 +1,164 @@
+//! `mc-rcon`, a command-line RCON client.
+//! 
+//! Without `-c`, this opens an interactive prompt; with it, this runs the given commands in order and exits.
+//! If standard input is not a terminal, the commands are read from it instead, one per line.
+//! Run `mc-rcon --help` for the full usage.
+
+use std::{env, io::{self, IsTerminal, Write}, process::ExitCode};
+
+use mc_rcon::{formatting, LogInError, Password, RconClient};
+use script::Output;
+
+mod repl;
+mod script;
+
+const USAGE: &str = "\
+Usage: mc-rcon [OPTIONS]
+
+Without -c, commands are read from standard input, one per line, if it is not a terminal
+(blank lines and lines starting with # are skipped); otherwise, an interactive prompt is opened.
+
+Options:
+      --host <HOST>          The server's host [default: localhost]
+      --port <PORT>          The server's RCON port [default: 25575]
+      --password <PASSWORD>  The RCON password
+      --password-env <VAR>   Read the RCON password from the environment variable VAR
+      --password-stdin       Read the RCON password from the first line of standard input
+  -c, --command <COMMAND>    Run COMMAND and exit instead of opening a prompt (may be repeated)
+      --output <FORMAT>      Print responses as text, or as JSON lines with the command, response (or error),
+                             and time taken in ms [default: text]
+      --halt-on-error        Stop running commands after one fails with an I/O error
+      --no-color             Remove formatting codes from responses instead of showing them as colors
+  -h, --help                 Print this help\n
+Exit status: 0 if every command succeeded, 1 for invalid arguments, 2 if the password was rejected,
+3 if the server could not be reached, and 4 if any command failed.
+";
+
+/// The exit status when the arguments are invalid, or output cannot be written.
+const FAILURE: u8 = 1;
+/// The exit status when the server rejects the password.
+const AUTH_FAILURE: u8 = 2;
+/// The exit status when the server cannot be reached.
+const CONNECTION_FAILURE: u8 = 3;
+/// The exit status when any command fails.
+const PARTIAL_FAILURE: u8 = 4;
+
+enum PasswordSource {
+  
+  Given(String),
+  Env(String),
+  Stdin
+  
+}
+
+struct Options {
+  
+  host: String,
+  port: u16,
+  password: Option<PasswordSource>,
+  commands: Vec<String>,
+  json: bool,
+  halt_on_error: bool,
+  no_color: bool
+  
+}
+
+fn main() -> ExitCode {
+  let options = match parse_args() {
+    Ok(Some(options)) => options,
+    Ok(None) => {
+      print!("{}", USAGE);
+      return ExitCode::SUCCESS
+    }
+    Err(e) => {
+      eprintln!("mc-rcon: {}\n\n{}", e, USAGE);
+      return ExitCode::from(FAILURE)
+    }
+  };
+  let password = match read_password(options.password) {
+    Ok(password) => password,
+    Err(e) => {
+      eprintln!("mc-rcon: {}", e);
+      return ExitCode::from(FAILURE)
+    }
+  };
+  // formatting codes are shown as colors in a terminal, and removed anywhere else
+  let color = !options.no_color && io::stdout().is_terminal();
+  
+  let client = match RconClient::connect((options.host.as_str(), options.port)) {
+    Ok(client) => client,
+    Err(e) => {
+      eprintln!("mc-rcon: could not connect to {}:{}: {}", options.host, options.port, e);
+      return ExitCode::from(CONNECTION_FAILURE)
+    }
+  };
+  match client.log_in(password) {
+    Ok(()) => {}
+    Err(e) => {
+      eprintln!("mc-rcon: could not log in: {}", e);
+      return ExitCode::from(match e {
+        LogInError::BadPassword => AUTH_FAILURE,
+        LogInError::IO(_) => CONNECTION_FAILURE,
+        _ => FAILURE
+      })
+    }
+  }
+  
+  let output = if options.json { Output::Json { strip_codes: options.no_color } } else { Output::Text { color } };
+  if !options.commands.is_empty() {
+    script::run(&client, options.commands.into_iter().map(Ok), output, options.halt_on_error)
+  } else if io::stdin().is_terminal() {
+    repl::run(client, color)
+  } else {
+    script::run(&client, script::stdin_commands(), output, options.halt_on_error)
+  }
+}
+
+fn parse_args() -> Result<Option<Options>, lexopt::Error> {
+  use lexopt::prelude::*;
+  
+  let mut options = Options { host: "localhost".to_owned(), port: 25575, password: None, commands: Vec::new(), json: false, halt_on_error: false, no_color: false };
+  let mut parser = lexopt::Parser::from_env();
+  while let Some(arg) = parser.next()? {
+    match arg {
+      Long("host") => options.host = parser.value()?.string()?,
+      Long("port") => options.port = parser.value()?.parse()?,
+      Long("password") => options.password = Some(PasswordSource::Given(parser.value()?.string()?)),
+      Long("password-env") => options.password = Some(PasswordSource::Env(parser.value()?.string()?)),
+      Long("password-stdin") => options.password = Some(PasswordSource::Stdin),
+      Short('c') | Long("command") => options.commands.push(parser.value()?.string()?),
+      Long("output") => options.json = match parser.value()?.string()?.as_str() {
+        "text" => false,
+        "json" => true,
+        other => Err(format!("invalid output format '{}'; expected text or json", other))?
+      },
+      Long("halt-on-error") => options.halt_on_error = true,
+      Long("no-color") => options.no_color = true,
+      Short('h') | Long("help") => return Ok(None),
+      _ => Err(arg.unexpected())?
+    }
+  }
+  Ok(Some(options))
+}
+
+fn read_password(source: Option<PasswordSource>) -> Result<Password, String> {
+  match source {
+    Some(PasswordSource::Given(password)) => Ok(Password::new(password)),
+    Some(PasswordSource::Env(var)) => env::var(&var).map(Password::new).map_err(|e| format!("could not read the password from ${}: {}", var, e)),
+    Some(PasswordSource::Stdin) => {
+      let mut line = String::new();
+      io::stdin().read_line(&mut line).map_err(|e| format!("could not read the password from standard input: {}", e))?;
+      Ok(Password::new(line.trim_end_matches(['\r', '\n'])))
+    }
+    None => Err("no password given; use --password, --password-env, or --password-stdin".to_owned())
+  }
+}
+
+/// Prints a response in full, with its formatting codes shown as colors or removed.
+fn print_response(response: &str, color: bool) -> io::Result<()> {
+  if response.is_empty() {
+    return Ok(())
+  }
+  let response = if color { formatting::to_ansi(response) } else { formatting::strip_codes(response) };
+  writeln!(io::stdout().lock(), "{}", response.trim_end_matches('\n'))
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::{process::ExitCode, sync::{atomic::{AtomicBool, Ordering::SeqCst}, mpsc::{self, RecvTimeoutError}, Arc}, thread, time::Duration};
+
+use mc_rcon::RconClient;
+use rustyline::{error::ReadlineError, DefaultEditor};
+
+use crate::{print_response, FAILURE};
+
+/// Runs an interactive prompt until the user presses Ctrl-D.
+/// 
+/// Ctrl-C at the prompt clears the line, and while a command is running it stops waiting for the response.
+pub fn run(client: RconClient, color: bool) -> ExitCode {
+  let client = Arc::new(client);
+  let mut editor = match DefaultEditor::new() {
+    Ok(editor) => editor,
+    Err(e) => {
+      eprintln!("mc-rcon: could not open a prompt: {}", e);
+      return ExitCode::from(FAILURE)
+    }
+  };
+  let interrupted = Arc::new(AtomicBool::new(false));
+  let handler_interrupted = Arc::clone(&interrupted);
+  // this replaces the editor's own handler, which it installs when created but does not need,
+  // since while the prompt is open the terminal is in raw mode and Ctrl-C reaches the editor as a key press instead
+  if let Err(e) = ctrlc::set_handler(move || handler_interrupted.store(true, SeqCst)) {
+    eprintln!("mc-rcon: could not handle Ctrl-C: {}", e);
+  }
+  
+  loop {
+    match editor.readline("> ") {
+      Ok(input) => {
+        // a multi-line paste arrives as one input, and each line of it is a command
+        for command in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+          let _ = editor.add_history_entry(command);
+          if !run_command(&client, command, &interrupted, color) {
+            break // cancelling one line of a paste cancels the rest
+          }
+        }
+      }
+      Err(ReadlineError::Interrupted) => {}
+      Err(ReadlineError::Eof) => return ExitCode::SUCCESS,
+      Err(e) => {
+        eprintln!("mc-rcon: could not read from the prompt: {}", e);
+        return ExitCode::from(FAILURE)
+      }
+    }
+  }
+}
+
+/// Sends a command and prints its response, or returns `false` if the user cancelled it with Ctrl-C first.
+fn run_command(client: &Arc<RconClient>, command: &str, interrupted: &AtomicBool, color: bool) -> bool {
+  interrupted.store(false, SeqCst);
+  let (sender, receiver) = mpsc::channel();
+  let worker_client = Arc::clone(client);
+  let worker_command = command.to_owned();
+  // the command runs on another thread so that it can be abandoned;
+  // that thread still reads the rest of the response, and the next command waits for it, so responses are never mixed up
+  thread::spawn(move || sender.send(worker_client.send_command(&worker_command)));
+  loop {
+    match receiver.recv_timeout(Duration::from_millis(50)) {
+      Ok(Ok(response)) => if let Err(e) = print_response(&response, color) {
+        eprintln!("error: could not print the response: {}", e);
+      }
+      Ok(Err(e)) => eprintln!("error: {}", e),
+      Err(RecvTimeoutError::Timeout) if interrupted.swap(false, SeqCst) => {
+        eprintln!("(cancelled)"); // the terminal has already echoed ^C
+        return false
+      }
+      Err(RecvTimeoutError::Timeout) => continue,
+      Err(RecvTimeoutError::Disconnected) => eprintln!("error: {} failed unexpectedly", command)
+    }
+    return true
+  }
+}
\ No newline at end of file
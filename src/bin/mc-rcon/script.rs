@@ -0,0 +1,100 @@
+//! Running commands without a prompt, one after another, for `-c` and for commands piped to standard input.
+
+use std::{fmt::Write as _, io::{self, ErrorKind, Write}, process::ExitCode, time::{Duration, Instant}};
+
+use mc_rcon::{formatting, CommandError, RconClient};
+
+use crate::{print_response, FAILURE, PARTIAL_FAILURE};
+
+/// How responses are printed.
+#[derive(Debug, Clone, Copy)]
+pub enum Output {
+  
+  /// Each response as text, with its formatting codes shown as colors or removed.
+  Text { color: bool },
+  /// One JSON object per command, with its formatting codes kept unless `strip_codes` is set.
+  Json { strip_codes: bool }
+  
+}
+
+/// Runs the given commands in order, printing their responses.
+/// 
+/// Commands that fail are reported and skipped, unless the failure is an I/O error and `halt_on_error` is set,
+/// in which case the rest are not sent.
+pub fn run<I: IntoIterator<Item = io::Result<String>>>(client: &RconClient, commands: I, output: Output, halt_on_error: bool) -> ExitCode {
+  let mut failed = false;
+  for command in commands {
+    let command = match command {
+      Ok(command) => command,
+      Err(e) => {
+        eprintln!("mc-rcon: could not read commands: {}", e);
+        return ExitCode::from(PARTIAL_FAILURE)
+      }
+    };
+    let start = Instant::now();
+    let result = client.send_command(&command);
+    let elapsed = start.elapsed();
+    
+    let printed = match (&result, output) {
+      (Ok(response), Output::Text { color }) => print_response(response, color),
+      (Err(e), Output::Text { .. }) => {
+        eprintln!("mc-rcon: {}: {}", command, e);
+        Ok(())
+      }
+      (_, Output::Json { strip_codes }) => print_json(&command, &result, elapsed, strip_codes)
+    };
+    if let Err(e) = printed {
+      // the output being closed early (for example, by `head`) is not worth complaining about
+      if e.kind() != ErrorKind::BrokenPipe {
+        eprintln!("mc-rcon: could not print the response: {}", e);
+      }
+      return ExitCode::from(FAILURE)
+    }
+    
+    if let Err(e) = result {
+      failed = true;
+      if halt_on_error && matches!(e, CommandError::IO(_)) {
+        break
+      }
+    }
+  }
+  if failed { ExitCode::from(PARTIAL_FAILURE) } else { ExitCode::SUCCESS }
+}
+
+/// Reads commands from standard input, one per line, skipping blank lines and lines starting with `#`.
+pub fn stdin_commands() -> impl Iterator<Item = io::Result<String>> {
+  io::stdin().lines().filter(|line| match line {
+    Ok(line) => !line.trim().is_empty() && !line.starts_with('#'),
+    Err(_) => true
+  }).map(|line| line.map(|line| line.trim_end_matches('\r').to_owned()))
+}
+
+/// Prints a line like `{"command":"seed","response":"Seed: [42]","ms":1.234}`, or with `"error"` instead of `"response"` if the command failed.
+fn print_json(command: &str, result: &Result<String, CommandError>, elapsed: Duration, strip_codes: bool) -> io::Result<()> {
+  let mut line = format!("{{\"command\":{}", json_string(command));
+  match result {
+    Ok(response) if strip_codes => write!(line, ",\"response\":{}", json_string(&formatting::strip_codes(response))),
+    Ok(response) => write!(line, ",\"response\":{}", json_string(response)),
+    Err(e) => write!(line, ",\"error\":{}", json_string(&e.to_string()))
+  }.expect("writing to a string cannot fail");
+  writeln!(io::stdout().lock(), "{},\"ms\":{:.3}}}", line, elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Quotes and escapes `s` as a JSON string.
+fn json_string(s: &str) -> String {
+  let mut quoted = String::with_capacity(s.len() + 2);
+  quoted.push('"');
+  for c in s.chars() {
+    match c {
+      '"' => quoted.push_str("\\\""),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      '\r' => quoted.push_str("\\r"),
+      '\t' => quoted.push_str("\\t"),
+      c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).expect("writing to a string cannot fail"),
+      c => quoted.push(c)
+    }
+  }
+  quoted.push('"');
+  quoted
+}
\ No newline at end of file
@@ -0,0 +1,100 @@
+use std::{fmt::{self, Display, Formatter}, mem, ops::Deref};
+
+/// A buffer that holds the response to a command, reused from one command to the next. See [`RconClient::send_command_into`](crate::RconClient::send_command_into).
+/// 
+/// Once the buffer has grown to fit the longest response, sending more commands into it does not allocate.
+/// ```
+/// # use std::error::Error;
+/// # use mc_rcon::{RconClient, ResponseBuffer};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(feature = "testing")] {
+/// # use mc_rcon::testing::ScriptedServer;
+/// let server = ScriptedServer::new("password", |command| format!("you said {}", command))?;
+/// let client = RconClient::connect(server.local_addr())?;
+/// client.log_in("password")?;
+/// let mut buf = ResponseBuffer::new();
+/// assert_eq!(client.send_command_into("hello", &mut buf)?, "you said hello");
+/// let capacity = buf.capacity();
+/// assert_eq!(client.send_command_into("goodbye", &mut buf)?, "you said goodbye");
+/// assert_eq!(buf.capacity(), capacity); // the same memory was reused
+/// assert_eq!(buf.as_str(), "you said goodbye");
+/// drop(client);
+/// server.join()?;
+/// # }
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ResponseBuffer(String);
+
+impl ResponseBuffer {
+  
+  /// Creates an empty buffer, which does not allocate until a response is written into it.
+  pub fn new() -> ResponseBuffer {
+    ResponseBuffer(String::new())
+  }
+  
+  /// Creates an empty buffer with room for a response of `capacity` bytes.
+  pub fn with_capacity(capacity: usize) -> ResponseBuffer {
+    ResponseBuffer(String::with_capacity(capacity))
+  }
+  
+  /// Returns the last response written into this buffer, or the empty string if there is none.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+  
+  /// Returns the number of bytes this buffer can hold without allocating.
+  pub fn capacity(&self) -> usize {
+    self.0.capacity()
+  }
+  
+  /// Takes the bytes out of this buffer to read a response into, leaving it empty.
+  pub(crate) fn take_bytes(&mut self) -> Vec<u8> {
+    let mut bytes = mem::take(&mut self.0).into_bytes();
+    bytes.clear();
+    bytes
+  }
+  
+  /// Replaces the contents of this buffer with `text`, returning it.
+  pub(crate) fn set(&mut self, text: String) -> &str {
+    self.0 = text;
+    &self.0
+  }
+  
+}
+
+impl Deref for ResponseBuffer {
+  
+  type Target = str;
+  
+  fn deref(&self) -> &str {
+    &self.0
+  }
+  
+}
+
+impl Display for ResponseBuffer {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    Display::fmt(&self.0, f)
+  }
+  
+}
+
+impl From<String> for ResponseBuffer {
+  
+  fn from(text: String) -> Self {
+    ResponseBuffer(text)
+  }
+  
+}
+
+impl From<ResponseBuffer> for String {
+  
+  fn from(buffer: ResponseBuffer) -> Self {
+    buffer.0
+  }
+  
+}
\ No newline at end of file
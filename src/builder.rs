@@ -0,0 +1,200 @@
+use std::{fmt::{self, Debug, Formatter}, io, net::ToSocketAddrs, time::Duration};
+
+use crate::{Dialect, LogInError, RconClient, HEADER_LEN, MAX_INCOMING_PAYLOAD_LEN, MAX_OUTGOING_PAYLOAD_LEN, MAX_PACKET_LEN, PROTOCOL_MAX_PAYLOAD_LEN};
+
+/// A builder for an [`RconClient`] with custom settings,
+/// as returned by [`RconClient::with_buffer_sizes`] or [`RconClientBuilder::from_config`].
+#[derive(Debug, Clone)]
+pub struct RconClientBuilder {
+  
+  max_incoming: usize,
+  max_outgoing: usize,
+  dialect: Dialect,
+  read_timeout: Option<Duration>,
+  sentinel_command: Option<String>
+  
+}
+
+/// Where and how to connect to a server, for storing in an application's configuration file.
+/// 
+/// With the `serde` feature, this can be deserialized from any format that serde supports, for example TOML:
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// # use mc_rcon::ConnectionConfig;
+/// # 
+/// #[derive(serde::Deserialize)]
+/// struct AppConfig {
+///   rcon: ConnectionConfig
+/// }
+/// 
+/// let config: AppConfig = toml::from_str("[rcon]\naddr = \"localhost:25575\"\npassword = \"hunter2\"\nread_timeout_secs = 30\n").unwrap();
+/// assert_eq!(config.rcon.addr, "localhost:25575");
+/// assert_eq!(config.rcon.read_timeout_secs, Some(30));
+/// assert_eq!(config.rcon.sentinel_command, None);
+/// # }
+/// ```
+/// 
+/// The password is not shown by the [`Debug`] implementation, like a [`Password`](crate::Password).
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionConfig {
+  
+  /// The address of the server, for example `localhost:25575`.
+  pub addr: String,
+  /// The password to log in with.
+  pub password: String,
+  /// The [read timeout](RconClient::set_read_timeout) in seconds, if any.
+  pub read_timeout_secs: Option<u64>,
+  /// The [sentinel command](RconClient::set_sentinel_command), if not the default.
+  pub sentinel_command: Option<String>
+  
+}
+
+impl Debug for ConnectionConfig {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ConnectionConfig")
+      .field("addr", &self.addr)
+      .field("password", &format_args!("[REDACTED]"))
+      .field("read_timeout_secs", &self.read_timeout_secs)
+      .field("sentinel_command", &self.sentinel_command)
+      .finish()
+  }
+  
+}
+
+impl ConnectionConfig {
+  
+  /// Connects to the server and logs in as this configuration describes.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::ConnectionConfig;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let config = ConnectionConfig { addr: "localhost:25575".into(), password: "hunter2".into(), read_timeout_secs: Some(30), sentinel_command: None };
+  /// let client = config.connect()?;
+  /// println!("{}", client.send_command("seed")?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClientBuilder::connect`] and [`RconClient::log_in`].
+  /// 
+  /// # Panics
+  /// 
+  /// Panics in the same cases as [`RconClientBuilder::from_config`].
+  pub fn connect(&self) -> Result<RconClient, LogInError> {
+    let client = RconClientBuilder::from_config(self).connect(&*self.addr)?;
+    client.log_in(&*self.password)?;
+    Ok(client)
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Returns a builder for a client that considers response packets of `max_incoming` payload bytes full,
+  /// and sends passwords and commands of up to `max_outgoing` bytes.
+  /// 
+  /// These replace [`MAX_INCOMING_PAYLOAD_LEN`] and the [outgoing payload limit](RconClient::set_max_outgoing_payload)
+  /// for the built client only.
+  /// Each response is read into a buffer of at least `max_incoming` bytes,
+  /// so a proxy in front of a server that sends huge packets can raise it to read each response without reallocating,
+  /// and a client on a memory-constrained device can lower it to use smaller buffers.
+  /// Note that a packet shorter than `max_incoming` is taken to be the end of its response,
+  /// so it must not be lower than the length of the server's fragments.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::with_buffer_sizes(65536, 1446).connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("{}", client.send_command("help")?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `max_incoming` is 0 or too long for a packet's length field,
+  /// or if `max_outgoing` is greater than [`PROTOCOL_MAX_PAYLOAD_LEN`].
+  pub fn with_buffer_sizes(max_incoming: usize, max_outgoing: usize) -> RconClientBuilder {
+    assert!(max_incoming > 0, "incoming payload length must be at least 1 byte");
+    assert!(max_incoming <= MAX_PACKET_LEN as usize - HEADER_LEN, "incoming payload length must fit in a packet's length field");
+    assert!(max_outgoing <= PROTOCOL_MAX_PAYLOAD_LEN, "outgoing payload limit must be no greater than {} bytes", PROTOCOL_MAX_PAYLOAD_LEN);
+    RconClientBuilder { max_incoming, max_outgoing, dialect: Dialect::Minecraft, read_timeout: None, sentinel_command: None }
+  }
+  
+}
+
+impl RconClientBuilder {
+  
+  /// Returns a builder for a client with the read timeout and sentinel command from the given configuration,
+  /// and the default buffer sizes.
+  /// 
+  /// The configuration's address and password are not used by the builder:
+  /// pass the address to [`connect`](RconClientBuilder::connect) and log in with the password,
+  /// or use [`ConnectionConfig::connect`] to do all of that at once.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if the read timeout is zero, or if the sentinel command is longer than [`MAX_OUTGOING_PAYLOAD_LEN`].
+  pub fn from_config(config: &ConnectionConfig) -> RconClientBuilder {
+    let builder = RconClient::with_buffer_sizes(MAX_INCOMING_PAYLOAD_LEN, MAX_OUTGOING_PAYLOAD_LEN).read_timeout(config.read_timeout_secs.map(Duration::from_secs));
+    match &config.sentinel_command {
+      Some(command) => builder.sentinel_command(command.clone()),
+      None => builder
+    }
+  }
+  
+  /// Sets the dialect of RCON that the built client speaks, which is [`Dialect::Minecraft`] by default.
+  /// 
+  /// Unlike [`RconClient::connect_with_dialect`], this does not change the outgoing payload limit.
+  pub fn dialect(self, dialect: Dialect) -> RconClientBuilder {
+    RconClientBuilder { dialect, ..self }
+  }
+  
+  /// Sets the [read timeout](RconClient::set_read_timeout) of the built client, which is `None` by default.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `timeout` is zero.
+  pub fn read_timeout(self, timeout: Option<Duration>) -> RconClientBuilder {
+    assert!(timeout != Some(Duration::ZERO), "read timeout must be non-zero");
+    RconClientBuilder { read_timeout: timeout, ..self }
+  }
+  
+  /// Sets the [sentinel command](RconClient::set_sentinel_command) of the built client.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `command` is longer than [`MAX_OUTGOING_PAYLOAD_LEN`].
+  pub fn sentinel_command(self, command: String) -> RconClientBuilder {
+    assert!(command.len() <= MAX_OUTGOING_PAYLOAD_LEN, "sentinel command must be no longer than {} bytes", MAX_OUTGOING_PAYLOAD_LEN);
+    RconClientBuilder { sentinel_command: Some(command), ..self }
+  }
+  
+  /// Constructs the client and connects to a server at the given address.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::connect`].
+  pub fn connect<A: ToSocketAddrs>(self, server_addr: A) -> io::Result<RconClient> {
+    let mut client = RconClient::connect_with_dialect(server_addr, self.dialect)?;
+    client.max_incoming_payload = self.max_incoming;
+    client.max_outgoing_payload = self.max_outgoing;
+    client.set_read_timeout(self.read_timeout)?;
+    if let Some(command) = self.sentinel_command {
+      client.set_sentinel_command(command);
+    }
+    Ok(client)
+  }
+  
+}
\ No newline at end of file
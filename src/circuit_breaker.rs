@@ -0,0 +1,137 @@
+use std::{sync::Mutex, time::{Duration, Instant}};
+
+use crate::{CommandError, RconClient};
+
+/// The state of a [`CircuitBreakerRconClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+  
+  /// Commands are sent normally, and this many of the most recent commands failed in a row.
+  Closed {
+    
+    /// The number of consecutive failures so far.
+    consecutive_failures: u32
+    
+  },
+  /// Commands are not sent, until the given time.
+  Open {
+    
+    /// When the next command may be sent to check whether the server has recovered.
+    until: Instant
+    
+  },
+  /// A single command is being sent to check whether the server has recovered, and other commands are not sent until it completes.
+  HalfOpen
+  
+}
+
+/// A wrapper around an [`RconClient`] that stops sending commands for a while after several consecutive failures.
+/// 
+/// A server that is overloaded or in an error state may fail every command,
+/// and retrying immediately only adds to its load.
+/// After `failure_threshold` consecutive commands fail with an I/O error (such as timing out),
+/// this client fails every command with [`CommandError::CircuitOpen`] without sending it, until `recovery_timeout` has passed.
+/// It then sends the next command as a trial: if it succeeds, commands are sent normally again,
+/// and if it fails, the circuit opens for another `recovery_timeout`.
+/// 
+/// Only I/O errors count as failures, since other errors are caused by the commands or the session rather than the server's health.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::{error::Error, time::Duration};
+/// # use mc_rcon::{RconClient, CircuitBreakerRconClient, CommandError};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = RconClient::connect("localhost:25575")?;
+/// client.log_in("SuperSecurePassword")?;
+/// let client = CircuitBreakerRconClient::new(client, 5, Duration::from_secs(30));
+/// loop {
+///   match client.send_command("list") {
+///     Ok(players) => println!("{}", players),
+///     Err(CommandError::CircuitOpen) => {} // the server is struggling, so give it some time
+///     Err(e) => eprintln!("{}", e)
+///   }
+///   std::thread::sleep(Duration::from_secs(1));
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CircuitBreakerRconClient {
+  
+  inner: RconClient,
+  failure_threshold: u32,
+  recovery_timeout: Duration,
+  state: Mutex<CircuitState>
+  
+}
+
+impl CircuitBreakerRconClient {
+  
+  /// Wraps `inner` in a circuit breaker that opens after `failure_threshold` consecutive failures, for `recovery_timeout` at a time.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `failure_threshold` is 0.
+  pub fn new(inner: RconClient, failure_threshold: u32, recovery_timeout: Duration) -> Self {
+    assert!(failure_threshold > 0, "failure threshold must be at least 1");
+    Self { inner, failure_threshold, recovery_timeout, state: Mutex::new(CircuitState::Closed { consecutive_failures: 0 }) }
+  }
+  
+  /// Returns the wrapped client, which can be used directly to log in or to send commands regardless of the circuit's state.
+  pub fn inner(&self) -> &RconClient {
+    &self.inner
+  }
+  
+  /// Consumes this wrapper and returns the wrapped client.
+  pub fn into_inner(self) -> RconClient {
+    self.inner
+  }
+  
+  /// Returns the current state of the circuit.
+  pub fn state(&self) -> CircuitState {
+    *self.state.lock().unwrap_or_else(|e| e.into_inner())
+  }
+  
+  /// Closes the circuit, so that commands are sent normally again regardless of previous failures.
+  pub fn reset(&self) {
+    *self.state.lock().unwrap_or_else(|e| e.into_inner()) = CircuitState::Closed { consecutive_failures: 0 };
+  }
+  
+  /// Sends the given command with [`RconClient::send_command`], unless the circuit is open.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::send_command`],
+  /// and with [`CommandError::CircuitOpen`] (without sending anything) if the circuit is open,
+  /// or if another thread is currently sending a trial command.
+  pub fn send_command(&self, command: &str) -> Result<String, CommandError> {
+    self.before_send()?;
+    let result = self.inner.send_command(command);
+    self.after_send(matches!(result, Err(CommandError::IO(_))));
+    result
+  }
+  
+  fn before_send(&self) -> Result<(), CommandError> {
+    let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+    match *state {
+      CircuitState::Closed { .. } => Ok(()),
+      CircuitState::Open { until } if Instant::now() >= until => {
+        *state = CircuitState::HalfOpen;
+        Ok(())
+      }
+      CircuitState::Open { .. } | CircuitState::HalfOpen => Err(CommandError::CircuitOpen)
+    }
+  }
+  
+  fn after_send(&self, failed: bool) {
+    let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+    *state = match (*state, failed) {
+      (_, false) => CircuitState::Closed { consecutive_failures: 0 },
+      (CircuitState::Closed { consecutive_failures }, true) if consecutive_failures + 1 < self.failure_threshold => {
+        CircuitState::Closed { consecutive_failures: consecutive_failures + 1 }
+      }
+      (_, true) => CircuitState::Open { until: Instant::now() + self.recovery_timeout }
+    };
+  }
+  
+}
\ No newline at end of file
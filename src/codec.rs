@@ -0,0 +1,91 @@
+use std::io;
+
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{PacketDecodeError, RconPacket, HEADER_LEN, I32_LEN, PROTOCOL_MAX_PAYLOAD_LEN};
+
+/// A [`tokio_util`](https://docs.rs/tokio-util) codec that frames a byte stream into [`RconPacket`]s, available with the `tokio` feature.
+/// 
+/// Together with the `tokio` I/O traits that [`AsyncRconClient`](crate::AsyncRconClient) implements,
+/// this allows building custom protocol handling on top of a connection:
+/// ```no_run
+/// # use std::error::Error;
+/// # use futures_util::{SinkExt, StreamExt};
+/// # use mc_rcon::{RconCodec, RconPacket, TokioRconClient};
+/// # use tokio_util::codec::Framed;
+/// # 
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let mut client = TokioRconClient::connect("localhost:25575").await?;
+/// client.log_in("SuperSecurePassword").await?;
+/// let mut framed = Framed::new(client, RconCodec::new());
+/// framed.send(RconPacket::command("seed")).await?;
+/// if let Some(response) = framed.next().await {
+///   println!("{}", String::from_utf8_lossy(&response?.payload));
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+/// 
+/// Decoding fails with an error of kind [`InvalidData`](io::ErrorKind::InvalidData) if a packet is malformed
+/// (see [`RconPacket::decode`]) or its length field is too large for the protocol.
+/// ```
+/// # use bytes::BytesMut;
+/// # use mc_rcon::{RconCodec, RconPacket};
+/// # use tokio_util::codec::{Decoder, Encoder};
+/// let mut codec = RconCodec::new();
+/// let mut bytes = BytesMut::new();
+/// codec.encode(RconPacket::command("seed"), &mut bytes)?;
+/// let mut partial = bytes.split_to(5);
+/// assert_eq!(codec.decode(&mut partial)?, None);
+/// partial.unsplit(bytes);
+/// assert_eq!(codec.decode(&mut partial)?, Some(RconPacket::command("seed")));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RconCodec {}
+
+impl RconCodec {
+  
+  /// Construct an `RconCodec`.
+  pub fn new() -> RconCodec {
+    RconCodec {}
+  }
+  
+}
+
+impl Decoder for RconCodec {
+  
+  type Item = RconPacket;
+  type Error = io::Error;
+  
+  fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<RconPacket>> {
+    let Some(length) = src.get(..I32_LEN) else {
+      return Ok(None)
+    };
+    let length = i32::from_le_bytes(length.try_into().expect("length field is 4 bytes"));
+    let total_len = match usize::try_from(length) {
+      Ok(length) if (HEADER_LEN..=HEADER_LEN + PROTOCOL_MAX_PAYLOAD_LEN).contains(&length) => I32_LEN + length,
+      _ => Err(io::Error::new(io::ErrorKind::InvalidData, PacketDecodeError::InvalidLength))?
+    };
+    if src.len() < total_len {
+      src.reserve(total_len - src.len());
+      return Ok(None)
+    }
+    let frame = src.split_to(total_len);
+    RconPacket::decode(&frame).map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+  }
+  
+}
+
+impl Encoder<RconPacket> for RconCodec {
+  
+  type Error = io::Error;
+  
+  fn encode(&mut self, packet: RconPacket, dst: &mut BytesMut) -> io::Result<()> {
+    dst.extend_from_slice(&packet.encode());
+    Ok(())
+  }
+  
+}
\ No newline at end of file
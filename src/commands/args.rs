@@ -0,0 +1,119 @@
+//! Local validation of the arguments given to the typed command wrappers.
+
+use crate::CommandError;
+
+use super::{Component, ItemSpec, ItemSyntax, Selector};
+
+/// Checks that `target` is a player name, UUID, or selector that can be safely inserted into a command.
+/// 
+/// Selectors (anything starting with `@`) are passed through verbatim apart from rejecting line breaks,
+/// since their arguments may legitimately contain spaces and quotes.
+pub(crate) fn target(target: &str) -> Result<&str, CommandError> {
+  let valid = if target.starts_with('@') {
+    !target.contains(['\n', '\r'])
+  } else {
+    !target.is_empty() && !target.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+  };
+  if valid {
+    Ok(target)
+  } else {
+    Err(CommandError::InvalidArgument(format!("invalid target: {:?}", target)))
+  }
+}
+
+/// Converts `selector` to a command argument, checking that it is valid and can be safely inserted into a command.
+pub(crate) fn selector(selector: impl Into<Selector>) -> Result<String, CommandError> {
+  selector.into().argument()
+}
+
+/// Checks that `name` is a valid scoreboard objective name, which may only contain letters, digits, and `_-.+`.
+pub(crate) fn objective(name: &str) -> Result<&str, CommandError> {
+  if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "_-.+".contains(c)) {
+    Ok(name)
+  } else {
+    Err(CommandError::InvalidArgument(format!("invalid objective name: {:?}", name)))
+  }
+}
+
+/// Checks that `id` is a valid resource location, like `minecraft:stone` or `stone`.
+pub(crate) fn resource_location(id: &str) -> Result<&str, CommandError> {
+  let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+  let valid_namespace = !namespace.is_empty() && namespace.chars().all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '.' | '-'));
+  let valid_path = !path.is_empty() && path.chars().all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '.' | '-' | '/'));
+  if valid_namespace && valid_path {
+    Ok(id)
+  } else {
+    Err(CommandError::InvalidArgument(format!("invalid resource location: {:?}", id)))
+  }
+}
+
+/// Converts `item` to a command argument in the given syntax, checking that its id is a valid resource location,
+/// that it has data in that syntax if it has any, and that the data can be safely inserted into a command without ending the argument early.
+pub(crate) fn item(item: &ItemSpec, syntax: ItemSyntax) -> Result<String, CommandError> {
+  resource_location(&item.id)?;
+  match item.data(syntax) {
+    None if item.has_data() => Err(CommandError::InvalidArgument(format!("item has no {} for this server's version: {}", syntax, item.id))),
+    Some(data) if !is_balanced(data) => Err(CommandError::InvalidArgument(format!("invalid item data: {:?}", data))),
+    _ => Ok(item.to_argument(syntax))
+  }
+}
+
+/// Returns whether the brackets and quotes in `data` are balanced, so that it ends where the surrounding brackets do,
+/// and it contains no line breaks.
+fn is_balanced(data: &str) -> bool {
+  let mut closers = Vec::new();
+  let mut quote = None;
+  let mut chars = data.chars();
+  while let Some(c) = chars.next() {
+    match (quote, c) {
+      (_, '\n' | '\r') => return false,
+      (Some(_), '\\') => {
+        chars.next();
+      }
+      (Some(q), c) if c == q => quote = None,
+      (Some(_), _) => {}
+      (None, '"' | '\'') => quote = Some(c),
+      (None, '[') => closers.push(']'),
+      (None, '{') => closers.push('}'),
+      (None, '(') => closers.push(')'),
+      (None, ']' | '}' | ')') => {
+        if closers.pop() != Some(c) {
+          return false
+        }
+      }
+      (None, _) => {}
+    }
+  }
+  quote.is_none() && closers.is_empty()
+}
+
+/// Checks that `predicate` is a block id or block tag, optionally followed by block states and NBT,
+/// that can be safely inserted into a command without ending the argument early.
+pub(crate) fn block_predicate(predicate: &str) -> Result<&str, CommandError> {
+  let id = predicate.strip_prefix('#').unwrap_or(predicate);
+  let (id, data) = id.split_at(id.find(['[', '{']).unwrap_or(id.len()));
+  let valid_data = data.is_empty() || (data.ends_with([']', '}']) && is_balanced(data));
+  if resource_location(id).is_ok() && valid_data {
+    Ok(predicate)
+  } else {
+    Err(CommandError::InvalidArgument(format!("invalid block predicate: {:?}", predicate)))
+  }
+}
+
+/// Checks that `path` is an NBT path that can be safely inserted into a command.
+pub(crate) fn nbt_path(path: &str) -> Result<&str, CommandError> {
+  if !path.trim().is_empty() && !path.contains(['\n', '\r']) {
+    Ok(path)
+  } else {
+    Err(CommandError::InvalidArgument(format!("invalid NBT path: {:?}", path)))
+  }
+}
+
+/// Checks that `component` can be safely inserted into a command.
+pub(crate) fn component(component: &Component) -> Result<&Component, CommandError> {
+  if component.is_valid() {
+    Ok(component)
+  } else {
+    Err(CommandError::InvalidArgument(format!("invalid text component: {}", component)))
+  }
+}
\ No newline at end of file
@@ -0,0 +1,184 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{CommandError, RconClient};
+
+use super::parse::unexpected;
+
+/// The text between the target and the source of each ban in `banlist` responses.
+const BANNED_BY: &str = " was banned by ";
+
+/// Which bans to list with [`RconClient::banlist`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BanlistKind {
+  
+  /// Bans of player names.
+  Players,
+  /// Bans of IP addresses.
+  Ips
+  
+}
+
+impl Display for BanlistKind {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      BanlistKind::Players => write!(f, "players"),
+      BanlistKind::Ips => write!(f, "ips")
+    }
+  }
+  
+}
+
+/// A ban listed by [`RconClient::banlist`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BanEntry {
+  
+  /// The banned player name or IP address.
+  pub target: String,
+  /// Who issued the ban, like a player name, `Server`, or `Rcon`.
+  pub source: String,
+  /// The reason given for the ban, which may span several lines.
+  pub reason: String
+  
+}
+
+impl RconClient {
+  
+  /// Lists the player or IP bans on the server with `banlist`.
+  /// 
+  /// Ban lists are one of the few responses that routinely span several packets;
+  /// they are reassembled like any other response (see [`send_command`](RconClient::send_command)).
+  /// 
+  /// Minecraft versions before 1.20.2 do not separate the bans in the response at all,
+  /// in which case the boundary between each reason and the next target is guessed from the characters allowed in targets;
+  /// reasons ending in such characters may therefore be cut short in those versions.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::BanlistKind};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// for ban in client.banlist(BanlistKind::Players)? {
+  ///   println!("{} was banned by {}: {}", ban.target, ban.source, ban.reason);
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// Responses from servers before and since 1.20.2 are handled like this:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::{BanEntry, BanlistKind}};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let many: String = (0..200).map(|i| format!("\nPlayer{} was banned by Server: Banned by an operator.", i)).collect();
+  /// let mut responses = [
+  ///   "There are 2 ban(s):\nSteve was banned by Rcon: Griefing\nspawn\nAlex was banned by Server: Banned by an operator.".to_owned(),
+  ///   "There are 2 ban(s):Steve was banned by Rcon: Griefing!Alex was banned by Server: Banned by an operator.".to_owned(), // before 1.20.2
+  ///   "There are 1 ban(s):\n192.168.0.7 was banned by Steve: Spam".to_owned(),
+  ///   format!("There are 200 ban(s):{}", many), // several packets long
+  ///   "There are no bans".to_owned(),
+  ///   "There are 2 ban(s):\nsomething else".to_owned()
+  /// ].into_iter();
+  /// let server = ScriptedServer::new("password", move |command| match command {
+  ///   "seed" => "Seed: [1]".to_owned(), // sent by the client to find the end of a long response
+  ///   _ => responses.next().unwrap_or_default()
+  /// })?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// let ban = |target: &str, source: &str, reason: &str| BanEntry { target: target.into(), source: source.into(), reason: reason.into() };
+  /// let expected = [ban("Steve", "Rcon", "Griefing\nspawn"), ban("Alex", "Server", "Banned by an operator.")];
+  /// assert_eq!(client.banlist(BanlistKind::Players)?, expected);
+  /// let expected = [ban("Steve", "Rcon", "Griefing!"), ban("Alex", "Server", "Banned by an operator.")];
+  /// assert_eq!(client.banlist(BanlistKind::Players)?, expected);
+  /// assert_eq!(client.banlist(BanlistKind::Ips)?, [ban("192.168.0.7", "Steve", "Spam")]);
+  /// let bans = client.banlist(BanlistKind::Players)?;
+  /// assert_eq!(bans.len(), 200);
+  /// assert_eq!(bans[199], ban("Player199", "Server", "Banned by an operator."));
+  /// assert_eq!(client.banlist(BanlistKind::Players)?, []);
+  /// assert!(matches!(client.banlist(BanlistKind::Players), Err(CommandError::UnexpectedResponse(_))));
+  /// assert!(client.stats().fragmented_responses > 0);
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the response cannot be parsed.
+  pub fn banlist(&self, kind: BanlistKind) -> Result<Vec<BanEntry>, CommandError> {
+    let response = self.send_supported(&format!("banlist {}", kind))?;
+    // "There are no bans", or "There are 2 ban(s):" followed by "Steve was banned by Server: Banned by an operator." for each ban
+    if response.starts_with("There are no bans") {
+      return Ok(Vec::new())
+    }
+    let (_, list) = response.split_once("ban(s):").ok_or_else(|| unexpected(&response))?;
+    let entries = if list.contains('\n') { parse_lines(list) } else { parse_concatenated(list, kind) };
+    entries.ok_or_else(|| unexpected(&response))
+  }
+  
+}
+
+/// Parses bans that are each on their own line, where lines that do not start a ban continue the previous reason.
+fn parse_lines(list: &str) -> Option<Vec<BanEntry>> {
+  let mut entries: Vec<BanEntry> = Vec::new();
+  for line in list.lines() {
+    if let Some(entry) = parse_entry(line) {
+      entries.push(entry);
+    } else if let Some(entry) = entries.last_mut() {
+      entry.reason.push('\n');
+      entry.reason.push_str(line);
+    } else if !line.trim().is_empty() {
+      return None
+    }
+  }
+  Some(entries)
+}
+
+fn parse_entry(line: &str) -> Option<BanEntry> {
+  let (target, rest) = line.split_once(BANNED_BY)?;
+  let (source, reason) = rest.split_once(": ").or_else(|| Some((rest.strip_suffix(':')?, "")))?;
+  if target.is_empty() || target.contains(char::is_whitespace) {
+    return None
+  }
+  Some(BanEntry { target: target.to_owned(), source: source.to_owned(), reason: reason.to_owned() })
+}
+
+/// Parses bans that follow each other without any separator, as in Minecraft versions before 1.20.2.
+fn parse_concatenated(list: &str, kind: BanlistKind) -> Option<Vec<BanEntry>> {
+  let markers: Vec<usize> = list.match_indices(BANNED_BY).map(|(i, _)| i).collect();
+  let mut entries = Vec::new();
+  let mut target = list[..*markers.first()?].trim();
+  for (i, &marker) in markers.iter().enumerate() {
+    let end = markers.get(i + 1).copied().unwrap_or(list.len());
+    let (source, mut reason) = list[marker + BANNED_BY.len()..end].split_once(": ")?;
+    let next_target = if i + 1 < markers.len() {
+      // the next target is the longest run of characters allowed in targets at the end of this reason
+      let target_len = match kind {
+        BanlistKind::Players => reason.chars().rev().take_while(|&c| c.is_ascii_alphanumeric() || c == '_').take(16).count(),
+        BanlistKind::Ips => reason.chars().rev().take_while(|&c| c.is_ascii_hexdigit() || c == '.' || c == ':').count()
+      };
+      // those characters are all ASCII, so the number of characters is the number of bytes
+      let next_target = reason[reason.len() - target_len..].trim_start_matches(['.', ':']);
+      reason = &reason[..reason.len() - next_target.len()];
+      Some(next_target)
+    } else {
+      None
+    };
+    if target.is_empty() {
+      return None
+    }
+    entries.push(BanEntry { target: target.to_owned(), source: source.to_owned(), reason: reason.to_owned() });
+    target = next_target.unwrap_or_default();
+  }
+  Some(entries)
+}
\ No newline at end of file
@@ -0,0 +1,239 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::unexpected, Component, Selector};
+
+/// The color of a bossbar. See [`Bossbar::set_color`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BossbarColor {
+  
+  /// Blue.
+  Blue,
+  /// Green.
+  Green,
+  /// Pink.
+  Pink,
+  /// Purple.
+  Purple,
+  /// Red.
+  Red,
+  /// White, which is the default.
+  White,
+  /// Yellow.
+  Yellow
+  
+}
+
+impl Display for BossbarColor {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let color = match self {
+      BossbarColor::Blue => "blue",
+      BossbarColor::Green => "green",
+      BossbarColor::Pink => "pink",
+      BossbarColor::Purple => "purple",
+      BossbarColor::Red => "red",
+      BossbarColor::White => "white",
+      BossbarColor::Yellow => "yellow"
+    };
+    write!(f, "{}", color)
+  }
+  
+}
+
+/// The style of a bossbar, which is how many segments it is divided into. See [`Bossbar::set_style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BossbarStyle {
+  
+  /// A single continuous bar, which is the default.
+  Progress,
+  /// 6 segments.
+  Notched6,
+  /// 10 segments.
+  Notched10,
+  /// 12 segments.
+  Notched12,
+  /// 20 segments.
+  Notched20
+  
+}
+
+impl Display for BossbarStyle {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let style = match self {
+      BossbarStyle::Progress => "progress",
+      BossbarStyle::Notched6 => "notched_6",
+      BossbarStyle::Notched10 => "notched_10",
+      BossbarStyle::Notched12 => "notched_12",
+      BossbarStyle::Notched20 => "notched_20"
+    };
+    write!(f, "{}", style)
+  }
+  
+}
+
+/// A handle to a custom bossbar on the server, created with [`RconClient::bossbar_add`] or [`RconClient::bossbar`].
+/// 
+/// The handle only holds the bossbar's id; every method sends a `bossbar` command to change or query the bossbar on the server.
+/// Errors are the same as for [`send_command`](RconClient::send_command),
+/// with [`CommandError::UnexpectedResponse`] if the server does not confirm the change,
+/// such as when the bossbar does not exist.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::error::Error;
+/// # use mc_rcon::{RconClient, commands::BossbarColor};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = RconClient::connect("localhost:25575")?;
+/// client.log_in("SuperSecurePassword")?;
+/// let bar = client.bossbar_add("backup", "Backing up...".into())?;
+/// bar.set_color(BossbarColor::Green)?;
+/// bar.set_players("@a")?;
+/// bar.set_max(100)?;
+/// for progress in 0..=100 {
+///   bar.set_value(progress)?;
+///   // ...
+/// }
+/// bar.remove()?;
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bossbar<'a> {
+  
+  client: &'a RconClient,
+  id: String
+  
+}
+
+impl<'a> Bossbar<'a> {
+  
+  /// Returns the namespaced id of this bossbar, like `minecraft:backup`.
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+  
+  /// Sets the name displayed above the bossbar.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors with [`CommandError::InvalidArgument`] (without sending anything) if `name` could not be safely inserted into a command.
+  pub fn set_name(&self, name: Component) -> Result<(), CommandError> {
+    self.set("name", &args::component(&name)?.to_string())
+  }
+  
+  /// Sets the current value of the bossbar, which is shown relative to its [maximum](Bossbar::set_max).
+  /// 
+  /// # Errors
+  /// 
+  /// Errors with [`CommandError::InvalidArgument`] (without sending anything) if `value` is too large for Minecraft.
+  pub fn set_value(&self, value: u32) -> Result<(), CommandError> {
+    self.set("value", &bossbar_int(value)?.to_string())
+  }
+  
+  /// Sets the maximum value of the bossbar.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors with [`CommandError::InvalidArgument`] (without sending anything) if `max` is 0 or too large for Minecraft.
+  pub fn set_max(&self, max: u32) -> Result<(), CommandError> {
+    if max == 0 {
+      Err(CommandError::InvalidArgument("bossbar maximum must be at least 1".to_owned()))?
+    }
+    self.set("max", &bossbar_int(max)?.to_string())
+  }
+  
+  /// Sets the color of the bossbar.
+  pub fn set_color(&self, color: BossbarColor) -> Result<(), CommandError> {
+    self.set("color", &color.to_string())
+  }
+  
+  /// Sets the style of the bossbar.
+  pub fn set_style(&self, style: BossbarStyle) -> Result<(), CommandError> {
+    self.set("style", &style.to_string())
+  }
+  
+  /// Sets which players can see the bossbar to those matched by `players`, replacing any previous players.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors with [`CommandError::InvalidArgument`] (without sending anything) if `players` could not be safely inserted into a command.
+  pub fn set_players(&self, players: impl Into<Selector>) -> Result<(), CommandError> {
+    self.set("players", &args::selector(players)?)
+  }
+  
+  /// Sets whether the bossbar is visible to its players.
+  pub fn set_visible(&self, visible: bool) -> Result<(), CommandError> {
+    self.set("visible", if visible { "true" } else { "false" })
+  }
+  
+  /// Gets the current value of the bossbar.
+  pub fn get_value(&self) -> Result<u32, CommandError> {
+    let response = self.client.send_supported(&format!("bossbar get {} value", self.id))?;
+    // "Custom bossbar [Backup] has a value of 50"
+    let (_, value) = response.rsplit_once(" has a value of ").ok_or_else(|| unexpected(&response))?;
+    value.trim().parse().map_err(|_| unexpected(&response))
+  }
+  
+  /// Removes the bossbar from the server.
+  pub fn remove(self) -> Result<(), CommandError> {
+    // "Removed custom bossbar [Backup]"
+    self.client.expect_prefix(&format!("bossbar remove {}", self.id), "Removed custom bossbar ")
+  }
+  
+  fn set(&self, property: &str, value: &str) -> Result<(), CommandError> {
+    let response = self.client.send_supported(&format!("bossbar set {} {} {}", self.id, property, value))?;
+    // "Custom bossbar [Backup] has changed value to 50", "Custom bossbar [Backup] is now visible",
+    // or "Nothing changed. That's already the value of this bossbar" (and similar) if the property already had the value
+    if response.starts_with("Custom bossbar ") || response.starts_with("Nothing changed") {
+      Ok(())
+    } else {
+      Err(unexpected(&response))
+    }
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Creates a custom bossbar with the given id and name with `bossbar add`, and returns a handle to it.
+  /// 
+  /// If `id` has no namespace, it is in the `minecraft` namespace.
+  /// The new bossbar is not shown to any players until [`Bossbar::set_players`] is called.
+  /// See [`Bossbar`] for an example.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `id` or `name` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server does not confirm that the bossbar was created,
+  /// such as when a bossbar with the same id already exists.
+  pub fn bossbar_add(&self, id: &str, name: Component) -> Result<Bossbar<'_>, CommandError> {
+    let bossbar = self.bossbar(id)?;
+    // "Created custom bossbar [Backup]"
+    self.expect_prefix(&format!("bossbar add {} {}", bossbar.id, args::component(&name)?), "Created custom bossbar ")?;
+    Ok(bossbar)
+  }
+  
+  /// Returns a handle to an existing custom bossbar with the given id, without sending anything.
+  /// 
+  /// If `id` has no namespace, it is in the `minecraft` namespace.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors with [`CommandError::InvalidArgument`] if `id` is not a valid resource location.
+  pub fn bossbar(&self, id: &str) -> Result<Bossbar<'_>, CommandError> {
+    let id = args::resource_location(id)?;
+    let id = if id.contains(':') { id.to_owned() } else { format!("minecraft:{}", id) };
+    Ok(Bossbar { client: self, id })
+  }
+  
+}
+
+/// Checks that `value` fits in the integer arguments of `bossbar set`.
+fn bossbar_int(value: u32) -> Result<i32, CommandError> {
+  i32::try_from(value).map_err(|_| CommandError::InvalidArgument(format!("bossbar value is too large: {}", value)))
+}
\ No newline at end of file
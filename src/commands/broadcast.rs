@@ -0,0 +1,103 @@
+use crate::{formatting::FORMATTING_CODE_PREFIX, CommandError, RconClient};
+
+use super::{args, Component, Selector};
+
+/// The longest chat message, in characters, that Minecraft accepts.
+const MAX_CHAT_MESSAGE_LEN: usize = 256;
+
+/// How [`RconClient::broadcast_long`] sends each message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BroadcastStyle {
+  
+  /// With `say`, which shows the message to every player prefixed with `[Rcon]` (or the server's name for RCON).
+  Say,
+  /// With `tellraw`, which shows the message as-is to the selected players.
+  Tellraw(Selector)
+  
+}
+
+impl RconClient {
+  
+  /// Sends `text` to players as one or more chat messages, returning how many messages were sent.
+  /// 
+  /// The text is split into messages at whitespace, so that each is short enough for both Minecraft's chat
+  /// and this client's [outgoing payload limit](RconClient::set_max_outgoing_payload); line breaks always start a new message.
+  /// Words too long to fit in one message are split between characters,
+  /// but never inside a [formatting code](crate::formatting).
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::{BroadcastStyle, Selector}};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let report = "...";
+  /// client.broadcast_long(report, BroadcastStyle::Tellraw(Selector::all()))?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// and with [`CommandError::InvalidArgument`] (without sending anything) if the selector could not be safely inserted into a command.
+  /// If an error occurs part way through, the messages before it have already been sent.
+  pub fn broadcast_long(&self, text: &str, style: BroadcastStyle) -> Result<usize, CommandError> {
+    let targets = match &style {
+      BroadcastStyle::Say => None,
+      BroadcastStyle::Tellraw(targets) => Some(args::selector(targets)?)
+    };
+    let command = |chunk: &str| match &targets {
+      None => format!("say {}", chunk),
+      Some(targets) => format!("tellraw {} {}", targets, Component::from(chunk))
+    };
+    let fits = |chunk: &str| chunk.chars().count() <= MAX_CHAT_MESSAGE_LEN && command(chunk).len() <= self.max_outgoing_payload();
+    let mut sent = 0;
+    for chunk in text.lines().flat_map(|line| chunks(line, fits)) {
+      self.send_supported(&command(&chunk))?;
+      sent += 1;
+    }
+    Ok(sent)
+  }
+  
+}
+
+/// Splits `line` into chunks that `fits` accepts, preferring to split at whitespace.
+fn chunks(line: &str, fits: impl Fn(&str) -> bool) -> Vec<String> {
+  let mut chunks = Vec::new();
+  let mut chunk = String::new();
+  for word in line.split_whitespace() {
+    let joined = if chunk.is_empty() { word.to_owned() } else { format!("{} {}", chunk, word) };
+    if fits(&joined) {
+      chunk = joined;
+      continue
+    }
+    if !chunk.is_empty() {
+      chunks.push(chunk);
+    }
+    chunk = String::new();
+    if fits(word) {
+      chunk = word.to_owned();
+      continue
+    }
+    // the word does not fit in a chunk of its own, so split it between characters, keeping formatting codes whole
+    let mut units = word.chars();
+    while let Some(c) = units.next() {
+      let mut unit = c.to_string();
+      if c == FORMATTING_CODE_PREFIX {
+        unit.extend(units.next());
+      }
+      if !fits(&(chunk.clone() + &unit)) && !chunk.is_empty() {
+        chunks.push(chunk);
+        chunk = String::new();
+      }
+      chunk += &unit;
+    }
+  }
+  if !chunk.is_empty() {
+    chunks.push(chunk);
+  }
+  chunks
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A text component, as used for names and messages shown to players.
+/// 
+/// When displayed, a component is written as JSON, as commands accept it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Component {
+  
+  /// Plain text, which is quoted as a JSON string.
+  Text(String),
+  /// A text component already written as JSON, which is used verbatim.
+  /// 
+  /// See <https://minecraft.wiki/w/Raw_JSON_text_format> for the format.
+  Json(String)
+  
+}
+
+impl Component {
+  
+  /// Returns whether this component can be safely inserted into a command.
+  /// 
+  /// Plain text is always quoted, but JSON must not contain line breaks.
+  pub(crate) fn is_valid(&self) -> bool {
+    match self {
+      Component::Text(_) => true,
+      Component::Json(json) => !json.trim().is_empty() && !json.contains(['\n', '\r'])
+    }
+  }
+  
+}
+
+impl Display for Component {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Component::Text(text) => {
+        write!(f, "\"")?;
+        for c in text.chars() {
+          match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?
+          }
+        }
+        write!(f, "\"")
+      }
+      Component::Json(json) => write!(f, "{}", json)
+    }
+  }
+  
+}
+
+impl From<&str> for Component {
+  
+  fn from(text: &str) -> Self {
+    Component::Text(text.to_owned())
+  }
+  
+}
+
+impl From<String> for Component {
+  
+  fn from(text: String) -> Self {
+    Component::Text(text)
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,238 @@
+use std::{fmt::{self, Display, Formatter}, ops::{Bound, RangeBounds}};
+
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::unexpected, BlockPos, Selector};
+
+/// A comparison between two scores, as used by [`Condition::score_compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoreComparison {
+  
+  /// The first score is less than the second, with `<`.
+  Less,
+  /// The first score is less than or equal to the second, with `<=`.
+  LessOrEqual,
+  /// The scores are equal, with `=`.
+  Equal,
+  /// The first score is greater than or equal to the second, with `>=`.
+  GreaterOrEqual,
+  /// The first score is greater than the second, with `>`.
+  Greater
+  
+}
+
+impl Display for ScoreComparison {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let comparison = match self {
+      ScoreComparison::Less => "<",
+      ScoreComparison::LessOrEqual => "<=",
+      ScoreComparison::Equal => "=",
+      ScoreComparison::GreaterOrEqual => ">=",
+      ScoreComparison::Greater => ">"
+    };
+    write!(f, "{}", comparison)
+  }
+  
+}
+
+/// A fact about the world that can be tested with `execute if`. See [`RconClient::check`].
+/// 
+/// Like [`Selector`], a condition's arguments are only checked when it is used,
+/// where a problem with any of them makes [`check`](RconClient::check) fail with [`CommandError::InvalidArgument`] without sending anything.
+/// 
+/// ```
+/// # use mc_rcon::commands::{BlockPos, Condition, ScoreComparison, Selector};
+/// let condition = Condition::block(BlockPos { x: 10, y: Some(64), z: -5 }, "minecraft:air");
+/// assert_eq!(condition.to_string(), "if block 10 64 -5 minecraft:air");
+/// let condition = Condition::entity(Selector::all().tag("builder")).negate();
+/// assert_eq!(condition.to_string(), "unless entity @a[tag=builder]");
+/// let condition = Condition::score_matches("Steve", "kills", 10..);
+/// assert_eq!(condition.to_string(), "if score Steve kills matches 10..");
+/// let condition = Condition::score_compare("Steve", "kills", ScoreComparison::Greater, "Alex", "kills");
+/// assert_eq!(condition.to_string(), "if score Steve kills > Alex kills");
+/// let condition = Condition::dimension("Steve", "minecraft:the_nether");
+/// assert_eq!(condition.to_string(), "as Steve at @s if dimension minecraft:the_nether");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Condition {
+  
+  context: String,
+  test: String,
+  negated: bool,
+  error: Option<String>
+  
+}
+
+impl Condition {
+  
+  fn new(context: String, test: String, errors: impl IntoIterator<Item = Result<(), CommandError>>) -> Self {
+    // keep the first problem, like Selector does
+    let error = errors.into_iter().find_map(Result::err).map(|e| match e {
+      CommandError::InvalidArgument(error) => error,
+      e => e.to_string()
+    });
+    Self { context, test, negated: false, error }
+  }
+  
+  /// Tests whether `target` matches any entities, with `if entity`.
+  /// 
+  /// With [`check_count`](RconClient::check_count), the result is the number of matching entities.
+  pub fn entity(target: impl Into<Selector>) -> Self {
+    let target = target.into();
+    Self::new(String::new(), format!("entity {}", target), [target.argument().map(drop)])
+  }
+  
+  /// Tests whether the block at `pos` matches `predicate`, with `if block`.
+  /// 
+  /// The predicate is a block id or a block tag starting with `#`, optionally followed by block states and NBT,
+  /// like `minecraft:air`, `#minecraft:logs[axis=y]`, or `minecraft:chest{Items:[]}`.
+  /// The position's y coordinate must be known.
+  pub fn block(pos: BlockPos, predicate: &str) -> Self {
+    let y = match pos.y {
+      Some(_) => Ok(()),
+      None => Err(CommandError::InvalidArgument("block position needs a y coordinate".to_owned()))
+    };
+    Self::new(String::new(), format!("block {} {}", pos, predicate), [y, args::block_predicate(predicate).map(drop)])
+  }
+  
+  /// Tests whether the score of `target` for `objective` is in the given range, with `if score ... matches`.
+  /// 
+  /// The range must not be empty, and at least one bound must be given.
+  pub fn score_matches(target: impl Into<Selector>, objective: &str, range: impl RangeBounds<i32>) -> Self {
+    let target = target.into();
+    let (range, range_error) = match score_range(range.start_bound().cloned(), range.end_bound().cloned()) {
+      Ok(range) => (range, Ok(())),
+      Err(e) => (String::new(), Err(CommandError::InvalidArgument(format!("invalid score range: {}", e))))
+    };
+    let test = format!("score {} {} matches {}", target, objective, range);
+    Self::new(String::new(), test, [target.argument().map(drop), args::objective(objective).map(drop), range_error])
+  }
+  
+  /// Tests how the score of `target` for `objective` compares to the score of `source` for `source_objective`, with `if score`.
+  pub fn score_compare(target: impl Into<Selector>, objective: &str, comparison: ScoreComparison, source: impl Into<Selector>, source_objective: &str) -> Self {
+    let (target, source) = (target.into(), source.into());
+    let test = format!("score {} {} {} {} {}", target, objective, comparison, source, source_objective);
+    let errors = [target.argument().map(drop), args::objective(objective).map(drop), source.argument().map(drop), args::objective(source_objective).map(drop)];
+    Self::new(String::new(), test, errors)
+  }
+  
+  /// Tests whether `target` is in the given dimension, like `minecraft:the_nether`, with `as <target> at @s if dimension`.
+  /// 
+  /// `target` should select a single entity; if it selects none, the test fails.
+  pub fn dimension(target: impl Into<Selector>, dimension: &str) -> Self {
+    let target = target.into();
+    let context = format!("as {} at @s ", target);
+    Self::new(context, format!("dimension {}", dimension), [target.argument().map(drop), args::resource_location(dimension).map(drop)])
+  }
+  
+  /// Inverts this condition, testing it with `unless` instead of `if`.
+  pub fn negate(self) -> Self {
+    Self { negated: !self.negated, ..self }
+  }
+  
+  /// Returns this condition as the arguments of `execute`,
+  /// or [`CommandError::InvalidArgument`] if it is invalid or could not be safely inserted into a command.
+  pub(crate) fn argument(&self) -> Result<String, CommandError> {
+    match &self.error {
+      Some(error) => Err(CommandError::InvalidArgument(error.clone())),
+      None => Ok(self.to_string())
+    }
+  }
+  
+}
+
+impl Display for Condition {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}{} {}", self.context, if self.negated { "unless" } else { "if" }, self.test)
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Tests the given condition with `execute if` (or `execute unless`, for [negated](Condition::negate) conditions),
+  /// returning whether it passed.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::{BlockPos, Condition}};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// if client.check(&Condition::dimension("Steve", "minecraft:the_nether"))? {
+  ///   println!("Steve is in the nether");
+  /// }
+  /// let pos = BlockPos { x: 0, y: Some(64), z: 0 };
+  /// if client.check(&Condition::block(pos, "minecraft:air").negate())? {
+  ///   println!("Something is in the way");
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `condition` is invalid or could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way,
+  /// such as when an objective or dimension does not exist, or the block at the position is not loaded.
+  pub fn check(&self, condition: &Condition) -> Result<bool, CommandError> {
+    Ok(self.check_count(condition)? > 0)
+  }
+  
+  /// Tests the given condition like [`check`](RconClient::check), returning the count that the server reports.
+  /// 
+  /// For [entity conditions](Condition::entity), this is the number of matching entities
+  /// (or, for negated ones, 1 if there are none).
+  /// Other conditions count 1 if they pass and 0 if they fail.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`check`](RconClient::check).
+  pub fn check_count(&self, condition: &Condition) -> Result<i64, CommandError> {
+    let response = self.send_supported(&format!("execute {}", condition.argument()?))?;
+    // "Test passed", "Test passed, count: 3", "Test failed", or "Test failed, count: 0",
+    // or nothing if the context (like the target of a dimension condition) selected no entities
+    if response.is_empty() {
+      return Ok(0)
+    }
+    let passed = if response.starts_with("Test passed") {
+      true
+    } else if response.starts_with("Test failed") {
+      false
+    } else {
+      Err(unexpected(&response))?
+    };
+    match response.split_once("count: ") {
+      Some((_, count)) => count.trim().parse().map_err(|_| unexpected(&response)),
+      None => Ok(i64::from(passed))
+    }
+  }
+  
+}
+
+/// Formats a range of scores as Minecraft accepts them, like `..10`, `5..`, `5..10`, or `5`.
+fn score_range(start: Bound<i32>, end: Bound<i32>) -> Result<String, &'static str> {
+  let min = match start {
+    Bound::Included(min) => Some(min),
+    Bound::Excluded(min) => Some(min.checked_add(1).ok_or("range is empty")?),
+    Bound::Unbounded => None
+  };
+  let max = match end {
+    Bound::Included(max) => Some(max),
+    Bound::Excluded(max) => Some(max.checked_sub(1).ok_or("range is empty")?),
+    Bound::Unbounded => None
+  };
+  match (min, max) {
+    (None, None) => Err("at least one bound must be given"),
+    (Some(min), Some(max)) if min > max => Err("range is empty"),
+    (Some(min), Some(max)) if min == max => Ok(min.to_string()),
+    (Some(min), Some(max)) => Ok(format!("{}..{}", min, max)),
+    (Some(min), None) => Ok(format!("{}..", min)),
+    (None, Some(max)) => Ok(format!("..{}", max))
+  }
+}
\ No newline at end of file
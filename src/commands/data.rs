@@ -0,0 +1,92 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{snbt::SnbtValue, CommandError, RconClient};
+
+use super::{args, parse::unexpected, Selector};
+
+/// The holder of NBT data to query with [`RconClient::data_get`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DataTarget {
+  
+  /// An entity, given by a player name, UUID, or selector that matches exactly one entity.
+  Entity(Selector),
+  /// The block entity at the given position.
+  Block {
+    
+    /// The x coordinate of the block.
+    x: i32,
+    /// The y coordinate of the block.
+    y: i32,
+    /// The z coordinate of the block.
+    z: i32
+    
+  },
+  /// A command storage, given by its namespaced id.
+  Storage(String)
+  
+}
+
+impl Display for DataTarget {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      DataTarget::Entity(target) => write!(f, "entity {}", target),
+      DataTarget::Block { x, y, z } => write!(f, "block {} {} {}", x, y, z),
+      DataTarget::Storage(id) => write!(f, "storage {}", id)
+    }
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Gets NBT data with `data get`, optionally at the given NBT path, and parses it as [SNBT](crate::snbt).
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::DataTarget};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let health = client.data_get(DataTarget::Entity("Steve".into()), Some("Health"))?;
+  /// println!("Steve has {} health", health.as_f64().unwrap_or_default());
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if the target or path could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds with anything other than data,
+  /// such as when the target does not exist or has nothing at the given path.
+  pub fn data_get(&self, target: DataTarget, path: Option<&str>) -> Result<SnbtValue, CommandError> {
+    match &target {
+      DataTarget::Entity(target) => {
+        args::selector(target)?;
+      }
+      DataTarget::Block { .. } => {}
+      DataTarget::Storage(id) => {
+        args::resource_location(id)?;
+      }
+    }
+    let command = match path {
+      Some(path) => format!("data get {} {}", target, args::nbt_path(path)?),
+      None => format!("data get {}", target)
+    };
+    parse_data(&self.send_supported(&command)?)
+  }
+  
+}
+
+/// Parses the data in a response to `data get`.
+pub(super) fn parse_data(response: &str) -> Result<SnbtValue, CommandError> {
+  // "Steve has the following entity data: {...}", "1, 2, 3 has the following block data: {...}", or "Storage minecraft:x has the following contents: {...}"
+  let data = ["has the following entity data: ", "has the following block data: ", "has the following contents: "]
+    .into_iter()
+    .find_map(|marker| response.split_once(marker).map(|(_, data)| data))
+    .ok_or_else(|| unexpected(response))?;
+  data.parse().map_err(|_| unexpected(response))
+}
\ No newline at end of file
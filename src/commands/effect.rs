@@ -0,0 +1,141 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::{decimal, unexpected}, Selector};
+
+/// The longest effect duration, in seconds, that Minecraft accepts.
+const MAX_EFFECT_SECONDS: u32 = 1_000_000;
+
+/// The duration used by Minecraft when none is given.
+const DEFAULT_EFFECT_SECONDS: u32 = 30;
+
+/// How long an effect given with [`RconClient::effect_give`] lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectDuration {
+  
+  /// The given number of seconds, from 1 to 1,000,000.
+  Seconds(u32),
+  /// Forever, until cleared.
+  /// 
+  /// This uses the `infinite` keyword, which is only supported by Minecraft 1.19.4 and later.
+  Infinite
+  
+}
+
+impl Display for EffectDuration {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      EffectDuration::Seconds(seconds) => write!(f, "{}", seconds),
+      EffectDuration::Infinite => write!(f, "infinite")
+    }
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Gives the effect `effect_id` to `target` with `effect give`.
+  /// 
+  /// If `duration` or `amplifier` is `None`, Minecraft's defaults of 30 seconds and amplifier 0 (level I) are used.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::EffectDuration};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.effect_give("@a", "minecraft:speed", Some(EffectDuration::Seconds(60)), Some(1), true)?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `target` or `effect_id` could not be safely inserted into a command
+  /// or `duration` is not between 1 and 1,000,000 seconds,
+  /// or with [`CommandError::UnexpectedResponse`] if the server does not confirm that the effect was applied,
+  /// such as when the target is immune or already has a stronger effect.
+  pub fn effect_give(&self, target: impl Into<Selector>, effect_id: &str, duration: Option<EffectDuration>, amplifier: Option<u8>, hide_particles: bool) -> Result<(), CommandError> {
+    if let Some(EffectDuration::Seconds(seconds)) = duration {
+      if !(1..=MAX_EFFECT_SECONDS).contains(&seconds) {
+        Err(CommandError::InvalidArgument(format!("effect duration must be between 1 and {} seconds: {}", MAX_EFFECT_SECONDS, seconds)))?
+      }
+    }
+    let mut command = format!("effect give {} {}", args::selector(target)?, args::resource_location(effect_id)?);
+    // the arguments are positional, so later ones require the earlier ones to be given explicitly
+    if duration.is_some() || amplifier.is_some() || hide_particles {
+      command += &format!(" {}", duration.unwrap_or(EffectDuration::Seconds(DEFAULT_EFFECT_SECONDS)));
+    }
+    if amplifier.is_some() || hide_particles {
+      command += &format!(" {}", amplifier.unwrap_or(0));
+    }
+    if hide_particles {
+      command += " true";
+    }
+    // "Applied effect Speed to Steve", or "Applied effect Speed to 2 targets"
+    self.expect_prefix(&command, "Applied effect ")
+  }
+  
+  /// Clears the effect `effect_id`, or every effect if it is `None`, from `target` with `effect clear`,
+  /// returning whether anything was cleared.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `target` or `effect_id` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way,
+  /// such as when no entity matches `target`.
+  pub fn effect_clear(&self, target: impl Into<Selector>, effect_id: Option<&str>) -> Result<bool, CommandError> {
+    let target = args::selector(target)?;
+    let command = match effect_id {
+      Some(effect_id) => format!("effect clear {} {}", target, args::resource_location(effect_id)?),
+      None => format!("effect clear {}", target)
+    };
+    let response = self.send_supported(&command)?;
+    // "Removed every effect from Steve", "Removed effect Speed from 2 targets",
+    // "Target has no effects to remove", or "Target doesn't have the requested effect"
+    if response.starts_with("Removed ") {
+      Ok(true)
+    } else if response.starts_with("Target has no effects") || response.starts_with("Target doesn't have the requested effect") {
+      Ok(false)
+    } else {
+      Err(unexpected(&response))
+    }
+  }
+  
+  /// Gets the value of the attribute `attribute` of `target` with `attribute get`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let max_health = client.attribute_get("Steve", "minecraft:generic.max_health")?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `target` or `attribute` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds with anything other than a value,
+  /// such as when the entity does not have the attribute.
+  pub fn attribute_get(&self, target: impl Into<Selector>, attribute: &str) -> Result<f64, CommandError> {
+    let command = format!("attribute {} {} get", args::selector(target)?, args::resource_location(attribute)?);
+    let response = self.send_supported(&command)?;
+    // "Value of attribute Max Health for entity Steve is 20.0", or "Attribute Max Health for entity Steve has value 20.0" in some versions,
+    // where some mods display the value in scientific notation, like 1.0E-4
+    let (_, value) = response.rsplit_once(" is ").or_else(|| response.rsplit_once(" has value ")).ok_or_else(|| unexpected(&response))?;
+    decimal(value).ok_or_else(|| unexpected(&response))
+  }
+  
+}
\ No newline at end of file
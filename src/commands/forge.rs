@@ -0,0 +1,148 @@
+use crate::{CommandError, RconClient};
+
+use super::parse::{decimal, unexpected};
+
+/// The tick statistics of every dimension, as reported by Forge or NeoForge. See [`RconClient::forge_tps`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ForgeTpsReport {
+  
+  /// The statistics of each dimension, in the order the server listed them.
+  pub dimensions: Vec<DimensionTps>,
+  /// The statistics of the server as a whole, if the server reported them.
+  pub overall: Option<DimensionTps>,
+  /// The lines of the response that could not be parsed, if any.
+  pub raw: Vec<String>
+  
+}
+
+/// The tick statistics of one dimension. See [`ForgeTpsReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionTps {
+  
+  /// The name of the dimension, e.g. `minecraft:overworld`, or `Overall` for the whole server.
+  pub dimension: String,
+  /// The mean tick time in milliseconds.
+  pub mean_tick_ms: f64,
+  /// The mean ticks per second.
+  pub tps: f64
+  
+}
+
+impl RconClient {
+  
+  /// Queries the tick statistics of every dimension with Forge's `forge tps` command,
+  /// falling back to NeoForge's `neoforge tps` if the server does not recognize the former.
+  /// 
+  /// Both Forge's format (`Dim minecraft:overworld (minecraft:overworld): Mean tick time: 1.234 ms. Mean TPS: 20.000`)
+  /// and NeoForge's (`minecraft:overworld: 20.000 TPS (1.234 ms/tick)`) are recognized.
+  /// Servers with many dimensions produce long responses, which are reassembled as usual.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// for dimension in client.forge_tps()?.dimensions {
+  ///   println!("{}: {} TPS", dimension.dimension, dimension.tps);
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// Responses from Forge, NeoForge, and vanilla servers are handled like this:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::DimensionTps};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let unknown = "Unknown or incomplete command, see below for error<--[HERE]";
+  /// let modded: String = (0..100).map(|i| format!("Dim mymod:dim{0} (mymod:dim{0}): Mean tick time: 0.125 ms. Mean TPS: 20.000\n", i)).collect();
+  /// let mut responses = [
+  ///   // Forge, with many modded dimensions
+  ///   format!("Dim minecraft:overworld (minecraft:overworld): Mean tick time: 1.234 ms. Mean TPS: 20.000\n\
+  ///            {}Full tick statistics are available in the log\n\
+  ///            Overall: Mean tick time: 13.750 ms. Mean TPS: 19.876", modded),
+  ///   // NeoForge, which only has "neoforge tps"
+  ///   unknown.to_owned(),
+  ///   "minecraft:overworld: 17.500 TPS (57.143 ms/tick)\nminecraft:the_nether: 20.000 TPS (0.478 ms/tick)\nOverall: 17.500 TPS (57.621 ms/tick)".to_owned(),
+  ///   // vanilla
+  ///   unknown.to_owned(),
+  ///   unknown.to_owned(),
+  ///   // something else
+  ///   "Ticking normally".to_owned()
+  /// ].into_iter();
+  /// let server = ScriptedServer::new("password", move |command| match command {
+  ///   "seed" => "Seed: [1]".to_owned(), // sent by the client to find the end of a long response
+  ///   _ => responses.next().unwrap_or_default()
+  /// })?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// 
+  /// let report = client.forge_tps()?;
+  /// assert_eq!(report.dimensions.len(), 101);
+  /// assert_eq!(report.dimensions[0], DimensionTps { dimension: "minecraft:overworld".into(), mean_tick_ms: 1.234, tps: 20.0 });
+  /// assert_eq!(report.dimensions[100].dimension, "mymod:dim99");
+  /// assert_eq!(report.overall, Some(DimensionTps { dimension: "Overall".into(), mean_tick_ms: 13.75, tps: 19.876 }));
+  /// assert_eq!(report.raw, ["Full tick statistics are available in the log"]);
+  /// 
+  /// let report = client.forge_tps()?;
+  /// assert_eq!(report.dimensions[0], DimensionTps { dimension: "minecraft:overworld".into(), mean_tick_ms: 57.143, tps: 17.5 });
+  /// assert_eq!(report.dimensions[1].dimension, "minecraft:the_nether");
+  /// assert_eq!(report.overall.map(|overall| overall.tps), Some(17.5));
+  /// 
+  /// assert_eq!(client.forge_tps(), Err(CommandError::UnsupportedCommand));
+  /// assert_eq!(client.forge_tps(), Err(CommandError::UnexpectedResponse("Ticking normally".into())));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::UnsupportedCommand`] if the server has neither command,
+  /// or with [`CommandError::UnexpectedResponse`] if no line of the response can be parsed.
+  pub fn forge_tps(&self) -> Result<ForgeTpsReport, CommandError> {
+    let response = match self.send_supported("forge tps") {
+      Err(CommandError::UnsupportedCommand) => self.send_supported("neoforge tps")?,
+      response => response?
+    };
+    let mut report = ForgeTpsReport::default();
+    for line in response.lines().map(str::trim).filter(|line| !line.is_empty()) {
+      match parse_dimension(line) {
+        Some(dimension) if dimension.dimension.eq_ignore_ascii_case("overall") => report.overall = Some(dimension),
+        Some(dimension) => report.dimensions.push(dimension),
+        None => report.raw.push(line.to_owned())
+      }
+    }
+    if report.dimensions.is_empty() && report.overall.is_none() {
+      Err(unexpected(&response))?
+    }
+    Ok(report)
+  }
+  
+}
+
+fn parse_dimension(line: &str) -> Option<DimensionTps> {
+  let (dimension, mean_tick_ms, tps) = if let Some((name, stats)) = line.split_once("Mean tick time:") {
+    // Forge: "Dim minecraft:overworld (minecraft:overworld): Mean tick time: 1.234 ms. Mean TPS: 20.000"
+    let (tick, tps) = stats.split_once("Mean TPS:")?;
+    (name, decimal(tick.trim().trim_end_matches('.').trim_end_matches("ms"))?, decimal(tps)?)
+  } else {
+    // NeoForge: "minecraft:overworld: 20.000 TPS (1.234 ms/tick)"
+    let (name_and_tps, tick) = line.split_once(" TPS (")?;
+    let (name, tps) = name_and_tps.rsplit_once(": ")?;
+    (name, decimal(tick.trim_end_matches(')').trim_end_matches("ms/tick"))?, decimal(tps)?)
+  };
+  let dimension = dimension.trim().trim_end_matches(':').trim();
+  let dimension = dimension.strip_prefix("Dim ").unwrap_or(dimension).trim();
+  let dimension = dimension.split_once(" (").map_or(dimension, |(dimension, _)| dimension);
+  Some(DimensionTps { dimension: dimension.to_owned(), mean_tick_ms, tps })
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use crate::{snbt::SnbtValue, CommandError, RconClient};
+
+use super::{args, data::parse_data, parse::unexpected, Selector};
+
+/// A stack of items in a player's inventory, as returned by [`RconClient::get_inventory`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+  
+  /// The inventory slot the stack is in.
+  /// 
+  /// Slots 0 to 8 are the hotbar and 9 to 35 the rest of the inventory,
+  /// while 100 to 103 are the armor slots (feet to head) and -106 is the offhand.
+  pub slot: i8,
+  /// The namespaced id of the item, like `minecraft:diamond_sword`.
+  pub id: String,
+  /// The number of items in the stack.
+  pub count: u32,
+  /// The rest of the item's data, or an empty compound if it has none.
+  /// 
+  /// Since 1.20.5, this is the item's components, like `{"minecraft:damage": 10}`;
+  /// before that, it is the item's `tag` NBT, like `{Damage: 10}`.
+  pub components: SnbtValue
+  
+}
+
+impl RconClient {
+  
+  /// Gets the items in a player's inventory with `data get entity <player> Inventory`,
+  /// or `None` if the player is not online.
+  /// 
+  /// Both the item format used since 1.20.5 (with `count` and `components`)
+  /// and the one used before (with `Count` and `tag`) are understood.
+  /// An empty inventory gives an empty list.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// match client.get_inventory("Steve")? {
+  ///   Some(items) => for item in items {
+  ///     println!("slot {}: {} x{}", item.slot, item.id, item.count);
+  ///   }
+  ///   None => println!("Steve is not online")
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `player` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds with anything other than an inventory,
+  /// such as when `player` is a selector that matches an entity without one.
+  pub fn get_inventory(&self, player: impl Into<Selector>) -> Result<Option<Vec<ItemStack>>, CommandError> {
+    let command = format!("data get entity {} Inventory", args::selector(player)?);
+    let response = self.send_supported(&command)?;
+    // "Steve has the following entity data: [{Slot: 0b, id: "minecraft:stone", count: 64}]", or "No entity was found"
+    if response.starts_with("No entity was found") {
+      return Ok(None)
+    }
+    let SnbtValue::List(items) = parse_data(&response)? else {
+      Err(unexpected(&response))?
+    };
+    items.iter().map(|item| parse_item(item).ok_or_else(|| unexpected(&response))).collect::<Result<_, _>>().map(Some)
+  }
+  
+}
+
+fn parse_item(item: &SnbtValue) -> Option<ItemStack> {
+  let slot = i8::try_from(item.get("Slot")?.as_i64()?).ok()?;
+  let id = item.get("id")?.as_str()?.to_owned();
+  // "count" since 1.20.5, where it may be left out for a single item, and "Count" before
+  let count = match item.get("count").or_else(|| item.get("Count")) {
+    Some(count) => u32::try_from(count.as_i64()?).ok()?,
+    None => 1
+  };
+  let components = item.get("components").or_else(|| item.get("tag")).cloned().unwrap_or_else(|| SnbtValue::Compound(BTreeMap::new()));
+  Some(ItemStack { slot, id, count, components })
+}
\ No newline at end of file
@@ -0,0 +1,255 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::unexpected, MinecraftVersion, Selector};
+
+/// The most items that vanilla servers give with a single `give` command.
+pub const MAX_GIVE_COUNT: u32 = 6400;
+
+/// An item, with optional data, for [`RconClient::give`] and [`RconClient::clear`].
+/// 
+/// Item data is written differently depending on the server's version:
+/// since 1.20.5, items have components, written in square brackets like `minecraft:diamond_sword[minecraft:damage=10]`;
+/// before that, they have NBT, written in braces like `minecraft:diamond_sword{Damage:10}`.
+/// Since the contents differ as well as the brackets, a spec can hold the data in both forms,
+/// with [`with_components`](ItemSpec::with_components) and [`with_nbt`](ItemSpec::with_nbt),
+/// and the command wrappers pick the one that suits the server's [version](RconClient::minecraft_version).
+/// 
+/// ```
+/// # use mc_rcon::commands::{ItemSpec, ItemSyntax};
+/// let sword = ItemSpec::new("minecraft:diamond_sword").with_components("minecraft:damage=10").with_nbt("Damage:10");
+/// assert_eq!(sword.to_argument(ItemSyntax::Components), "minecraft:diamond_sword[minecraft:damage=10]");
+/// assert_eq!(sword.to_argument(ItemSyntax::Nbt), "minecraft:diamond_sword{Damage:10}");
+/// assert_eq!(ItemSpec::new("minecraft:stone").to_argument(ItemSyntax::Nbt), "minecraft:stone");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemSpec {
+  
+  /// The namespaced id of the item, like `minecraft:stone`.
+  pub id: String,
+  /// The item's components, without the surrounding square brackets, for servers running Minecraft 1.20.5 or later.
+  pub components: Option<String>,
+  /// The item's NBT, without the surrounding braces, for servers running Minecraft versions before 1.20.5.
+  pub nbt: Option<String>
+  
+}
+
+/// The syntax that a server uses for item data, which depends on its version. See [`ItemSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemSyntax {
+  
+  /// Components in square brackets, like `minecraft:damage=10`, as used since Minecraft 1.20.5.
+  Components,
+  /// NBT in braces, like `Damage:10`, as used before Minecraft 1.20.5.
+  Nbt
+  
+}
+
+impl ItemSyntax {
+  
+  /// Returns the syntax that servers running the given version use.
+  pub fn for_version(version: MinecraftVersion) -> ItemSyntax {
+    if version.at_least(1, 20, 5) {
+      ItemSyntax::Components
+    } else {
+      ItemSyntax::Nbt
+    }
+  }
+  
+}
+
+impl Display for ItemSyntax {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      ItemSyntax::Components => write!(f, "components"),
+      ItemSyntax::Nbt => write!(f, "NBT")
+    }
+  }
+  
+}
+
+impl ItemSpec {
+  
+  /// Returns a spec for the item with the given id and no data.
+  pub fn new(id: impl Into<String>) -> ItemSpec {
+    ItemSpec { id: id.into(), components: None, nbt: None }
+  }
+  
+  /// Sets the item's components (for Minecraft 1.20.5 and later), without the surrounding square brackets.
+  pub fn with_components(self, components: impl Into<String>) -> ItemSpec {
+    ItemSpec { components: Some(components.into()), ..self }
+  }
+  
+  /// Sets the item's NBT (for Minecraft versions before 1.20.5), without the surrounding braces.
+  pub fn with_nbt(self, nbt: impl Into<String>) -> ItemSpec {
+    ItemSpec { nbt: Some(nbt.into()), ..self }
+  }
+  
+  /// Returns whether this item has data in either syntax.
+  pub fn has_data(&self) -> bool {
+    self.components.is_some() || self.nbt.is_some()
+  }
+  
+  /// Returns the data of this item in the given syntax, if it has any.
+  pub fn data(&self, syntax: ItemSyntax) -> Option<&str> {
+    match syntax {
+      ItemSyntax::Components => self.components.as_deref(),
+      ItemSyntax::Nbt => self.nbt.as_deref()
+    }
+  }
+  
+  /// Writes this item as a command argument in the given syntax, leaving out any data it only has in the other syntax.
+  pub fn to_argument(&self, syntax: ItemSyntax) -> String {
+    match (syntax, self.data(syntax)) {
+      (ItemSyntax::Components, Some(components)) => format!("{}[{}]", self.id, components),
+      (ItemSyntax::Nbt, Some(nbt)) => format!("{}{{{}}}", self.id, nbt),
+      (_, None) => self.id.clone()
+    }
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Gives `count` of the given item to `target` with `give`.
+  /// 
+  /// If the item has data, the [syntax](ItemSyntax) for it is chosen by the server's [version](RconClient::minecraft_version),
+  /// which is queried the first time it is needed and remembered until the client [reconnects](RconClient::reconnect).
+  /// Servers that do not report their version (vanilla servers before 1.21.6) are given the item's components if it has them,
+  /// and its NBT otherwise.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::ItemSpec};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.give("Steve", &ItemSpec::new("minecraft:cooked_beef"), 16)?;
+  /// let pickaxe = ItemSpec::new("minecraft:diamond_pickaxe").with_components("minecraft:unbreakable={}").with_nbt("Unbreakable:1b");
+  /// client.give("Steve", &pickaxe, 1)?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// Servers before and since Minecraft 1.20.5 are given the item in their own syntax:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::ItemSpec};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let pickaxe = ItemSpec::new("minecraft:diamond_pickaxe").with_components("minecraft:unbreakable={}").with_nbt("Unbreakable:1b");
+  /// let servers = [
+  ///   ("This server is running Paper version 1.20.4-496 (MC: 1.20.4)", "give Steve minecraft:diamond_pickaxe{Unbreakable:1b} 1"),
+  ///   ("Server version info:\nid = 1.21.6\nname = 1.21.6", "give Steve minecraft:diamond_pickaxe[minecraft:unbreakable={}] 1")
+  /// ];
+  /// for (version, expected) in servers {
+  ///   let mut queried = false;
+  ///   let server = ScriptedServer::new("password", move |command| match command {
+  ///     "version" if !queried => {
+  ///       queried = true;
+  ///       version.to_owned()
+  ///     }
+  ///     command if command == expected => "Gave 1 [Diamond Pickaxe] to Steve".to_owned(),
+  ///     command => format!("Unexpected command: {}", command)
+  ///   })?;
+  ///   let client = RconClient::connect(server.local_addr())?;
+  ///   client.log_in("password")?;
+  ///   client.give("Steve", &pickaxe, 1)?;
+  ///   client.give("Steve", &pickaxe, 1)?; // the version is only queried once
+  ///   drop(client);
+  ///   server.join()?;
+  /// }
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `target` or `item` could not be safely inserted into a command,
+  /// or if the item has data, but not in the syntax that the server's version uses,
+  /// or if `count` is 0 or greater than [`MAX_GIVE_COUNT`],
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way,
+  /// such as when no player matches `target` or the item's data is invalid.
+  pub fn give(&self, target: impl Into<Selector>, item: &ItemSpec, count: u32) -> Result<(), CommandError> {
+    if !(1..=MAX_GIVE_COUNT).contains(&count) {
+      Err(CommandError::InvalidArgument(format!("item count must be from 1 to {}: {}", MAX_GIVE_COUNT, count)))?
+    }
+    let command = format!("give {} {} {}", args::selector(target)?, args::item(item, self.item_syntax(item)?)?, count);
+    // "Gave 16 [Cooked Beef] to Steve", or "Gave 16 [Cooked Beef] to 2 players"
+    self.expect_prefix(&command, "Gave ")
+  }
+  
+  /// Removes up to `max_count` of the given item, or any item if it is `None`, from the inventory of `target` with `clear`,
+  /// returning the number of items removed.
+  /// 
+  /// Without a `max_count`, every matching item is removed.
+  /// The syntax for the item's data is chosen like for [`give`](RconClient::give).
+  /// A `max_count` of 0 removes nothing, and returns the number of matching items instead.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::ItemSpec};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let removed = client.clear("Steve", Some(&ItemSpec::new("minecraft:tnt")), None)?;
+  /// println!("Confiscated {} TNT", removed);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `target` or `item` could not be safely inserted into a command,
+  /// or if the item has data, but not in the syntax that the server's version uses,
+  /// or if `max_count` is given without an `item` or is too large for the command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way,
+  /// such as when no player matches `target`.
+  pub fn clear(&self, target: impl Into<Selector>, item: Option<&ItemSpec>, max_count: Option<u32>) -> Result<u32, CommandError> {
+    let target = args::selector(target)?;
+    let command = match (item, max_count) {
+      (Some(item), Some(max_count)) => {
+        if i32::try_from(max_count).is_err() {
+          Err(CommandError::InvalidArgument(format!("maximum item count is too large: {}", max_count)))?
+        }
+        format!("clear {} {} {}", target, args::item(item, self.item_syntax(item)?)?, max_count)
+      }
+      (Some(item), None) => format!("clear {} {}", target, args::item(item, self.item_syntax(item)?)?),
+      (None, Some(_)) => Err(CommandError::InvalidArgument("a maximum item count needs an item to count".to_owned()))?,
+      (None, None) => format!("clear {}", target)
+    };
+    let response = self.send_supported(&command)?;
+    // "Removed 5 item(s) from player Steve", "Removed 5 item(s) from 2 players",
+    // "Found 5 matching item(s) on player Steve" (with a maximum of 0), or "No items were found on player Steve"
+    if response.starts_with("No items were found") {
+      return Ok(0)
+    }
+    let count = response.strip_prefix("Removed ").or_else(|| response.strip_prefix("Found ")).ok_or_else(|| unexpected(&response))?;
+    count.split_whitespace().next().and_then(|count| count.parse().ok()).ok_or_else(|| unexpected(&response))
+  }
+    
+  /// Chooses the syntax for the data of `item`, only querying the server's version if it has data.
+  fn item_syntax(&self, item: &ItemSpec) -> Result<ItemSyntax, CommandError> {
+    if !item.has_data() {
+      return Ok(ItemSyntax::Components) // the syntax does not matter
+    }
+    match self.cached_minecraft_version()? {
+      Some(version) => Ok(ItemSyntax::for_version(version)),
+      // the server could be from before or since 1.20.5, so use whichever data the item has
+      None if item.components.is_some() => Ok(ItemSyntax::Components),
+      None => Ok(ItemSyntax::Nbt)
+    }
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::unexpected, BlockPos};
+
+/// The result of [`RconClient::locate_structure`], [`locate_biome`](RconClient::locate_biome), or [`locate_poi`](RconClient::locate_poi).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Located {
+  
+  /// The id of what was found.
+  /// 
+  /// When searching for a tag, this is the id of the specific structure, biome, or point of interest that was found.
+  pub id: String,
+  /// Where it was found.
+  pub pos: BlockPos,
+  /// How far away it was found, in blocks, if the server reported it.
+  pub distance: Option<u32>
+  
+}
+
+impl RconClient {
+  
+  /// Finds the nearest structure of the given type (or tag, starting with `#`) with `locate structure`,
+  /// or `None` if there is none nearby.
+  /// 
+  /// Searches start from the position the command is run at, which for RCON is the world spawn.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// if let Some(village) = client.locate_structure("#minecraft:village")? {
+  ///   println!("There is a {} at {}", village.id, village.pos);
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `structure` is not a valid id or tag,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds with anything other than a location,
+  /// such as when the structure type does not exist.
+  pub fn locate_structure(&self, structure: &str) -> Result<Option<Located>, CommandError> {
+    self.locate("structure", structure)
+  }
+  
+  /// Finds the nearest biome of the given type (or tag, starting with `#`) with `locate biome`,
+  /// or `None` if there is none within a reasonable distance.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`locate_structure`](RconClient::locate_structure).
+  pub fn locate_biome(&self, biome: &str) -> Result<Option<Located>, CommandError> {
+    self.locate("biome", biome)
+  }
+  
+  /// Finds the nearest point of interest of the given type (or tag, starting with `#`) with `locate poi`,
+  /// or `None` if there is none within a reasonable distance.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`locate_structure`](RconClient::locate_structure).
+  pub fn locate_poi(&self, poi: &str) -> Result<Option<Located>, CommandError> {
+    self.locate("poi", poi)
+  }
+  
+  fn locate(&self, kind: &str, id: &str) -> Result<Option<Located>, CommandError> {
+    args::resource_location(id.strip_prefix('#').unwrap_or(id))?;
+    let response = self.send_supported(&format!("locate {} {}", kind, id))?;
+    // "Could not find a structure of type "..." nearby", or "Could not find a biome of type "..." within reasonable distance"
+    if response.starts_with("Could not find") {
+      return Ok(None)
+    }
+    // "The nearest minecraft:plains is at [12, 64, -40] (42 blocks away)", or "The nearest #minecraft:village (minecraft:village_plains) is at [128, ~, -256] (286 blocks away)"
+    let (name, rest) = response.strip_prefix("The nearest ").and_then(|rest| rest.split_once(" is at ")).ok_or_else(|| unexpected(&response))?;
+    let id = match name.split_once(" (") {
+      Some((_, id)) => id.strip_suffix(')').ok_or_else(|| unexpected(&response))?,
+      None => name
+    };
+    let (pos, rest) = rest.split_once(']').ok_or_else(|| unexpected(&response))?;
+    let pos = BlockPos::parse(pos.trim_start_matches('[')).ok_or_else(|| unexpected(&response))?;
+    let distance = rest.trim().strip_prefix('(').and_then(|rest| rest.split_whitespace().next()).and_then(|distance| distance.parse().ok());
+    Ok(Some(Located { id: id.to_owned(), pos, distance }))
+  }
+  
+}
\ No newline at end of file
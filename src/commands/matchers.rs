@@ -0,0 +1,186 @@
+//! Interpretation of responses whose phrasing depends on the server's language.
+//! 
+//! Minecraft servers can be configured to respond in languages other than English,
+//! which changes the phrasing of most responses.
+//! The typed command wrappers extract what they can without relying on phrasing (such as numbers, bracketed values, and lists),
+//! and otherwise ask the client's [`ResponseMatcher`], which is [`EN`] unless changed with
+//! [`RconClient::set_response_matcher`](crate::RconClient::set_response_matcher).
+//! Currently, recognizing unknown commands and the wrappers for `list`, `seed`, `difficulty`, `whitelist`, and `worldborder` use the matcher;
+//! the other wrappers expect English responses.
+//! 
+//! To support another language, implement [`ResponseMatcher`] with that language's phrases:
+//! ```
+//! # use mc_rcon::commands::{Difficulty, ResponseMatcher, WhitelistOutcome};
+//! struct German;
+//! 
+//! impl ResponseMatcher for German {
+//! 
+//!   fn is_unknown_command(&self, response: &str) -> bool {
+//!     response.starts_with("Unbekannter oder unvollständiger Befehl")
+//!   }
+//! 
+//!   fn difficulty_name(&self, difficulty: Difficulty) -> &str {
+//!     match difficulty {
+//!       Difficulty::Peaceful => "Friedlich",
+//!       Difficulty::Easy => "Einfach",
+//!       Difficulty::Normal => "Normal",
+//!       Difficulty::Hard => "Schwer"
+//!     }
+//!   }
+//! 
+//!   fn whitelist_outcome(&self, response: &str) -> Option<WhitelistOutcome> {
+//!     if response.contains("zur Whitelist hinzugefügt") {
+//!       Some(WhitelistOutcome::Added)
+//!     } else if response.contains("von der Whitelist entfernt") {
+//!       Some(WhitelistOutcome::Removed)
+//!     } else if response.contains("bereits auf der Whitelist") {
+//!       Some(WhitelistOutcome::AlreadyWhitelisted)
+//!     } else if response.contains("nicht auf der Whitelist") {
+//!       Some(WhitelistOutcome::NotWhitelisted)
+//!     } else if response.contains("existiert nicht") {
+//!       Some(WhitelistOutcome::PlayerNotFound)
+//!     } else {
+//!       None
+//!     }
+//!   }
+//! 
+//!   fn is_worldborder_changed(&self, response: &str) -> bool {
+//!     (response.contains("Weltbarriere") && !response.contains("kann nicht")) || response.starts_with("Nichts hat sich geändert")
+//!   }
+//! 
+//! }
+//! 
+//! assert_eq!(German.parse_difficulty("Der Schwierigkeitsgrad ist Schwer"), Some(Difficulty::Hard));
+//! assert_eq!(German.whitelist_outcome("Steve wurde zur Whitelist hinzugefügt"), Some(WhitelistOutcome::Added));
+//! assert_eq!(German.parse_seed("Startwert: [-42]"), Some(-42));
+//! let list = German.parse_list("Es sind 1 von maximal 20 Spielern online: Steve").unwrap();
+//! assert_eq!((list.online, list.max, list.players), (1, 20, vec!["Steve".to_owned()]));
+//! ```
+//! 
+//! Matchers for other languages are welcome as contributions:
+//! add a unit struct implementing [`ResponseMatcher`] to this module, along with a constant named after the language code like [`EN`],
+//! using the phrases from the language's file in the Minecraft assets.
+
+use crate::response::minecraft::{parse_list_response, ListResponse};
+
+use super::{parse::decimal, Difficulty, WhitelistOutcome};
+
+/// Interprets responses whose phrasing depends on the server's language. See the [module documentation](self) for details.
+/// 
+/// Responses are given to these methods with [formatting codes](crate::formatting) already removed.
+pub trait ResponseMatcher: Send + Sync {
+  
+  /// Returns whether the response means that the server does not recognize the command.
+  fn is_unknown_command(&self, response: &str) -> bool;
+  
+  /// Returns the name the server displays for the given difficulty.
+  fn difficulty_name(&self, difficulty: Difficulty) -> &str;
+  
+  /// Returns the outcome described by the response to `whitelist add` or `whitelist remove`, if it is one.
+  fn whitelist_outcome(&self, response: &str) -> Option<WhitelistOutcome>;
+  
+  /// Returns whether the response to `worldborder set`, `worldborder add`, or `worldborder center` means that the command succeeded,
+  /// including when the world border was already as requested.
+  fn is_worldborder_changed(&self, response: &str) -> bool;
+  
+  /// Parses the response to `list`.
+  /// 
+  /// By default, this takes the first two numbers in the response as the number of players online and the maximum,
+  /// and the comma-separated names after the first colon (or on each following line) as the players.
+  fn parse_list(&self, response: &str) -> Option<ListResponse> {
+    let (counts, players) = response.split_once(':').unwrap_or((response, ""));
+    let mut numbers = counts.split(|c: char| !c.is_ascii_digit()).filter(|number| !number.is_empty()).map(str::parse);
+    let (online, max) = (numbers.next()?.ok()?, numbers.next()?.ok()?);
+    let players = players
+      .lines()
+      .flat_map(|line| line.rsplit(':').next().unwrap_or(line).split(','))
+      .map(str::trim)
+      .filter(|name| !name.is_empty())
+      .map(str::to_owned)
+      .collect();
+    Some(ListResponse { online, max, players })
+  }
+  
+  /// Parses the response to `seed`.
+  /// 
+  /// By default, this takes the number in square brackets, as in `Seed: [-42]`.
+  fn parse_seed(&self, response: &str) -> Option<i64> {
+    let (_, seed) = response.split_once('[')?;
+    let (seed, _) = seed.split_once(']')?;
+    seed.trim().parse().ok()
+  }
+  
+  /// Parses the response to `worldborder get`.
+  /// 
+  /// By default, this takes the first number in the response, as in `The world border is currently 59999968 block(s) wide`.
+  fn parse_worldborder(&self, response: &str) -> Option<f64> {
+    response.split_whitespace().find_map(decimal)
+  }
+  
+  /// Parses the response to `difficulty` (without arguments).
+  /// 
+  /// By default, this looks for the [name](ResponseMatcher::difficulty_name) of each difficulty, ignoring case.
+  fn parse_difficulty(&self, response: &str) -> Option<Difficulty> {
+    let words: Vec<&str> = response.split(|c: char| !c.is_alphanumeric()).collect();
+    [Difficulty::Peaceful, Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+      .into_iter()
+      .find(|&difficulty| words.iter().any(|word| word.eq_ignore_ascii_case(self.difficulty_name(difficulty))))
+  }
+  
+}
+
+/// The [`ResponseMatcher`] for English, which is the default. See [`EN`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct English;
+
+/// The [`ResponseMatcher`] for English, which is the default.
+pub const EN: English = English;
+
+impl ResponseMatcher for English {
+  
+  fn is_unknown_command(&self, response: &str) -> bool {
+    let lower = response.trim_start().to_lowercase();
+    // "Unknown or incomplete command, see below for error" in vanilla, or "Unknown command. Type "/help" for help." in older versions and Bukkit derivatives
+    lower.starts_with("unknown command") || lower.starts_with("unknown or incomplete command")
+  }
+  
+  fn difficulty_name(&self, difficulty: Difficulty) -> &str {
+    match difficulty {
+      Difficulty::Peaceful => "Peaceful",
+      Difficulty::Easy => "Easy",
+      Difficulty::Normal => "Normal",
+      Difficulty::Hard => "Hard"
+    }
+  }
+  
+  fn whitelist_outcome(&self, response: &str) -> Option<WhitelistOutcome> {
+    // "Added Steve to the whitelist", "Removed Steve from the whitelist", "Player is already whitelisted",
+    // "Player is not whitelisted", or "That player does not exist"
+    if response.starts_with("Added ") {
+      Some(WhitelistOutcome::Added)
+    } else if response.starts_with("Removed ") {
+      Some(WhitelistOutcome::Removed)
+    } else if response.contains("already whitelisted") {
+      Some(WhitelistOutcome::AlreadyWhitelisted)
+    } else if response.contains("not whitelisted") {
+      Some(WhitelistOutcome::NotWhitelisted)
+    } else if response.contains("does not exist") {
+      Some(WhitelistOutcome::PlayerNotFound)
+    } else {
+      None
+    }
+  }
+  
+  fn is_worldborder_changed(&self, response: &str) -> bool {
+    // "Set the world border to 1000 block(s) wide", "Shrinking the world border to 1000 blocks wide over 600 seconds",
+    // "Growing the world border to ...", "Set the center of the world border to 0.00, 0.00",
+    // "Nothing changed. The world border is already that size" (or "already centered there"),
+    // or "Set world border to 1000 blocks wide (from 60000000 blocks)" and the like in older versions
+    ["Set ", "Shrinking ", "Growing ", "Nothing changed"].iter().any(|prefix| response.starts_with(prefix))
+  }
+  
+  fn parse_list(&self, response: &str) -> Option<ListResponse> {
+    parse_list_response(response).ok()
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+//! Typed wrappers around common Minecraft commands.
+//! 
+//! The wrappers themselves are methods on [`RconClient`](crate::RconClient);
+//! this module contains the types they accept and return.
+//! They send ordinary commands with [`send_command`](crate::RconClient::send_command) and interpret the responses,
+//! failing with [`CommandError::UnsupportedCommand`](crate::CommandError::UnsupportedCommand) if the server does not recognize the command,
+//! or with [`CommandError::UnexpectedResponse`](crate::CommandError::UnexpectedResponse)
+//! if the server responds in a way they do not recognize.
+//! Responses are interpreted after removing any [formatting codes](crate::formatting),
+//! and phrasing that depends on the server's language is interpreted by a [`ResponseMatcher`] (see [`matchers`]).
+//! Arguments that cannot be safely inserted into a command are rejected with
+//! [`CommandError::InvalidArgument`](crate::CommandError::InvalidArgument) before anything is sent.
+
+mod args;
+mod banlist;
+mod bossbar;
+mod broadcast;
+mod component;
+mod condition;
+mod data;
+mod effect;
+mod forge;
+mod inventory;
+mod item;
+mod locate;
+pub mod matchers;
+mod parse;
+mod position;
+mod save;
+mod scoreboard;
+mod selector;
+mod server;
+mod stop;
+mod teleport;
+mod tps;
+mod worldborder;
+mod xp;
+
+pub use banlist::{BanEntry, BanlistKind};
+pub use bossbar::{Bossbar, BossbarColor, BossbarStyle};
+pub use broadcast::BroadcastStyle;
+pub use component::Component;
+pub use condition::{Condition, ScoreComparison};
+pub use data::DataTarget;
+pub use effect::EffectDuration;
+pub use forge::{DimensionTps, ForgeTpsReport};
+pub use inventory::ItemStack;
+pub use item::{ItemSpec, ItemSyntax, MAX_GIVE_COUNT};
+pub use locate::Located;
+pub use matchers::ResponseMatcher;
+pub use position::{BlockPos, Coordinates, Vec3};
+pub use save::SaveOutcome;
+pub use scoreboard::Objective;
+pub use selector::{GameMode, Selector, Sort};
+pub use server::{Difficulty, MinecraftVersion, WhitelistOutcome};
+pub use stop::StopError;
+pub use teleport::{Rotation, TeleportDest};
+pub use tps::{MsptReport, MsptStats, TpsReport};
+pub use worldborder::MAX_WORLD_BORDER_DIAMETER;
+pub use xp::XpUnit;
\ No newline at end of file
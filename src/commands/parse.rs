@@ -0,0 +1,43 @@
+//! Utilities shared by the typed command wrappers for interpreting responses.
+
+use crate::{formatting::strip_codes, CommandError, RconClient};
+
+/// Parses a decimal number as servers display it, ignoring surrounding whitespace and decorations like the `*` Paper adds to capped TPS values.
+pub(crate) fn decimal(text: &str) -> Option<f64> {
+  text.trim().trim_start_matches('*').trim_end_matches('%').parse().ok()
+}
+
+/// Returns an [`UnexpectedResponse`](CommandError::UnexpectedResponse) error for the given response.
+pub(crate) fn unexpected(response: &str) -> CommandError {
+  CommandError::UnexpectedResponse(response.to_owned())
+}
+
+impl RconClient {
+  
+  /// Strips formatting codes from a response, failing with [`CommandError::UnsupportedCommand`]
+  /// if the [response matcher](crate::commands::matchers) says the server did not recognize the command.
+  pub(crate) fn supported(&self, response: &str) -> Result<String, CommandError> {
+    let stripped = strip_codes(response);
+    if self.response_matcher().is_unknown_command(&stripped) {
+      Err(CommandError::UnsupportedCommand)
+    } else {
+      Ok(stripped)
+    }
+  }
+  
+  /// Sends a command and returns its response with [`supported`](RconClient::supported).
+  pub(crate) fn send_supported(&self, command: &str) -> Result<String, CommandError> {
+    self.supported(&self.send_command(command)?)
+  }
+  
+  /// Sends a command whose successful response starts with `prefix`, failing with [`CommandError::UnexpectedResponse`] if it does not.
+  pub(crate) fn expect_prefix(&self, command: &str, prefix: &str) -> Result<(), CommandError> {
+    let response = self.send_supported(command)?;
+    if response.starts_with(prefix) {
+      Ok(())
+    } else {
+      Err(CommandError::UnexpectedResponse(response))
+    }
+  }
+  
+}
\ No newline at end of file
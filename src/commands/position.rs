@@ -0,0 +1,177 @@
+use std::fmt::{self, Display, Formatter};
+
+/// The position of a block, as reported by commands like [`RconClient::locate_structure`](crate::RconClient::locate_structure).
+/// 
+/// Minecraft sometimes reports a position without a y coordinate,
+/// displaying `~` in its place (for example, when locating structures), in which case `y` is `None`.
+/// When displayed, a missing y coordinate is written as `~` as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockPos {
+  
+  /// The x coordinate of the block.
+  pub x: i32,
+  /// The y coordinate of the block, or `None` if it is unknown.
+  pub y: Option<i32>,
+  /// The z coordinate of the block.
+  pub z: i32
+  
+}
+
+impl BlockPos {
+  
+  /// Creates a position with all three coordinates known.
+  pub fn new(x: i32, y: i32, z: i32) -> Self {
+    Self { x, y: Some(y), z }
+  }
+  
+  /// Parses a position as Minecraft displays it, like `[12, ~, -40]` or `12, 64, -40`.
+  pub(crate) fn parse(text: &str) -> Option<Self> {
+    let text = text.trim();
+    let text = text.strip_prefix('[').and_then(|text| text.strip_suffix(']')).unwrap_or(text);
+    let mut coordinates = text.split(',').map(str::trim);
+    let x = coordinates.next()?.parse().ok()?;
+    let y = match coordinates.next()? {
+      "~" => None,
+      y => Some(y.parse().ok()?)
+    };
+    let z = coordinates.next()?.parse().ok()?;
+    if coordinates.next().is_some() {
+      return None
+    }
+    Some(Self { x, y, z })
+  }
+  
+}
+
+impl Display for BlockPos {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self.y {
+      Some(y) => write!(f, "{} {} {}", self.x, y, self.z),
+      None => write!(f, "{} ~ {}", self.x, self.z)
+    }
+  }
+  
+}
+
+/// A precise position, such as the position of an entity. See [`RconClient::get_position`](crate::RconClient::get_position).
+/// 
+/// When displayed, the coordinates are separated by spaces as commands accept them,
+/// without scientific notation and rounded to at most 6 decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+  
+  /// The x coordinate.
+  pub x: f64,
+  /// The y coordinate.
+  pub y: f64,
+  /// The z coordinate.
+  pub z: f64
+  
+}
+
+impl Vec3 {
+  
+  /// Creates a position from its coordinates.
+  pub fn new(x: f64, y: f64, z: f64) -> Self {
+    Self { x, y, z }
+  }
+  
+  /// Returns whether all of the coordinates are finite, which is required to use them in a command.
+  pub fn is_finite(&self) -> bool {
+    self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+  }
+  
+  fn write_with_prefix(&self, f: &mut Formatter<'_>, prefix: &str) -> fmt::Result {
+    for (i, value) in [self.x, self.y, self.z].into_iter().enumerate() {
+      if i > 0 {
+        write!(f, " ")?;
+      }
+      write!(f, "{}", prefix)?;
+      // relative coordinates of 0 are written as just the prefix
+      if prefix.is_empty() || value != 0.0 {
+        write!(f, "{}", number(value))?;
+      }
+    }
+    Ok(())
+  }
+  
+}
+
+impl Display for Vec3 {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    self.write_with_prefix(f, "")
+  }
+  
+}
+
+/// Coordinates that can be given to a command, which may be relative to the position the command is run at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coordinates {
+  
+  /// The position of a block.
+  Block(BlockPos),
+  /// A precise position.
+  Absolute(Vec3),
+  /// An offset along each axis from the position the command is run at, written with `~`.
+  Relative(Vec3),
+  /// An offset from the position the command is run at, relative to its rotation, written with `^`.
+  /// 
+  /// The coordinates are left, up, and forwards, in that order.
+  Local(Vec3)
+  
+}
+
+impl Coordinates {
+  
+  /// Returns whether all of the coordinates are finite, which is required to use them in a command.
+  pub fn is_finite(&self) -> bool {
+    match self {
+      Coordinates::Block(_) => true,
+      Coordinates::Absolute(pos) | Coordinates::Relative(pos) | Coordinates::Local(pos) => pos.is_finite()
+    }
+  }
+  
+}
+
+impl Display for Coordinates {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Coordinates::Block(pos) => write!(f, "{}", pos),
+      Coordinates::Absolute(pos) => pos.write_with_prefix(f, ""),
+      Coordinates::Relative(pos) => pos.write_with_prefix(f, "~"),
+      Coordinates::Local(pos) => pos.write_with_prefix(f, "^")
+    }
+  }
+  
+}
+
+impl From<BlockPos> for Coordinates {
+  
+  fn from(pos: BlockPos) -> Self {
+    Coordinates::Block(pos)
+  }
+  
+}
+
+impl From<Vec3> for Coordinates {
+  
+  fn from(pos: Vec3) -> Self {
+    Coordinates::Absolute(pos)
+  }
+  
+}
+
+/// Formats a number in a form commands accept: without scientific notation, and with at most 6 decimal places.
+pub(crate) fn number(value: f64) -> String {
+  let text = format!("{:.6}", value);
+  let text = text.trim_end_matches('0').trim_end_matches('.');
+  // avoid writing "-0" for small negative values
+  if text == "-0" {
+    "0".to_owned()
+  } else {
+    text.to_owned()
+  }
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use crate::{CommandError, RconClient};
+
+/// The outcome of [`RconClient::save_all`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SaveOutcome {
+  
+  /// The server confirmed that the game was saved.
+  Saved,
+  /// The server started saving, but did not confirm that it finished,
+  /// either because no wait was requested or because the wait ran out.
+  Started,
+  /// The server reported that saving failed, with the given message.
+  Failed(String)
+  
+}
+
+impl RconClient {
+  
+  /// Saves the game with `save-all`, or `save-all flush` if `flush` is true, and returns the outcome.
+  /// 
+  /// Some servers respond as soon as saving starts, and report completion in a separate packet later.
+  /// If `wait` is given and the first response does not confirm completion,
+  /// this method waits up to that long for a follow-up response confirming completion (or failure).
+  /// Servers that report both in one response, fragmented or not, are handled without waiting.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::{RconClient, commands::SaveOutcome};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.save_off()?;
+  /// if client.save_all(true, Some(Duration::from_secs(30)))? == SaveOutcome::Saved {
+  ///   // back up the world
+  /// }
+  /// client.save_on()?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the server does not respond like it is saving.
+  /// If waiting is interrupted by a packet with a different id, or the follow-up response is not valid UTF-8,
+  /// that is an I/O error of kind [`InvalidData`](std::io::ErrorKind::InvalidData).
+  pub fn save_all(&self, flush: bool, wait: Option<Duration>) -> Result<SaveOutcome, CommandError> {
+    let response = self.send_command_detailed(if flush { "save-all flush" } else { "save-all" })?;
+    let mut outcome = save_outcome(&response)?;
+    if let (SaveOutcome::Started, Some(wait)) = (&outcome, wait) {
+      let deadline = Instant::now() + wait;
+      while let Some(follow_up) = self.read_follow_up(response.request_id, deadline)? {
+        outcome = save_outcome(&follow_up)?;
+        if outcome != SaveOutcome::Started {
+          break
+        }
+      }
+    }
+    Ok(outcome)
+  }
+  
+  /// Disables automatic saving with `save-off`.
+  /// 
+  /// This succeeds whether or not automatic saving was already disabled.
+  /// See [`save_all`](RconClient::save_all) for an example.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the server does not respond like it changed the setting.
+  pub fn save_off(&self) -> Result<(), CommandError> {
+    self.toggle_saving("save-off")
+  }
+  
+  /// Enables automatic saving with `save-on`.
+  /// 
+  /// This succeeds whether or not automatic saving was already enabled.
+  /// See [`save_all`](RconClient::save_all) for an example.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the server does not respond like it changed the setting.
+  pub fn save_on(&self) -> Result<(), CommandError> {
+    self.toggle_saving("save-on")
+  }
+  
+  fn toggle_saving(&self, command: &str) -> Result<(), CommandError> {
+    let response = self.send_command(command)?;
+    // "Automatic saving is now disabled", "Saving is already turned off", or "Turned off world auto-saving" in older versions
+    if response.to_lowercase().contains("saving") {
+      Ok(())
+    } else {
+      Err(CommandError::UnexpectedResponse(response))
+    }
+  }
+  
+}
+
+fn save_outcome(response: &str) -> Result<SaveOutcome, CommandError> {
+  let lower = response.to_lowercase();
+  // completion is "Saved the game" ("Saved the world" in older versions), and may arrive in the same response as "Saving the game..."
+  if lower.contains("saved the") {
+    Ok(SaveOutcome::Saved)
+  } else if lower.contains("unable to save") || lower.contains("failed") {
+    Ok(SaveOutcome::Failed(response.to_owned()))
+  } else if lower.contains("saving") {
+    Ok(SaveOutcome::Started)
+  } else {
+    Err(CommandError::UnexpectedResponse(response.to_owned()))
+  }
+}
\ No newline at end of file
@@ -0,0 +1,166 @@
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::unexpected, Selector};
+
+/// A scoreboard objective. See [`RconClient::scoreboard_list_objectives`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Objective {
+  
+  /// The display name of the objective.
+  /// 
+  /// Minecraft only includes display names when listing objectives,
+  /// so this is the only thing known about each objective.
+  pub display_name: String
+  
+}
+
+impl RconClient {
+  
+  /// Gets the score of `target` for `objective` with `scoreboard players get`, or `None` if the target has no score for it.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// if let Some(deaths) = client.scoreboard_get("Steve", "deaths")? {
+  ///   println!("Steve has died {} times", deaths);
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// A vanilla server's responses are handled like this:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let server = ScriptedServer::new("password", |command| match command {
+  ///   "scoreboard players get Steve deaths" => "Steve has 3 [deaths]",
+  ///   "scoreboard players get Alex deaths" => "Can't get value of deaths for Alex; none is set",
+  ///   "scoreboard players get Steve kills" => "Unknown scoreboard objective 'kills'",
+  ///   "scoreboard players set Steve deaths 5" => "Set [deaths] for Steve to 5",
+  ///   "scoreboard players add Steve deaths 2" => "Added 2 to [deaths] for Steve (now 7)",
+  ///   "scoreboard players remove Steve deaths 3" => "Removed 3 from [deaths] for Steve (now 4)",
+  ///   "scoreboard players set Steve kills 5" => "Unknown scoreboard objective 'kills'",
+  ///   _ => "Unknown or incomplete command, see below for error<--[HERE]"
+  /// }.to_owned())?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// assert_eq!(client.scoreboard_get("Steve", "deaths")?, Some(3));
+  /// assert_eq!(client.scoreboard_get("Alex", "deaths")?, None);
+  /// assert_eq!(client.scoreboard_get("Steve", "kills"), Err(CommandError::UnexpectedResponse("Unknown scoreboard objective 'kills'".into())));
+  /// client.scoreboard_set("Steve", "deaths", 5)?;
+  /// client.scoreboard_add("Steve", "deaths", 2)?;
+  /// client.scoreboard_add("Steve", "deaths", -3)?;
+  /// assert!(matches!(client.scoreboard_set("Steve", "kills", 5), Err(CommandError::UnexpectedResponse(_))));
+  /// assert!(matches!(client.scoreboard_get("Steve Smith", "deaths"), Err(CommandError::InvalidArgument(_))));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `target` or `objective` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds with anything other than a score,
+  /// such as when the objective does not exist.
+  pub fn scoreboard_get(&self, target: impl Into<Selector>, objective: &str) -> Result<Option<i64>, CommandError> {
+    let command = format!("scoreboard players get {} {}", args::selector(target)?, args::objective(objective)?);
+    let response = self.send_supported(&command)?;
+    // "Steve has 3 [deaths]", or "Can't get value of deaths for Steve; none is set"
+    if response.contains("none is set") {
+      return Ok(None)
+    }
+    let (_, score) = response.split_once(" has ").ok_or_else(|| unexpected(&response))?;
+    let score = score.split_whitespace().next().and_then(|score| score.parse().ok()).ok_or_else(|| unexpected(&response))?;
+    Ok(Some(score))
+  }
+  
+  /// Sets the score of `target` for `objective` with `scoreboard players set`.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`scoreboard_get`](RconClient::scoreboard_get).
+  pub fn scoreboard_set(&self, target: impl Into<Selector>, objective: &str, score: i32) -> Result<(), CommandError> {
+    let command = format!("scoreboard players set {} {} {}", args::selector(target)?, args::objective(objective)?, score);
+    // "Set [deaths] for Steve to 3", or "Set [deaths] for 2 entities to 3"
+    self.expect_prefix(&command, "Set ")
+  }
+  
+  /// Adds `amount` (which may be negative) to the score of `target` for `objective` with `scoreboard players add`,
+  /// or `scoreboard players remove` if `amount` is negative.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`scoreboard_get`](RconClient::scoreboard_get).
+  pub fn scoreboard_add(&self, target: impl Into<Selector>, objective: &str, amount: i32) -> Result<(), CommandError> {
+    let (target, objective) = (args::selector(target)?, args::objective(objective)?);
+    // scoreboard players add only accepts non-negative amounts
+    if amount < 0 {
+      let command = format!("scoreboard players remove {} {} {}", target, objective, amount.unsigned_abs());
+      // "Removed 3 from [deaths] for Steve (now 0)"
+      self.expect_prefix(&command, "Removed ")
+    } else {
+      let command = format!("scoreboard players add {} {} {}", target, objective, amount);
+      // "Added 3 to [deaths] for Steve (now 3)"
+      self.expect_prefix(&command, "Added ")
+    }
+  }
+  
+  /// Lists the scoreboard objectives with `scoreboard objectives list`.
+  /// 
+  /// Example:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::Objective};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let mut responses = [
+  ///   "There are 2 objective(s): [Deaths], [Blocks Mined]",
+  ///   "There are no objectives",
+  ///   "Unknown or incomplete command, see below for error<--[HERE]",
+  ///   "Objectives: Deaths"
+  /// ].into_iter();
+  /// let server = ScriptedServer::new("password", move |_| responses.next().unwrap_or_default().to_owned())?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// let objectives = client.scoreboard_list_objectives()?;
+  /// assert_eq!(objectives, [Objective { display_name: "Deaths".into() }, Objective { display_name: "Blocks Mined".into() }]);
+  /// assert_eq!(client.scoreboard_list_objectives()?, []);
+  /// assert_eq!(client.scoreboard_list_objectives(), Err(CommandError::UnsupportedCommand));
+  /// assert!(matches!(client.scoreboard_list_objectives(), Err(CommandError::UnexpectedResponse(_))));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the response cannot be parsed.
+  pub fn scoreboard_list_objectives(&self) -> Result<Vec<Objective>, CommandError> {
+    let response = self.send_supported("scoreboard objectives list")?;
+    // "There are no objectives", or "There are 2 objective(s): [Deaths], [Kills]"
+    if response.starts_with("There are no objectives") {
+      return Ok(Vec::new())
+    }
+    let (_, list) = response.split_once(": ").ok_or_else(|| unexpected(&response))?;
+    let list = list.trim().strip_prefix('[').and_then(|list| list.strip_suffix(']')).ok_or_else(|| unexpected(&response))?;
+    Ok(list.split("], [").map(|display_name| Objective { display_name: display_name.to_owned() }).collect())
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,272 @@
+use std::{fmt::{self, Display, Formatter}, ops::{Bound, RangeBounds}};
+
+use crate::CommandError;
+
+use super::{args, position::number};
+
+/// A game mode, as used by [`Selector::gamemode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameMode {
+  
+  /// Survival mode.
+  Survival,
+  /// Creative mode.
+  Creative,
+  /// Adventure mode.
+  Adventure,
+  /// Spectator mode.
+  Spectator
+  
+}
+
+impl Display for GameMode {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let mode = match self {
+      GameMode::Survival => "survival",
+      GameMode::Creative => "creative",
+      GameMode::Adventure => "adventure",
+      GameMode::Spectator => "spectator"
+    };
+    write!(f, "{}", mode)
+  }
+  
+}
+
+/// The order in which a [`Selector`] picks entities, as used by [`Selector::sort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sort {
+  
+  /// Nearest first.
+  Nearest,
+  /// Furthest first.
+  Furthest,
+  /// In a random order.
+  Random,
+  /// In an unspecified order, which is the fastest.
+  Arbitrary
+  
+}
+
+impl Display for Sort {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let sort = match self {
+      Sort::Nearest => "nearest",
+      Sort::Furthest => "furthest",
+      Sort::Random => "random",
+      Sort::Arbitrary => "arbitrary"
+    };
+    write!(f, "{}", sort)
+  }
+  
+}
+
+/// The entities targeted by a command, given by a player name or a target selector like `@a[distance=..10]`.
+/// 
+/// The typed command wrappers accept anything that converts into a `Selector`.
+/// Strings are passed through verbatim (apart from rejecting ones that could not be safely inserted into a command),
+/// so any selector Minecraft understands can be used;
+/// the constructors and filters here build selectors that are checked before anything is sent.
+/// 
+/// Filters are only checked when the selector is used, where a problem with any of them
+/// makes the command wrapper fail with [`CommandError::InvalidArgument`] without sending anything.
+/// 
+/// ```
+/// # use mc_rcon::commands::{GameMode, Selector, Sort};
+/// let selector = Selector::all().distance(..=10.0).tag("builder").gamemode(GameMode::Creative);
+/// assert_eq!(selector.to_string(), "@a[distance=..10,tag=builder,gamemode=creative]");
+/// let selector = Selector::entities().sort(Sort::Nearest).limit(3);
+/// assert_eq!(selector.to_string(), "@e[sort=nearest,limit=3]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Selector {
+  
+  base: Base,
+  filters: Vec<(&'static str, String)>,
+  error: Option<String>
+  
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Base {
+  
+  Verbatim(String),
+  Player(String),
+  Variable(char)
+  
+}
+
+impl Selector {
+  
+  fn new(base: Base) -> Self {
+    Self { base, filters: Vec::new(), error: None }
+  }
+  
+  /// Selects the player with the given name.
+  /// 
+  /// The name must be a valid Minecraft username: 1 to 16 letters, digits, or underscores.
+  pub fn player(name: &str) -> Self {
+    let mut selector = Self::new(Base::Player(name.to_owned()));
+    let valid = (1..=16).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+      selector.error = Some(format!("invalid player name: {:?}", name));
+    }
+    selector
+  }
+  
+  /// Selects all players, with `@a`.
+  pub fn all() -> Self {
+    Self::new(Base::Variable('a'))
+  }
+  
+  /// Selects the nearest player, with `@p`.
+  /// 
+  /// For commands sent over RCON, this is the player nearest to the world spawn.
+  pub fn nearest() -> Self {
+    Self::new(Base::Variable('p'))
+  }
+  
+  /// Selects a random player, with `@r`.
+  pub fn random() -> Self {
+    Self::new(Base::Variable('r'))
+  }
+  
+  /// Selects all entities, including players, with `@e`.
+  pub fn entities() -> Self {
+    Self::new(Base::Variable('e'))
+  }
+  
+  /// Only selects entities whose distance (from the world spawn, for commands sent over RCON) is in the given range.
+  /// 
+  /// Minecraft ranges always include both ends, so exclusive upper bounds like `..10.0` are treated the same as `..=10.0`.
+  /// The bounds must be finite and non-negative, and at least one must be given.
+  pub fn distance(self, range: impl RangeBounds<f64>) -> Self {
+    let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+    match range_filter(bounds) {
+      Ok(range) => self.filter("distance", range),
+      Err(e) => self.fail(format!("invalid distance: {}", e))
+    }
+  }
+  
+  /// Only selects entities with the given tag, or without it if the tag starts with `!`.
+  /// 
+  /// This can be used more than once to require several tags.
+  pub fn tag(self, tag: &str) -> Self {
+    let name = tag.strip_prefix('!').unwrap_or(tag);
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || "_-.+".contains(c)) {
+      self.filter("tag", tag.to_owned())
+    } else {
+      self.fail(format!("invalid tag: {:?}", tag))
+    }
+  }
+  
+  /// Selects at most `limit` entities, which must be at least 1.
+  pub fn limit(self, limit: u32) -> Self {
+    if limit == 0 {
+      self.fail("selector limit must be at least 1".to_owned())
+    } else {
+      self.filter("limit", limit.to_string())
+    }
+  }
+  
+  /// Only selects players in the given game mode.
+  pub fn gamemode(self, mode: GameMode) -> Self {
+    self.filter("gamemode", mode.to_string())
+  }
+  
+  /// Picks entities in the given order, which matters when combined with [`limit`](Selector::limit).
+  pub fn sort(self, sort: Sort) -> Self {
+    self.filter("sort", sort.to_string())
+  }
+  
+  fn filter(mut self, key: &'static str, value: String) -> Self {
+    if matches!(self.base, Base::Variable(_)) {
+      self.filters.push((key, value));
+      self
+    } else {
+      self.fail(format!("{} can only be applied to target selector variables", key))
+    }
+  }
+  
+  fn fail(mut self, error: String) -> Self {
+    // keep the first problem, since later ones may be caused by it
+    self.error.get_or_insert(error);
+    self
+  }
+  
+  /// Returns this selector as a command argument,
+  /// or [`CommandError::InvalidArgument`] if it is invalid or could not be safely inserted into a command.
+  pub(crate) fn argument(&self) -> Result<String, CommandError> {
+    if let Some(error) = &self.error {
+      Err(CommandError::InvalidArgument(error.clone()))?
+    }
+    if let Base::Verbatim(target) = &self.base {
+      args::target(target)?;
+    }
+    Ok(self.to_string())
+  }
+  
+}
+
+impl Display for Selector {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match &self.base {
+      Base::Verbatim(target) | Base::Player(target) => write!(f, "{}", target)?,
+      Base::Variable(variable) => write!(f, "@{}", variable)?
+    }
+    for (i, (key, value)) in self.filters.iter().enumerate() {
+      write!(f, "{}{}={}", if i == 0 { '[' } else { ',' }, key, value)?;
+    }
+    if !self.filters.is_empty() {
+      write!(f, "]")?;
+    }
+    Ok(())
+  }
+  
+}
+
+impl From<&str> for Selector {
+  
+  fn from(target: &str) -> Self {
+    Selector::new(Base::Verbatim(target.to_owned()))
+  }
+  
+}
+
+impl From<String> for Selector {
+  
+  fn from(target: String) -> Self {
+    Selector::new(Base::Verbatim(target))
+  }
+  
+}
+
+impl From<&Selector> for Selector {
+  
+  fn from(selector: &Selector) -> Self {
+    selector.clone()
+  }
+  
+}
+
+/// Formats a range of non-negative numbers as Minecraft accepts them, like `..10`, `5..`, `5..10`, or `5`.
+fn range_filter(bounds: (Bound<f64>, Bound<f64>)) -> Result<String, &'static str> {
+  let bound = |bound: Bound<f64>| match bound {
+    Bound::Included(value) | Bound::Excluded(value) => Some(value),
+    Bound::Unbounded => None
+  };
+  let (min, max) = (bound(bounds.0), bound(bounds.1));
+  if min.into_iter().chain(max).any(|value| !value.is_finite() || value < 0.0) {
+    Err("bounds must be finite and non-negative")?
+  }
+  match (min, max) {
+    (None, None) => Err("at least one bound must be given"),
+    (Some(min), Some(max)) if min > max => Err("lower bound is greater than upper bound"),
+    (Some(min), Some(max)) if min == max => Ok(number(min)),
+    (Some(min), Some(max)) => Ok(format!("{}..{}", number(min), number(max))),
+    (Some(min), None) => Ok(format!("{}..", number(min))),
+    (None, Some(max)) => Ok(format!("..{}", number(max)))
+  }
+}
\ No newline at end of file
@@ -0,0 +1,311 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{response::minecraft::ListResponse, CommandError, RconClient};
+
+use super::{args, parse::unexpected, Selector};
+
+/// The difficulty of the game. See [`RconClient::difficulty`].
+/// 
+/// When displayed, a difficulty is written as commands accept it, like `peaceful`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+  
+  /// Peaceful.
+  Peaceful,
+  /// Easy.
+  Easy,
+  /// Normal.
+  Normal,
+  /// Hard.
+  Hard
+  
+}
+
+impl Display for Difficulty {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let difficulty = match self {
+      Difficulty::Peaceful => "peaceful",
+      Difficulty::Easy => "easy",
+      Difficulty::Normal => "normal",
+      Difficulty::Hard => "hard"
+    };
+    write!(f, "{}", difficulty)
+  }
+  
+}
+
+/// The outcome of [`RconClient::whitelist_add`] or [`RconClient::whitelist_remove`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WhitelistOutcome {
+  
+  /// The player was added to the whitelist.
+  Added,
+  /// The player was removed from the whitelist.
+  Removed,
+  /// The player was already on the whitelist, so nothing changed.
+  AlreadyWhitelisted,
+  /// The player was not on the whitelist, so nothing changed.
+  NotWhitelisted,
+  /// No player with the given name exists.
+  PlayerNotFound
+  
+}
+
+/// A Minecraft version number, like 1.20.4. See [`RconClient::minecraft_version`].
+/// 
+/// Versions compare in release order, so they can be used to decide which command syntax a server accepts.
+/// A version without a patch number, like 1.21, has a patch number of 0, and is displayed without it.
+/// 
+/// ```
+/// # use mc_rcon::commands::MinecraftVersion;
+/// let version = MinecraftVersion::parse("1.20.6").unwrap();
+/// assert!(version.at_least(1, 20, 5));
+/// assert!(version < MinecraftVersion::parse("1.21").unwrap());
+/// assert_eq!(MinecraftVersion::new(1, 21, 0).to_string(), "1.21");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinecraftVersion {
+  
+  /// The major version, which has been 1 for every release so far.
+  pub major: u32,
+  /// The minor version, like 20 in 1.20.4.
+  pub minor: u32,
+  /// The patch version, like 4 in 1.20.4.
+  pub patch: u32
+  
+}
+
+impl MinecraftVersion {
+  
+  /// Returns the version with the given numbers.
+  pub const fn new(major: u32, minor: u32, patch: u32) -> MinecraftVersion {
+    MinecraftVersion { major, minor, patch }
+  }
+  
+  /// Parses a version number like `1.20` or `1.20.4`, as returned by [`RconClient::server_version`].
+  pub fn parse(version: &str) -> Option<MinecraftVersion> {
+    let mut parts = version.split('.').map(|part| part.parse().ok());
+    let (major, minor) = (parts.next()??, parts.next()??);
+    let patch = parts.next().unwrap_or(Some(0))?;
+    parts.next().is_none().then_some(MinecraftVersion { major, minor, patch })
+  }
+  
+  /// Returns whether this version is the given version or a later one.
+  pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+    *self >= MinecraftVersion::new(major, minor, patch)
+  }
+  
+}
+
+impl Display for MinecraftVersion {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}.{}", self.major, self.minor)?;
+    if self.patch != 0 {
+      write!(f, ".{}", self.patch)?;
+    }
+    Ok(())
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Lists the players online with `list`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let list = client.list()?;
+  /// println!("{}/{} players online: {}", list.online, list.max, list.players.join(", "));
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the response cannot be parsed.
+  pub fn list(&self) -> Result<ListResponse, CommandError> {
+    let response = self.send_supported("list")?;
+    self.response_matcher().parse_list(&response).ok_or_else(|| unexpected(&response))
+  }
+  
+  /// Gets the world seed with `seed`.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`list`](RconClient::list).
+  pub fn seed(&self) -> Result<i64, CommandError> {
+    let response = self.send_supported("seed")?;
+    self.response_matcher().parse_seed(&response).ok_or_else(|| unexpected(&response))
+  }
+  
+  /// Gets the Minecraft version that the server runs with `version`, like `1.20.4`,
+  /// or `None` if the server does not have the command or its response is in a format this method does not recognize.
+  /// 
+  /// Vanilla servers have the command since 1.21.6, and Bukkit derivatives like Paper have long had it.
+  /// Only the numeric part of the version is returned, which [`MinecraftVersion::parse`] understands
+  /// (or use [`minecraft_version`](RconClient::minecraft_version) to get it parsed);
+  /// snapshots and other versions without one give `None`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("The server runs Minecraft {}", client.server_version()?.as_deref().unwrap_or("of an unknown version"));
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command).
+  pub fn server_version(&self) -> Result<Option<String>, CommandError> {
+    match self.send_supported("version") {
+      Ok(response) => Ok(parse_version(&response)),
+      Err(CommandError::UnsupportedCommand) => Ok(None),
+      Err(e) => Err(e)
+    }
+  }
+  
+  /// Gets the Minecraft version that the server runs like [`server_version`](RconClient::server_version), but parsed,
+  /// so that it can be compared to decide which command syntax to use.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// if client.minecraft_version()?.is_some_and(|version| version.at_least(1, 20, 5)) {
+  ///   client.send_command("give Steve minecraft:diamond_sword[minecraft:unbreakable={}]")?;
+  /// } else {
+  ///   client.send_command("give Steve minecraft:diamond_sword{Unbreakable:1b}")?;
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`server_version`](RconClient::server_version).
+  pub fn minecraft_version(&self) -> Result<Option<MinecraftVersion>, CommandError> {
+    let version = self.server_version()?.as_deref().and_then(MinecraftVersion::parse);
+    *self.version_cache.lock().unwrap_or_else(|e| e.into_inner()) = Some(version);
+    Ok(version)
+  }
+  
+  /// Returns the server's version like [`minecraft_version`](RconClient::minecraft_version),
+  /// but only queries it if it has not been since the client connected.
+  pub(crate) fn cached_minecraft_version(&self) -> Result<Option<MinecraftVersion>, CommandError> {
+    let cached = *self.version_cache.lock().unwrap_or_else(|e| e.into_inner());
+    match cached {
+      Some(version) => Ok(version),
+      None => self.minecraft_version()
+    }
+  }
+  
+  /// Gets the difficulty with `difficulty`.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`list`](RconClient::list).
+  pub fn difficulty(&self) -> Result<Difficulty, CommandError> {
+    let response = self.send_supported("difficulty")?;
+    self.response_matcher().parse_difficulty(&response).ok_or_else(|| unexpected(&response))
+  }
+  
+  /// Sets the difficulty with `difficulty`.
+  /// 
+  /// Setting the difficulty to what it already is succeeds.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the response does not mention the new difficulty.
+  pub fn set_difficulty(&self, difficulty: Difficulty) -> Result<(), CommandError> {
+    let response = self.send_supported(&format!("difficulty {}", difficulty))?;
+    // "The difficulty has been set to Hard", or "The difficulty did not change; it is already set to Hard"
+    if self.response_matcher().parse_difficulty(&response) == Some(difficulty) {
+      Ok(())
+    } else {
+      Err(unexpected(&response))
+    }
+  }
+  
+  /// Adds `player` to the whitelist with `whitelist add`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::WhitelistOutcome};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// match client.whitelist_add("Steve")? {
+  ///   WhitelistOutcome::Added | WhitelistOutcome::AlreadyWhitelisted => println!("Steve can join"),
+  ///   outcome => println!("could not whitelist Steve: {:?}", outcome)
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `player` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the response does not describe a [`WhitelistOutcome`].
+  pub fn whitelist_add(&self, player: impl Into<Selector>) -> Result<WhitelistOutcome, CommandError> {
+    self.whitelist("add", player)
+  }
+  
+  /// Removes `player` from the whitelist with `whitelist remove`.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`whitelist_add`](RconClient::whitelist_add).
+  pub fn whitelist_remove(&self, player: impl Into<Selector>) -> Result<WhitelistOutcome, CommandError> {
+    self.whitelist("remove", player)
+  }
+  
+  fn whitelist(&self, action: &str, player: impl Into<Selector>) -> Result<WhitelistOutcome, CommandError> {
+    let response = self.send_supported(&format!("whitelist {} {}", action, args::selector(player)?))?;
+    self.response_matcher().whitelist_outcome(&response).ok_or_else(|| unexpected(&response))
+  }
+  
+}
+
+fn parse_version(response: &str) -> Option<String> {
+  // "This server is running Paper version 1.20.4-496-ver/1.20.4@7ac24a1 (2024-05-10T13:08:26Z) (Implementing API version 1.20.4-R0.1-SNAPSHOT)"
+  // in Bukkit derivatives, which used to write "(MC: 1.20.4)" after their own version instead
+  if let Some((_, rest)) = response.split_once("(MC: ") {
+    return version_number(rest)
+  }
+  if let Some((_, rest)) = response.split_once("running Paper version ") {
+    return version_number(rest)
+  }
+  // "Server version info:\nid = 1.21.6\nname = 1.21.6\ndata = 4435\n..." in vanilla
+  response.lines().find_map(|line| line.trim().strip_prefix("id = ")).and_then(version_number)
+}
+
+/// Takes a version number like `1.20` or `1.20.4` from the start of `text`.
+fn version_number(text: &str) -> Option<String> {
+  let end = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+  let version = text[..end].trim_end_matches('.');
+  MinecraftVersion::parse(version).map(|_| version.to_owned())
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use std::{error::Error, fmt::{self, Display, Formatter}, io::{self, ErrorKind, Read}, time::{Duration, Instant}};
+
+use crate::{is_closed, timed_out, CommandError, RconClient, ReadTimeoutGuard};
+
+/// A failed attempt to stop the server. See [`RconClient::stop`] for details.
+#[derive(Debug)]
+pub enum StopError {
+  
+  /// The `stop` command could not be sent.
+  Command(CommandError),
+  /// The `stop` command was sent, but the server did not close the connection in time.
+  TimedOut,
+  /// The `stop` command was sent, but an I/O error occurred while waiting for the server to close the connection.
+  IO(io::Error)
+  
+}
+
+impl From<CommandError> for StopError {
+  
+  fn from(e: CommandError) -> Self {
+    StopError::Command(e)
+  }
+  
+}
+
+impl Display for StopError {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      StopError::Command(e) => write!(f, "failed to send stop command: {}", e),
+      StopError::TimedOut => write!(f, "timed out waiting for the server to stop"),
+      StopError::IO(e) => write!(f, "failed while waiting for the server to stop: {}", e)
+    }
+  }
+  
+}
+
+impl Error for StopError {}
+
+impl RconClient {
+  
+  /// Stops the server with `stop`, consuming this client.
+  /// 
+  /// Once the server has shut down, it closes the connection.
+  /// If `wait` is given, this method blocks until that happens, or until `wait` has passed,
+  /// so that callers can reliably do things that require the server to be stopped, like backing up the world.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.stop(Some(Duration::from_secs(60)))?;
+  /// // the server has stopped, so the world can be backed up
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// * If the command could not be sent, returns [`StopError::Command`] with the same errors as [`send_command`](RconClient::send_command).
+  ///   Note that if the server closes the connection before responding, that is not an error.
+  /// * If `wait` passes before the server closes the connection, returns [`StopError::TimedOut`].
+  /// * If any other I/O errors occur while waiting, returns [`StopError::IO`] with the error.
+  pub fn stop(self, wait: Option<Duration>) -> Result<(), StopError> {
+    match self.send_command("stop") {
+      Ok(_) => {}
+      Err(CommandError::IO(e)) if is_closed(&e) => return Ok(()),
+      Err(e) => Err(e)?
+    }
+    if let Some(wait) = wait {
+      let deadline = Instant::now() + wait;
+      let stream = self.into_parts();
+      let guard = ReadTimeoutGuard::new(&stream).map_err(StopError::IO)?;
+      let mut stream = &stream;
+      let mut buf = [0; 256];
+      loop {
+        let read = guard.limit_until(deadline).and_then(|()| stream.read(&mut buf)).map_err(timed_out);
+        match read {
+          Ok(0) => break, // the server closed the connection
+          Ok(_) => {} // discard anything the server sends while shutting down
+          Err(e) if e.kind() == ErrorKind::Interrupted => {}
+          Err(e) if e.kind() == ErrorKind::TimedOut => Err(StopError::TimedOut)?,
+          Err(e) if is_closed(&e) => break,
+          Err(e) => Err(StopError::IO(e))?
+        }
+      }
+    }
+    Ok(())
+  }
+  
+}
\ No newline at end of file
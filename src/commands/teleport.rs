@@ -0,0 +1,181 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{snbt::SnbtValue, CommandError, RconClient};
+
+use super::{args, parse::unexpected, position::number, Coordinates, DataTarget, Selector, Vec3};
+
+/// Where to teleport to with [`RconClient::tp`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeleportDest {
+  
+  /// The position of an entity, given by a player name, UUID, or selector that matches exactly one entity.
+  Entity(Selector),
+  /// The given coordinates, optionally also setting the rotation of the teleported entities.
+  Position {
+    
+    /// The coordinates to teleport to.
+    /// 
+    /// Relative and local coordinates are relative to the position the command is run at,
+    /// which for RCON is the world spawn, not the teleported entities.
+    pos: Coordinates,
+    /// The rotation to give the teleported entities, or `None` to keep their current rotation.
+    rotation: Option<Rotation>
+    
+  }
+  
+}
+
+impl Display for TeleportDest {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      TeleportDest::Entity(target) => write!(f, "{}", target),
+      TeleportDest::Position { pos, rotation: None } => write!(f, "{}", pos),
+      TeleportDest::Position { pos, rotation: Some(rotation) } => write!(f, "{} {}", pos, rotation)
+    }
+  }
+  
+}
+
+impl<T: Into<Coordinates>> From<T> for TeleportDest {
+  
+  fn from(pos: T) -> Self {
+    TeleportDest::Position { pos: pos.into(), rotation: None }
+  }
+  
+}
+
+/// The direction an entity is facing, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rotation {
+  
+  /// The horizontal rotation, where 0 is south and 90 is west.
+  pub yaw: f32,
+  /// The vertical rotation, where -90 is straight up and 90 is straight down.
+  pub pitch: f32
+  
+}
+
+impl Display for Rotation {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {}", number(self.yaw.into()), number(self.pitch.into()))
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Teleports `target` to `dest` with `tp`.
+  /// 
+  /// Anything that converts to [`Coordinates`], like a [`BlockPos`](super::BlockPos) or [`Vec3`], can be given as the destination.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::{BlockPos, TeleportDest}};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.tp("Steve", BlockPos::new(0, 64, 0))?;
+  /// client.tp("@a", TeleportDest::Entity("Steve".into()))?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// A position set with this method can be read back with [`get_position`](RconClient::get_position):
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::{Coordinates, Rotation, TeleportDest, Vec3}};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let mut steve = Vec3::new(0.5, 64.0, 0.5);
+  /// let server = ScriptedServer::new("password", move |command| match command.split(' ').collect::<Vec<_>>()[..] {
+  ///   ["tp", "Steve", x, y, z] => {
+  ///     steve = Vec3::new(x.parse().unwrap(), y.parse().unwrap(), z.parse().unwrap());
+  ///     format!("Teleported Steve to {:.6}, {:.6}, {:.6}", steve.x, steve.y, steve.z)
+  ///   }
+  ///   ["tp", "Steve", "~", "~10", "~", "90", "0"] => "Teleported Steve to 0.500000, 74.000000, 0.500000".to_owned(),
+  ///   ["tp", "Alex", ..] => "No entity was found".to_owned(),
+  ///   ["data", "get", "entity", "Steve", "Pos"] => format!("Steve has the following entity data: [{:?}d, {:?}d, {:?}d]", steve.x, steve.y, steve.z),
+  ///   // as if teleporting to other entities were disabled, for example by a plugin
+  ///   _ => "Unknown or incomplete command, see below for error<--[HERE]".to_owned()
+  /// })?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// assert_eq!(client.get_position("Steve")?, Vec3::new(0.5, 64.0, 0.5));
+  /// let pos = Vec3::new(-12345678.25, 72.0, 0.1);
+  /// assert_eq!(TeleportDest::from(pos).to_string(), "-12345678.25 72 0.1");
+  /// client.tp("Steve", pos)?;
+  /// assert_eq!(client.get_position("Steve")?, pos);
+  /// 
+  /// let relative = TeleportDest::Position { pos: Coordinates::Relative(Vec3::new(0.0, 10.0, 0.0)), rotation: Some(Rotation { yaw: 90.0, pitch: 0.0 }) };
+  /// client.tp("Steve", relative)?;
+  /// assert!(matches!(client.tp("Alex", pos), Err(CommandError::UnexpectedResponse(_))));
+  /// assert_eq!(client.tp("@e", TeleportDest::Entity("Steve".into())), Err(CommandError::UnsupportedCommand));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if either entity could not be safely inserted into a command
+  /// or any coordinate or rotation is not finite,
+  /// or with [`CommandError::UnexpectedResponse`] if the server does not confirm the teleport,
+  /// such as when no entity matches `target`.
+  pub fn tp(&self, target: impl Into<Selector>, dest: impl Into<TeleportDest>) -> Result<(), CommandError> {
+    let dest = dest.into();
+    match &dest {
+      TeleportDest::Entity(target) => {
+        args::selector(target)?;
+      }
+      TeleportDest::Position { pos, rotation } => {
+        let finite_rotation = rotation.is_none_or(|rotation| rotation.yaw.is_finite() && rotation.pitch.is_finite());
+        if !pos.is_finite() || !finite_rotation {
+          Err(CommandError::InvalidArgument(format!("non-finite destination: {}", dest)))?
+        }
+      }
+    }
+    // "Teleported Steve to 0.500000, 64.000000, 0.500000", or "Teleported 2 entities to Steve"
+    self.expect_prefix(&format!("tp {} {}", args::selector(target)?, dest), "Teleported ")
+  }
+  
+  /// Gets the position of `target` from its `Pos` data with `data get`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let pos = client.get_position("Steve")?;
+  /// println!("Steve is at {}", pos);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`data_get`](RconClient::data_get),
+  /// or with [`CommandError::UnexpectedResponse`] if the data is not a position.
+  pub fn get_position(&self, target: impl Into<Selector>) -> Result<Vec3, CommandError> {
+    let pos = self.data_get(DataTarget::Entity(target.into()), Some("Pos"))?;
+    match &pos {
+      SnbtValue::List(coordinates) if coordinates.len() == 3 => {
+        let coordinate = |i: usize| coordinates[i].as_f64().ok_or_else(|| unexpected(&pos.to_string()));
+        Ok(Vec3::new(coordinate(0)?, coordinate(1)?, coordinate(2)?))
+      }
+      _ => Err(unexpected(&pos.to_string()))
+    }
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use crate::{CommandError, RconClient};
+
+use super::parse::{decimal, unexpected};
+
+/// The average ticks per second over several periods, as reported by Paper and its forks. See [`RconClient::tps`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TpsReport {
+  
+  /// The average TPS over the last minute.
+  pub last_1m: f64,
+  /// The average TPS over the last 5 minutes.
+  pub last_5m: f64,
+  /// The average TPS over the last 15 minutes.
+  pub last_15m: f64
+  
+}
+
+/// Tick time statistics in milliseconds over several periods, as reported by Paper and its forks. See [`RconClient::mspt`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsptReport {
+  
+  /// Tick times over the last 5 seconds.
+  pub last_5s: MsptStats,
+  /// Tick times over the last 10 seconds.
+  pub last_10s: MsptStats,
+  /// Tick times over the last minute.
+  pub last_1m: MsptStats
+  
+}
+
+/// Tick time statistics in milliseconds over one period. See [`MsptReport`].
+/// 
+/// Different versions of Paper report different statistics, so each one is present only if the server reported it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsptStats {
+  
+  /// The mean tick time.
+  pub avg: Option<f64>,
+  /// The shortest tick time.
+  pub min: Option<f64>,
+  /// The median tick time.
+  pub median: Option<f64>,
+  /// The 95th percentile tick time.
+  pub p95: Option<f64>,
+  /// The longest tick time.
+  pub max: Option<f64>
+  
+}
+
+impl RconClient {
+  
+  /// Queries the server's average ticks per second with Paper's `tps` command.
+  /// 
+  /// The response, for example `§6TPS from last 1m, 5m, 15m: §a20.0, §a*20.0, §a19.98`, is parsed after removing its formatting codes.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("TPS over the last minute: {}", client.tps()?.last_1m);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// Responses from Paper 1.20 and 1.21, and from a vanilla server, are handled like this:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::TpsReport};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let mut responses = [
+  ///   "§6TPS from last 1m, 5m, 15m: §a*20.0, §a*20.0, §a19.98", // Paper 1.20 (TPS above 20 is capped and starred)
+  ///   "§6TPS from last 1m, 5m, 15m: §e17.52, §a19.43, §a19.81", // Paper 1.21
+  ///   "Unknown or incomplete command, see below for error<--[HERE]", // vanilla
+  ///   "§6TPS from last 1m, 5m, 15m: §a20.0, §a20.0" // malformed
+  /// ].into_iter();
+  /// let server = ScriptedServer::new("password", move |_| responses.next().unwrap_or_default().to_owned())?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// assert_eq!(client.tps()?, TpsReport { last_1m: 20.0, last_5m: 20.0, last_15m: 19.98 });
+  /// assert_eq!(client.tps()?, TpsReport { last_1m: 17.52, last_5m: 19.43, last_15m: 19.81 });
+  /// assert_eq!(client.tps(), Err(CommandError::UnsupportedCommand));
+  /// assert!(matches!(client.tps(), Err(CommandError::UnexpectedResponse(_))));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::UnsupportedCommand`] if the server does not have the `tps` command (e.g. a vanilla server),
+  /// or with [`CommandError::UnexpectedResponse`] if the response cannot be parsed.
+  pub fn tps(&self) -> Result<TpsReport, CommandError> {
+    let response = self.send_command("tps")?;
+    parse_tps(&self.supported(&response)?).ok_or_else(|| unexpected(&response))
+  }
+  
+  /// Queries the server's tick times with Paper's `mspt` command.
+  /// 
+  /// Paper 1.20 reports the average, shortest, and longest tick times, for example:
+  /// 
+  /// ```text
+  /// §6Server tick times §e(§7avg§e/§7min§e/§7max§e)§6 from last 5s§7,§6 10s§7,§6 1m§e:
+  /// §6◴ §a6.2§7/§a3.1§7/§a13.1§7, §a6.1§7/§a3.1§7/§a13.1§7, §a6.6§7/§a3.0§7/§a30.8
+  /// ```
+  /// 
+  /// Newer versions report other statistics, such as `(min/med/95%ile/max)`, with each period on its own line;
+  /// this method reads the statistic names from the response, so it handles either.
+  /// 
+  /// Example:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::MsptStats};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let mut responses = [
+  ///   // Paper 1.20
+  ///   "§6Server tick times §e(§7avg§e/§7min§e/§7max§e)§6 from last 5s§7,§6 10s§7,§6 1m§e:\n\
+  ///    §6◴ §a6.2§7/§a3.1§7/§a13.1§7, §a6.1§7/§a3.1§7/§a13.1§7, §a6.6§7/§a3.0§7/§a30.8",
+  ///   // Paper 1.21
+  ///   "§6Server tick times §e(§7min§e/§7med§e/§795%ile§e/§7max§e)§6 from last 5s§7,§6 10s§7,§6 1m§e:\n\
+  ///    §6◴ §a0.4§7/§a1.1§7/§a3.2§7/§a7.2\n\
+  ///    §6◴ §a0.4§7/§a1.0§7/§a3.0§7/§a7.2\n\
+  ///    §6◴ §a0.3§7/§a1.0§7/§e2.8§7/§c52.5",
+  ///   // vanilla
+  ///   "Unknown or incomplete command, see below for error<--[HERE]",
+  ///   // malformed
+  ///   "§6Server tick times §e(§7avg§e/§7min§e/§7max§e)§6 from last 5s§7,§6 10s§7,§6 1m§e:\n§6◴ §a6.2§7/§a3.1"
+  /// ].into_iter();
+  /// let server = ScriptedServer::new("password", move |_| responses.next().unwrap_or_default().to_owned())?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// 
+  /// let report = client.mspt()?;
+  /// assert_eq!(report.last_5s, MsptStats { avg: Some(6.2), min: Some(3.1), max: Some(13.1), ..MsptStats::default() });
+  /// assert_eq!(report.last_1m, MsptStats { avg: Some(6.6), min: Some(3.0), max: Some(30.8), ..MsptStats::default() });
+  /// 
+  /// let report = client.mspt()?;
+  /// assert_eq!(report.last_5s, MsptStats { min: Some(0.4), median: Some(1.1), p95: Some(3.2), max: Some(7.2), ..MsptStats::default() });
+  /// assert_eq!(report.last_10s.median, Some(1.0));
+  /// assert_eq!(report.last_1m, MsptStats { min: Some(0.3), median: Some(1.0), p95: Some(2.8), max: Some(52.5), ..MsptStats::default() });
+  /// 
+  /// assert_eq!(client.mspt(), Err(CommandError::UnsupportedCommand));
+  /// assert!(matches!(client.mspt(), Err(CommandError::UnexpectedResponse(_))));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`tps`](RconClient::tps).
+  pub fn mspt(&self) -> Result<MsptReport, CommandError> {
+    let response = self.send_command("mspt")?;
+    parse_mspt(&self.supported(&response)?).ok_or_else(|| unexpected(&response))
+  }
+  
+}
+
+fn parse_tps(response: &str) -> Option<TpsReport> {
+  // "TPS from last 1m, 5m, 15m: 20.0, *20.0, 19.98"
+  let line = response.lines().find(|line| line.contains("TPS from last"))?;
+  let (_, values) = line.rsplit_once(':')?;
+  let mut values = values.split(',').map(decimal);
+  let report = TpsReport { last_1m: values.next()??, last_5m: values.next()??, last_15m: values.next()?? };
+  values.next().is_none().then_some(report)
+}
+
+fn parse_mspt(response: &str) -> Option<MsptReport> {
+  // "Server tick times (avg/min/max) from last 5s, 10s, 1m:\n◴ 6.2/3.1/13.1, 6.1/3.1/13.1, 6.6/3.0/30.8"
+  let (header, rest) = response.split_once('\n')?;
+  let labels = header.split_once('(')?.1.split_once(')')?.0;
+  let labels: Vec<_> = labels.split('/').map(|label| label.trim().to_lowercase()).collect();
+  // windows may be separated by commas, newlines, or both, and lines may start with a symbol like "◴"
+  let windows = rest.split([',', '\n']).map(|window| window.trim_start_matches(|c: char| !c.is_ascii_digit() && c != '*')).filter(|window| !window.trim().is_empty());
+  let mut windows = windows.map(|window| {
+    let mut stats = MsptStats::default();
+    let values: Vec<_> = window.split('/').collect();
+    if values.len() != labels.len() {
+      return None
+    }
+    for (label, value) in labels.iter().zip(values) {
+      let value = Some(decimal(value)?);
+      match label.as_str() {
+        "avg" | "mean" => stats.avg = value,
+        "min" => stats.min = value,
+        "med" | "median" => stats.median = value,
+        "95%ile" | "95th" | "p95" => stats.p95 = value,
+        "max" => stats.max = value,
+        _ => {}
+      }
+    }
+    Some(stats)
+  });
+  let report = MsptReport { last_5s: windows.next()??, last_10s: windows.next()??, last_1m: windows.next()?? };
+  windows.next().is_none().then_some(report)
+}
\ No newline at end of file
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use crate::{CommandError, RconClient};
+
+use super::{parse::unexpected, position::number};
+
+/// The largest world border diameter that vanilla servers accept, in blocks.
+pub const MAX_WORLD_BORDER_DIAMETER: f64 = 59_999_968.0;
+
+/// The furthest from the origin that vanilla servers accept the world border's center, in blocks along each axis.
+const MAX_WORLD_BORDER_CENTER: f64 = 29_999_984.0;
+
+impl RconClient {
+  
+  /// Gets the diameter of the world border in blocks with `worldborder get`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("The world is {} blocks wide", client.worldborder_get()?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// The phrasing of older versions is understood too:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// for response in ["The world border is currently 59999968 block(s) wide", "World border is currently 60000000 blocks wide"] {
+  ///   let server = ScriptedServer::new("password", move |_| response.to_owned())?;
+  ///   let client = RconClient::connect(server.local_addr())?;
+  ///   client.log_in("password")?;
+  ///   assert!(client.worldborder_get()? >= 59_999_968.0);
+  ///   drop(client);
+  ///   server.join()?;
+  /// }
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::UnexpectedResponse`] if the response does not contain a diameter.
+  pub fn worldborder_get(&self) -> Result<f64, CommandError> {
+    let response = self.send_supported("worldborder get")?;
+    self.response_matcher().parse_worldborder(&response).ok_or_else(|| unexpected(&response))
+  }
+  
+  /// Sets the diameter of the world border in blocks with `worldborder set`,
+  /// moving it gradually `over` the given time (in whole seconds, rounded down) if one is given.
+  /// 
+  /// Diameters larger than [`MAX_WORLD_BORDER_DIAMETER`] are reduced to it.
+  /// Setting the diameter to what it already is succeeds.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.worldborder_center(0.0, 0.0)?;
+  /// client.worldborder_set(1000.0, Some(Duration::from_secs(600)))?; // shrink the world over ten minutes
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `diameter` is not positive and finite,
+  /// or `over` is too long for the command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way,
+  /// such as when the diameter is less than 1 block.
+  pub fn worldborder_set(&self, diameter: f64, over: Option<Duration>) -> Result<(), CommandError> {
+    if !diameter.is_finite() || diameter <= 0.0 {
+      Err(CommandError::InvalidArgument(format!("world border diameter must be positive and finite: {}", diameter)))?
+    }
+    self.worldborder_resize("set", diameter.min(MAX_WORLD_BORDER_DIAMETER), over)
+  }
+  
+  /// Grows the world border by `distance` blocks in diameter, or shrinks it if `distance` is negative, with `worldborder add`,
+  /// moving it gradually `over` the given time (in whole seconds, rounded down) if one is given.
+  /// 
+  /// Distances larger than [`MAX_WORLD_BORDER_DIAMETER`] in either direction are reduced to it.
+  /// Adding 0 succeeds.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `distance` is not finite,
+  /// or `over` is too long for the command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way,
+  /// such as when the resulting diameter would be too small or too large.
+  pub fn worldborder_add(&self, distance: f64, over: Option<Duration>) -> Result<(), CommandError> {
+    if !distance.is_finite() {
+      Err(CommandError::InvalidArgument(format!("world border distance must be finite: {}", distance)))?
+    }
+    self.worldborder_resize("add", distance.clamp(-MAX_WORLD_BORDER_DIAMETER, MAX_WORLD_BORDER_DIAMETER), over)
+  }
+  
+  fn worldborder_resize(&self, action: &str, distance: f64, over: Option<Duration>) -> Result<(), CommandError> {
+    let mut command = format!("worldborder {} {}", action, number(distance));
+    if let Some(over) = over {
+      let seconds = i32::try_from(over.as_secs()).map_err(|_| CommandError::InvalidArgument(format!("world border time is too long: {:?}", over)))?;
+      command += &format!(" {}", seconds);
+    }
+    self.expect_worldborder_changed(&command)
+  }
+  
+  /// Moves the center of the world border to the given x and z coordinates with `worldborder center`.
+  /// 
+  /// Moving the center to where it already is succeeds.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `x` or `z` is not finite
+  /// or is further than 29,999,984 blocks from the origin,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds in any other way.
+  pub fn worldborder_center(&self, x: f64, z: f64) -> Result<(), CommandError> {
+    if ![x, z].iter().all(|coordinate| coordinate.abs() <= MAX_WORLD_BORDER_CENTER) {
+      Err(CommandError::InvalidArgument(format!("world border center is out of bounds: {}, {}", x, z)))?
+    }
+    self.expect_worldborder_changed(&format!("worldborder center {} {}", number(x), number(z)))
+  }
+  
+  fn expect_worldborder_changed(&self, command: &str) -> Result<(), CommandError> {
+    let response = self.send_supported(command)?;
+    if self.response_matcher().is_worldborder_changed(&response) {
+      Ok(())
+    } else {
+      Err(unexpected(&response))
+    }
+  }
+  
+}
\ No newline at end of file
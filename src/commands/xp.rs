@@ -0,0 +1,127 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{CommandError, RconClient};
+
+use super::{args, parse::unexpected, Selector};
+
+/// The unit of experience used by [`RconClient::xp_query`], [`xp_add`](RconClient::xp_add), and [`xp_set`](RconClient::xp_set).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum XpUnit {
+  
+  /// Experience levels.
+  Levels,
+  /// Experience points towards the next level.
+  Points
+  
+}
+
+impl Display for XpUnit {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      XpUnit::Levels => write!(f, "levels"),
+      XpUnit::Points => write!(f, "points")
+    }
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Gets the experience of `player` in the given unit with `xp query`.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, commands::XpUnit};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let levels = client.xp_query("Steve", XpUnit::Levels)?;
+  /// println!("Steve has {} levels", levels);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// A vanilla server's responses to this and the other `xp` helpers are handled like this:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, RconClient, commands::XpUnit};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let server = ScriptedServer::new("password", |command| match command {
+  ///   "xp query Steve levels" => "Steve has 30 experience levels",
+  ///   "xp query Steve points" => "Steve has 12 experience points",
+  ///   "xp query Alex levels" => "No player was found",
+  ///   "xp add Steve -5 levels" => "Gave -5 experience levels to Steve",
+  ///   "xp add @a 100 points" => "Gave 100 experience points to 2 players",
+  ///   "xp set Steve 10 points" => "Set 10 experience points on Steve",
+  ///   "xp set Steve 1000 points" => "Cannot set experience points above the maximum points for the player's current level",
+  ///   _ => "Unknown or incomplete command, see below for error<--[HERE]"
+  /// }.to_owned())?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// assert_eq!(client.xp_query("Steve", XpUnit::Levels)?, 30);
+  /// assert_eq!(client.xp_query("Steve", XpUnit::Points)?, 12);
+  /// assert_eq!(client.xp_query("Alex", XpUnit::Levels), Err(CommandError::UnexpectedResponse("No player was found".into())));
+  /// client.xp_add("Steve", -5, XpUnit::Levels)?;
+  /// client.xp_add("@a", 100, XpUnit::Points)?;
+  /// client.xp_set("Steve", 10, XpUnit::Points)?;
+  /// assert!(matches!(client.xp_set("Steve", 1000, XpUnit::Points), Err(CommandError::UnexpectedResponse(_))));
+  /// assert!(matches!(client.xp_set("Steve", -1, XpUnit::Points), Err(CommandError::InvalidArgument(_))));
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// with [`CommandError::InvalidArgument`] (without sending anything) if `player` could not be safely inserted into a command,
+  /// or with [`CommandError::UnexpectedResponse`] if the server responds with anything other than an amount of experience,
+  /// such as when the player is not online.
+  pub fn xp_query(&self, player: impl Into<Selector>, unit: XpUnit) -> Result<i64, CommandError> {
+    let command = format!("xp query {} {}", args::selector(player)?, unit);
+    let response = self.send_supported(&command)?;
+    // "Steve has 5 experience levels", or "Steve has 12 experience points"
+    let (_, amount) = response.split_once(" has ").ok_or_else(|| unexpected(&response))?;
+    amount.split_whitespace().next().and_then(|amount| amount.parse().ok()).ok_or_else(|| unexpected(&response))
+  }
+  
+  /// Gives `amount` (which may be negative) experience in the given unit to `target` with `xp add`.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`xp_query`](RconClient::xp_query).
+  pub fn xp_add(&self, target: impl Into<Selector>, amount: i32, unit: XpUnit) -> Result<(), CommandError> {
+    // "Gave 5 experience levels to Steve", or "Gave 5 experience points to 2 players"
+    self.expect_prefix(&xp_command("add", target, amount, unit)?, "Gave ")
+  }
+  
+  /// Sets the experience of `target` in the given unit to `amount` with `xp set`.
+  /// 
+  /// Experience points can only be set up to the number needed to reach the next level.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`xp_query`](RconClient::xp_query),
+  /// and with [`CommandError::InvalidArgument`] (without sending anything) if `amount` is negative, which the server does not accept.
+  pub fn xp_set(&self, target: impl Into<Selector>, amount: i32, unit: XpUnit) -> Result<(), CommandError> {
+    if amount < 0 {
+      Err(CommandError::InvalidArgument(format!("cannot set experience to a negative amount: {}", amount)))?
+    }
+    // "Set 5 experience levels on Steve", or "Set 5 experience points on 2 players"
+    self.expect_prefix(&xp_command("set", target, amount, unit)?, "Set ")
+  }
+  
+}
+
+/// Formats an `xp` subcommand that applies `amount` experience in `unit` to `target`.
+fn xp_command(action: &str, target: impl Into<Selector>, amount: i32, unit: XpUnit) -> Result<String, CommandError> {
+  Ok(format!("xp {} {} {} {}", action, args::selector(target)?, amount, unit))
+}
\ No newline at end of file
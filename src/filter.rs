@@ -0,0 +1,74 @@
+/// A list of commands that an [`RconClient`](crate::RconClient) is allowed, or not allowed, to send.
+/// See [`RconClient::set_command_filter`](crate::RconClient::set_command_filter).
+/// 
+/// Each entry is a command prefix of one or more whole words, like `tps` or `scoreboard players get`,
+/// which matches a command that starts with it, ignoring case.
+/// Commands are compared without a leading `/` and without a namespace on the command name, so `stop` matches `/minecraft:stop`.
+/// 
+/// A blacklist cannot anticipate every way to run a command (for example, `execute run stop` runs `stop`),
+/// so when safety matters, a whitelist of the commands that are needed is the better choice.
+/// 
+/// ```
+/// # use mc_rcon::CommandFilter;
+/// let filter = CommandFilter::whitelist(vec!["list".into(), "scoreboard players get".into()]);
+/// assert!(filter.allows("list"));
+/// assert!(filter.allows("scoreboard players get Steve deaths"));
+/// assert!(!filter.allows("scoreboard players set Steve deaths 0"));
+/// assert!(!filter.allows("listen")); // prefixes are whole words
+/// 
+/// let filter = CommandFilter::blacklist(vec!["stop".into(), "op".into()]);
+/// assert!(filter.allows("list"));
+/// assert!(!filter.allows("/minecraft:stop"));
+/// assert!(!filter.allows("OP Steve"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommandFilter {
+  
+  prefixes: Vec<String>,
+  whitelist: bool
+  
+}
+
+impl CommandFilter {
+  
+  /// Returns a filter that allows only commands that start with one of the given prefixes.
+  pub fn whitelist(prefixes: Vec<String>) -> CommandFilter {
+    CommandFilter { prefixes, whitelist: true }
+  }
+  
+  /// Returns a filter that allows every command except those that start with one of the given prefixes.
+  pub fn blacklist(prefixes: Vec<String>) -> CommandFilter {
+    CommandFilter { prefixes, whitelist: false }
+  }
+  
+  /// Returns the prefixes in this filter.
+  pub fn prefixes(&self) -> &[String] {
+    &self.prefixes
+  }
+  
+  /// Returns whether this is a whitelist, as opposed to a blacklist.
+  pub fn is_whitelist(&self) -> bool {
+    self.whitelist
+  }
+  
+  /// Returns whether this filter allows the given command to be sent.
+  pub fn allows(&self, command: &str) -> bool {
+    let words = normalize(command);
+    let matched = self.prefixes.iter().any(|prefix| {
+      let prefix = normalize(prefix);
+      prefix.len() <= words.len() && prefix.iter().zip(&words).all(|(a, b)| a.eq_ignore_ascii_case(b))
+    });
+    matched == self.whitelist
+  }
+  
+}
+
+/// Splits a command into words, without a leading `/` or a namespace on the command name.
+fn normalize(command: &str) -> Vec<&str> {
+  let command = command.trim_start();
+  let mut words: Vec<_> = command.strip_prefix('/').unwrap_or(command).split_whitespace().collect();
+  if let Some(name) = words.first_mut() {
+    *name = name.rsplit(':').next().unwrap_or(name);
+  }
+  words
+}
\ No newline at end of file
@@ -0,0 +1,84 @@
+//! Utilities for Minecraft's legacy `§` formatting codes.
+//! 
+//! Servers, and plugins in particular, often color their responses with [formatting codes](https://minecraft.wiki/w/Formatting_codes),
+//! each of which is a `§` followed by a single character.
+
+/// The character that starts a formatting code.
+pub const FORMATTING_CODE_PREFIX: char = '§';
+
+/// Returns the given text with all formatting codes removed.
+/// 
+/// A `§` at the very end of the text (which has no code after it) is removed as well.
+/// 
+/// Example:
+/// ```
+/// # use mc_rcon::formatting::strip_codes;
+/// assert_eq!(strip_codes("§6TPS from last 1m, 5m, 15m: §a20.0"), "TPS from last 1m, 5m, 15m: 20.0");
+/// ```
+pub fn strip_codes(text: &str) -> String {
+  let mut stripped = String::with_capacity(text.len());
+  let mut chars = text.chars();
+  while let Some(c) = chars.next() {
+    if c == FORMATTING_CODE_PREFIX {
+      chars.next();
+    } else {
+      stripped.push(c);
+    }
+  }
+  stripped
+}
+
+/// Returns the given text with its formatting codes converted to ANSI escape sequences, for display in a terminal.
+/// 
+/// Colors and the bold, italic, underlined, and strikethrough styles are converted,
+/// and, as in Minecraft, a color code also resets any styles.
+/// Obfuscated text (`§k`) is shown normally, and unknown codes are removed.
+/// If the text has any formatting codes, the result ends by resetting the terminal's formatting.
+/// 
+/// Example:
+/// ```
+/// # use mc_rcon::formatting::to_ansi;
+/// assert_eq!(to_ansi("§6Gold §lbold§r plain"), "\x1b[0;33mGold \x1b[1mbold\x1b[0m plain\x1b[0m");
+/// assert_eq!(to_ansi("no codes"), "no codes");
+/// ```
+pub fn to_ansi(text: &str) -> String {
+  let mut converted = String::with_capacity(text.len());
+  let mut formatted = false;
+  let mut chars = text.chars();
+  while let Some(c) = chars.next() {
+    if c != FORMATTING_CODE_PREFIX {
+      converted.push(c);
+      continue
+    }
+    formatted = true;
+    let sequence = match chars.next().map(|code| code.to_ascii_lowercase()) {
+      Some('0') => "\x1b[0;30m",
+      Some('1') => "\x1b[0;34m",
+      Some('2') => "\x1b[0;32m",
+      Some('3') => "\x1b[0;36m",
+      Some('4') => "\x1b[0;31m",
+      Some('5') => "\x1b[0;35m",
+      Some('6') => "\x1b[0;33m",
+      Some('7') => "\x1b[0;37m",
+      Some('8') => "\x1b[0;90m",
+      Some('9') => "\x1b[0;94m",
+      Some('a') => "\x1b[0;92m",
+      Some('b') => "\x1b[0;96m",
+      Some('c') => "\x1b[0;91m",
+      Some('d') => "\x1b[0;95m",
+      Some('e') => "\x1b[0;93m",
+      Some('f') => "\x1b[0;97m",
+      Some('l') => "\x1b[1m",
+      Some('m') => "\x1b[9m",
+      Some('n') => "\x1b[4m",
+      Some('o') => "\x1b[3m",
+      Some('r') => "\x1b[0m",
+      _ => ""
+    };
+    converted.push_str(sequence);
+  }
+  if formatted {
+    converted.push_str("\x1b[0m");
+  }
+  converted
+}
\ No newline at end of file
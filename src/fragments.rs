@@ -0,0 +1,294 @@
+use std::{fmt::{self, Debug, Formatter}, io::{self, BufRead, Read}, mem, net::TcpStream, str, sync::{atomic::Ordering::SeqCst, MutexGuard}, time::Instant};
+
+use crate::{command_io_error, decode_payload, sanitize, timed_out, CommandError, CommandPacket, PacketKind, RconClient, ReadTimeoutGuard, StatsCounters, COMMAND_TYPE};
+
+/// An iterator over the packets of a response, as returned by [`RconClient::send_command_stream`].
+/// 
+/// Each item is the text of one packet, except that a character split between two packets is yielded with the second.
+/// The iterator ends once the whole response has been received, or after yielding an error.
+/// 
+/// While this exists, the client's connection is locked, so other threads' commands wait until it is dropped.
+/// Dropping it before the end of the response reads and discards the rest, so that later commands are not confused by it.
+pub struct ResponseFragments<'a> {
+  
+  client: &'a RconClient,
+  stream: MutexGuard<'a, TcpStream>,
+  id: i32,
+  sentinel_id: Option<i32>,
+  deadline: Option<Instant>,
+  first: Option<Vec<u8>>,
+  partial: Vec<u8>,
+  packets: u32
+  
+}
+
+impl Debug for ResponseFragments<'_> {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ResponseFragments")
+      .field("client", &self.client)
+      .field("id", &self.id)
+      .field("sentinel_id", &self.sentinel_id)
+      .field("packets", &self.packets)
+      .finish_non_exhaustive()
+  }
+  
+}
+
+/// A reader over the text of a response as it arrives, as returned by [`RconClient::send_command_line_reader`].
+/// 
+/// Only the packet being read is held in memory, so huge [fragmented responses](https://wiki.vg/RCON#Fragmentation)
+/// can be read line by line with [`BufRead::lines`] without buffering them whole.
+/// Errors are returned as I/O errors, with [`CommandError`]s other than [`CommandError::IO`] wrapped as for [writing commands](RconClient#impl-Write-for-RconClient).
+/// 
+/// Like [`ResponseFragments`], this locks the client's connection while it exists, and reads and discards the rest of the response when dropped.
+#[derive(Debug)]
+pub struct ResponseReader<'a> {
+  
+  fragments: ResponseFragments<'a>,
+  current: io::Cursor<String>
+  
+}
+
+impl RconClient {
+  
+  /// Sends the given command to the server and returns an iterator over the packets of its response as they arrive.
+  /// 
+  /// This allows huge [fragmented responses](https://wiki.vg/RCON#Fragmentation) to be processed without buffering them whole,
+  /// or to be cut short by dropping the iterator.
+  /// The response ends in the same way as with [`send_command`](RconClient::send_command),
+  /// including the [sentinel command](RconClient::set_sentinel_command),
+  /// the [reassembly timeout](RconClient::set_reassembly_timeout), and the [fragment limit](RconClient::set_max_response_fragments).
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let mut total = 0;
+  /// for fragment in client.send_command_stream("help")? {
+  ///   let fragment = fragment?;
+  ///   total += fragment.len();
+  ///   print!("{}", fragment);
+  ///   if total > 1_000_000 {
+  ///     break // give up on responses that are too long
+  ///   }
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command).
+  /// Errors that occur before the first packet of the response arrives are returned by this method,
+  /// and any later ones by the iterator.
+  pub fn send_command_stream(&self, command: &str) -> Result<ResponseFragments<'_>, CommandError> {
+    if !self.is_logged_in() {
+      Err(CommandError::NotLoggedIn)?
+    }
+    let command = sanitize(command.as_bytes(), self.control_characters)?;
+    self.check_command_filter(&command)?;
+    if command.len() > self.max_outgoing_payload {
+      StatsCounters::increment(&self.stats.oversized_payloads);
+      Err(CommandError::CommandTooLong)?
+    }
+    
+    let stream = self.lock_stream();
+    if !self.connected.load(SeqCst) {
+      Err(io::Error::new(io::ErrorKind::NotConnected, "client has been disconnected"))?
+    }
+    let id = self.get_next_id();
+    self.write_packet(&stream, id, COMMAND_TYPE, &command)?;
+    StatsCounters::increment(&self.stats.commands_sent);
+    let mut payload = Vec::with_capacity(self.max_incoming_payload);
+    let Some(header) = self.read_first_response_within(&stream, &CommandPacket, id, &mut payload).map_err(timed_out)? else {
+      self.stats.record_success();
+      return Ok(ResponseFragments { client: self, stream, id, sentinel_id: None, deadline: None, first: Some(payload), partial: Vec::new(), packets: 0 })
+    };
+    if header.id == -1 {
+      StatsCounters::increment(&self.stats.auth_failures);
+      self.logged_in.store(false, SeqCst);
+      Err(CommandError::NotLoggedIn)?
+    } else if header.id != id {
+      Err(io::Error::new(io::ErrorKind::InvalidData, CommandPacket::INVLID_RESPONSE_ID_ERROR))?
+    }
+    
+    if payload.len() >= self.max_incoming_payload {
+      StatsCounters::increment(&self.stats.fragmented_responses);
+    }
+    let sentinel_id = if self.needs_fence(payload.len()) {
+      let sentinel_id = self.get_next_id();
+      self.write_fence(&stream, sentinel_id)?;
+      Some(sentinel_id)
+    } else {
+      self.stats.record_success();
+      None
+    };
+    let deadline = self.reassembly_timeout.map(|timeout| Instant::now() + timeout);
+    Ok(ResponseFragments { client: self, stream, id, sentinel_id, deadline, first: Some(payload), partial: Vec::new(), packets: 1 })
+  }
+  
+  /// Sends the given command to the server and returns a reader over the text of its response as it arrives.
+  /// 
+  /// This works like [`send_command_stream`](RconClient::send_command_stream),
+  /// but allows the response to be read with [`BufRead`], for example line by line, without holding more than one packet of it in memory.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, io::BufRead};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// for line in client.send_command_line_reader("help")?.lines() {
+  ///   let line = line?;
+  ///   if line.starts_with("/give") {
+  ///     println!("{}", line);
+  ///   }
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command_stream`](RconClient::send_command_stream).
+  pub fn send_command_line_reader(&self, command: &str) -> Result<ResponseReader<'_>, CommandError> {
+    Ok(ResponseReader { fragments: self.send_command_stream(command)?, current: io::Cursor::default() })
+  }
+  
+}
+
+impl ResponseFragments<'_> {
+  
+  /// Reads the next packet of the response, or returns `None` once the response to the sentinel arrives.
+  fn read_fragment(&mut self) -> Result<Option<Vec<u8>>, CommandError> {
+    let Some(sentinel_id) = self.sentinel_id else {
+      return Ok(None)
+    };
+    let guard = match self.deadline {
+      Some(deadline) => {
+        let guard = ReadTimeoutGuard::new(&self.stream)?;
+        guard.limit_until(deadline)?;
+        Some(guard)
+      }
+      None => None
+    };
+    let (header, payload) = self.client.read_response(&self.stream, &CommandPacket).map_err(timed_out)?;
+    drop(guard);
+    if header.id == sentinel_id {
+      self.sentinel_id = None;
+      self.client.stats.record_success();
+      Ok(None)
+    } else if header.id == self.id {
+      if self.packets >= self.client.max_response_fragments {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "response has too many fragments"))?
+      }
+      self.packets += 1;
+      Ok(Some(payload))
+    } else if header.id == -1 {
+      self.client.logged_in.store(false, SeqCst);
+      Err(io::Error::new(io::ErrorKind::InvalidData, "client became deauthenticated between packets"))?
+    } else {
+      Err(io::Error::new(io::ErrorKind::InvalidData, CommandPacket::INVLID_RESPONSE_ID_ERROR))?
+    }
+  }
+  
+  /// Takes the complete text received so far, with [`take_utf8`].
+  fn take_text(&mut self, last: bool) -> Result<String, CommandError> {
+    take_utf8(&mut self.partial, last).map_err(|e| {
+      // the iterator ends after an error, so discard the rest of the response now
+      self.partial.clear();
+      while let Ok(Some(_)) = self.read_fragment() {}
+      self.sentinel_id = None;
+      StatsCounters::increment(&self.client.stats.io_errors);
+      e.into()
+    })
+  }
+  
+}
+
+impl Iterator for ResponseFragments<'_> {
+  
+  type Item = Result<String, CommandError>;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    let payload = match self.first.take() {
+      Some(payload) => Some(payload),
+      None => match self.read_fragment() {
+        Ok(payload) => payload,
+        Err(e) => {
+          self.sentinel_id = None; // the connection is in an unknown state, so stop reading from it
+          if let CommandError::IO(_) = e {
+            StatsCounters::increment(&self.client.stats.io_errors);
+          }
+          return Some(Err(e))
+        }
+      }
+    };
+    match payload {
+      Some(payload) => {
+        self.partial.extend_from_slice(&payload);
+        Some(self.take_text(self.sentinel_id.is_none()))
+      }
+      None if !self.partial.is_empty() => Some(self.take_text(true)),
+      None => None
+    }
+  }
+  
+}
+
+impl Drop for ResponseFragments<'_> {
+  
+  fn drop(&mut self) {
+    // discard the rest of the response, so that the next command does not read it as its own
+    while let Ok(Some(_)) = self.read_fragment() {}
+  }
+  
+}
+
+impl Read for ResponseReader<'_> {
+  
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let read = self.fill_buf()?.read(buf)?;
+    self.consume(read);
+    Ok(read)
+  }
+  
+}
+
+impl BufRead for ResponseReader<'_> {
+  
+  fn fill_buf(&mut self) -> io::Result<&[u8]> {
+    while self.current.position() >= self.current.get_ref().len() as u64 {
+      match self.fragments.next() {
+        Some(fragment) => self.current = io::Cursor::new(fragment.map_err(command_io_error)?),
+        None => break
+      }
+    }
+    self.current.fill_buf()
+  }
+  
+  fn consume(&mut self, amount: usize) {
+    self.current.consume(amount);
+  }
+  
+}
+
+/// Takes the complete UTF-8 text from the start of `bytes`, leaving an incomplete character at the end for the next packet unless this is the `last`.
+/// 
+/// Fails with [`InvalidData`](io::ErrorKind::InvalidData) if the text is not valid UTF-8,
+/// or if this is the `last` packet and it ends part way through a character.
+pub(crate) fn take_utf8(bytes: &mut Vec<u8>, last: bool) -> io::Result<String> {
+  let complete = match str::from_utf8(bytes) {
+    Err(e) if !last && e.error_len().is_none() => e.valid_up_to(),
+    _ => bytes.len()
+  };
+  let rest = bytes.split_off(complete);
+  decode_payload(mem::replace(bytes, rest))
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use std::{io, net::{SocketAddr, TcpStream, ToSocketAddrs}, sync::mpsc::{self, RecvTimeoutError}, thread, time::Duration};
+
+use crate::{Dialect, RconClient};
+
+impl RconClient {
+  
+  /// Construct a `RconClient` and connect to a server at the given address,
+  /// racing connections to each address it resolves to in the style of [Happy Eyeballs](https://www.rfc-editor.org/rfc/rfc8305).
+  /// 
+  /// [`connect`](RconClient::connect) tries each address in turn, waiting for each attempt to fail before starting the next,
+  /// so a host name whose first address is unreachable (for example, an IPv6 address on a network without IPv6) may take a long time to connect.
+  /// This instead starts a connection to the first address, then another to the next address every `stagger`
+  /// (or as soon as an earlier attempt fails), alternating between IPv6 and IPv4 addresses,
+  /// and uses whichever connection succeeds first.
+  /// A `stagger` of 250 milliseconds is recommended by the RFC.
+  /// 
+  /// Attempts that are still in progress when one succeeds are abandoned, and closed if they later succeed;
+  /// each runs on its own thread, which ends when the attempt does.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect_happy_eyeballs("mc.example.com:25575", Duration::from_millis(250))?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`connect`](RconClient::connect), with the error of the last attempt to fail if every attempt fails,
+  /// or with [`InvalidInput`](io::ErrorKind::InvalidInput) if the address does not resolve to any addresses.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if a thread cannot be spawned.
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "connect", skip_all, fields(server_addr)))]
+  pub fn connect_happy_eyeballs<A: ToSocketAddrs>(server_addr: A, stagger: Duration) -> io::Result<RconClient> {
+    let mut addrs = interleave_families(server_addr.to_socket_addrs()?.collect()).into_iter();
+    let (results, attempts) = mpsc::channel();
+    let mut pending = 0;
+    let mut last_error = None;
+    loop {
+      let more = match addrs.next() {
+        Some(addr) => {
+          let results = results.clone();
+          thread::Builder::new().name("mc-rcon connect".to_owned()).spawn(move || {
+            // once a connection has won, nobody is listening, and this one is simply dropped
+            let _ = results.send(TcpStream::connect(addr));
+          }).expect("failed to spawn connection thread");
+          pending += 1;
+          addrs.len() > 0
+        }
+        None if pending == 0 => break,
+        None => false
+      };
+      let result = if more {
+        match attempts.recv_timeout(stagger) {
+          Ok(result) => result,
+          Err(RecvTimeoutError::Timeout) => continue,
+          Err(RecvTimeoutError::Disconnected) => unreachable!("a sender is kept")
+        }
+      } else {
+        attempts.recv().expect("a sender is kept")
+      };
+      pending -= 1;
+      match result {
+        Ok(stream) => return RconClient::from_stream(stream, Dialect::Minecraft),
+        Err(e) => last_error = Some(e)
+      }
+    }
+    Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "could not resolve to any addresses")))
+  }
+  
+}
+
+/// Reorders addresses to alternate between IPv6 and IPv4, starting with the family of the first,
+/// and otherwise keeping the resolver's order of preference.
+fn interleave_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+  let Some(ipv6) = addrs.first().map(SocketAddr::is_ipv6) else {
+    return addrs
+  };
+  let mut interleaved = Vec::with_capacity(addrs.len());
+  let (preferred, other): (Vec<SocketAddr>, Vec<SocketAddr>) = addrs.into_iter().partition(|addr| addr.is_ipv6() == ipv6);
+  let (mut preferred, mut other) = (preferred.into_iter(), other.into_iter());
+  loop {
+    match (preferred.next(), other.next()) {
+      (None, None) => break interleaved,
+      (a, b) => interleaved.extend(a.into_iter().chain(b))
+    }
+  }
+}
\ No newline at end of file
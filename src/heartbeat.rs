@@ -0,0 +1,87 @@
+use std::{sync::{mpsc::{self, RecvTimeoutError, Sender}, Arc}, thread::{self, JoinHandle}, time::Duration};
+
+use crate::{ConnectionState, RconClient};
+
+impl RconClient {
+  
+  /// Starts a background thread that sends `command` every `interval`, to keep an otherwise idle session alive.
+  /// 
+  /// Responses are ignored, and errors are ignored too (or logged as warnings with the `logging` feature),
+  /// so the heartbeat keeps going through transient failures.
+  /// It stops when the returned [`HeartbeatHandle`] is dropped, when the client is dropped, or when the client is [disconnected](RconClient::disconnect).
+  /// Heartbeat commands take turns with other commands like those sent from any other thread.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, sync::Arc, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = Arc::new(RconClient::connect("localhost:25575")?);
+  /// client.log_in("SuperSecurePassword")?;
+  /// let _heartbeat = client.start_heartbeat(Duration::from_secs(60), "list");
+  /// // ... hours later, the session is still alive
+  /// println!("{}", client.send_command("seed")?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `interval` is zero, or if the thread cannot be spawned.
+  pub fn start_heartbeat(self: &Arc<Self>, interval: Duration, command: &str) -> HeartbeatHandle {
+    assert!(!interval.is_zero(), "heartbeat interval must be non-zero");
+    let client = Arc::downgrade(self);
+    let command = command.to_owned();
+    let (stop, stopped) = mpsc::channel::<()>();
+    let thread = thread::Builder::new().name("mc-rcon heartbeat".to_owned()).spawn(move || {
+      // the handle never sends anything, so this only ends early when the handle is dropped
+      while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+        let Some(client) = client.upgrade() else {
+          break
+        };
+        if client.state() == ConnectionState::Disconnected {
+          break
+        }
+        #[cfg_attr(not(feature = "logging"), allow(unused_variables))]
+        if let Err(e) = client.send_command(&command) {
+          #[cfg(feature = "logging")]
+          log::warn!("heartbeat command failed: {}", e);
+        }
+      }
+    }).expect("failed to spawn heartbeat thread");
+    HeartbeatHandle { stop: Some(stop), thread: Some(thread) }
+  }
+  
+}
+
+/// A heartbeat started by [`RconClient::start_heartbeat`], which stops when this is dropped.
+/// 
+/// Dropping this waits for a heartbeat command that is in progress to finish.
+#[derive(Debug)]
+pub struct HeartbeatHandle {
+  
+  stop: Option<Sender<()>>,
+  thread: Option<JoinHandle<()>>
+  
+}
+
+impl HeartbeatHandle {
+  
+  /// Returns whether the heartbeat is still running, which it stops doing once its client has been dropped or disconnected.
+  pub fn is_running(&self) -> bool {
+    self.thread.as_ref().is_some_and(|thread| !thread.is_finished())
+  }
+  
+}
+
+impl Drop for HeartbeatHandle {
+  
+  fn drop(&mut self) {
+    drop(self.stop.take()); // wakes the thread up
+    if let Some(thread) = self.thread.take() {
+      let _ = thread.join();
+    }
+  }
+  
+}
\ No newline at end of file
@@ -26,46 +26,368 @@
 //! 
 //! Assuming that the server is configured accordingly, this program will print a response from the server like `Seed: [-1137927873379713691]`.
 //! 
-//! Note that, although RCON servers [can send multiple response packets](https://wiki.vg/RCON#Fragmentation), this crate currently does not handle that possibility.
-//! If you need that functionality, please open an issue.
+//! Responses too long for one packet are [split into several](https://wiki.vg/RCON#Fragmentation), which this crate reassembles.
+//! Since RCON does not mark the last packet of a response, whenever a response fills a whole packet,
+//! the client also sends a [sentinel command](RconClient::set_sentinel_command) (`seed` by default) as a fence,
+//! and treats the response to it as the end of the original response.
+//! Reassembly can be bounded with a [timeout](RconClient::set_reassembly_timeout) and a [fragment limit](RconClient::set_max_response_fragments),
+//! and [`RconClient::send_command_stream`] yields the packets of a response as they arrive instead of buffering them.
+//! 
+//! If the `tracing` feature is enabled, [`RconClient`] emits [`tracing`](https://docs.rs/tracing) spans named `connect` and `reconnect`
+//! (with the server's address), `log_in`, and `send` (with the packet type, the payload and response lengths, the request id, and the number of packets),
+//! along with debug-level events for every packet header it reads or writes.
+//! Passwords and command text are never recorded.
+//! ```
+//! # use std::{error::Error, io, net::TcpListener, sync::{Arc, Mutex}};
+//! # use mc_rcon::RconClient;
+//! # 
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(all(feature = "tracing", feature = "testing"))] {
+//! # use mc_rcon::testing::ScriptedServer;
+//! # use tracing_subscriber::{fmt::format::FmtSpan, util::SubscriberInitExt};
+//! # 
+//! # #[derive(Clone, Default)]
+//! # struct Output(Arc<Mutex<Vec<u8>>>);
+//! # 
+//! # impl io::Write for Output {
+//! #   fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+//! #     self.0.lock().unwrap().write(buf)
+//! #   }
+//! #   fn flush(&mut self) -> io::Result<()> {
+//! #     Ok(())
+//! #   }
+//! # }
+//! # 
+//! # let output = Output::default();
+//! # let writer = output.clone();
+//! # let subscriber = tracing_subscriber::fmt().with_writer(move || writer.clone()).with_ansi(false).with_max_level(tracing_subscriber::filter::LevelFilter::DEBUG).with_span_events(FmtSpan::CLOSE).finish();
+//! # let guard = subscriber.set_default();
+//! let server = ScriptedServer::new("password", |command| format!("you said {}", command))?;
+//! let client = RconClient::connect(server.local_addr())?;
+//! client.log_in("password")?;
+//! client.send_command("hello")?;
+//! # drop(client);
+//! # server.join()?;
+//! # let listener = TcpListener::bind("127.0.0.1:0")?;
+//! # let client = RconClient::connect(listener.local_addr()?)?;
+//! client.reconnect()?;
+//! # drop(guard);
+//! # let output = String::from_utf8(output.0.lock().unwrap().clone())?;
+//! 
+//! // with tracing_subscriber::fmt().with_span_events(FmtSpan::CLOSE):
+//! assert!(output.contains(" connect{server_addr=127.0.0.1:"));
+//! assert!(output.contains(" log_in:send{packet_type=3 payload_len=8 request_id=0 response_len=0 packets=1}: mc_rcon: close"));
+//! assert!(output.contains(" log_in: mc_rcon: close"));
+//! assert!(output.contains(" send{packet_type=2 payload_len=5 request_id=1 response_len=14 packets=1}: mc_rcon: close"));
+//! assert!(output.contains(" reconnect{server_addr=127.0.0.1:"));
+//! assert!(!output.contains("password") && !output.contains("hello"));
+//! # }
+//! #   Ok(())
+//! # }
+//! ```
+//! 
+//! If the `futures-io` feature is enabled, [`AsyncRconClient`] provides an asynchronous client
+//! over any connection implementing the [`futures-io`](https://docs.rs/futures-io) traits.
+//! The `async-smol` feature additionally provides [`SmolRconClient`], which connects with [`async-net`](https://docs.rs/async-net)
+//! and works with [`smol`](https://docs.rs/smol) and any other executor that can drive [`async-io`](https://docs.rs/async-io).
+//! The `tokio` feature provides [`TokioRconClient`] for [`tokio`](https://docs.rs/tokio),
+//! implements `tokio`'s I/O traits for `AsyncRconClient`, and provides [`RconCodec`] to frame a connection into packets.
+//! 
+//! If the `logging` feature is enabled, this crate uses [`log`](https://docs.rs/log) to warn about unusual server behavior,
+//! such as empty command responses (which Minecraft never sends, but other servers might).
+//! 
+//! If the `serde` feature is enabled, data types like [`CommandResponse`], [`RconStats`], and the results of the [typed commands](commands)
+//! implement [`serde`](https://docs.rs/serde)'s `Serialize` (and `Deserialize` where possible),
+//! errors can be converted to serializable records like [`CommandErrorRecord`],
+//! and a [`ConnectionConfig`] can be loaded from an application's configuration file.
+//! 
+// the items that features enable are linked to docs.rs when those features are disabled, so that the links still resolve
+#![cfg_attr(feature = "futures-io", doc = "[`AsyncRconClient`]: AsyncRconClient")]
+#![cfg_attr(not(feature = "futures-io"), doc = "[`AsyncRconClient`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.AsyncRconClient.html")]
+#![cfg_attr(feature = "async-smol", doc = "[`SmolRconClient`]: SmolRconClient")]
+#![cfg_attr(not(feature = "async-smol"), doc = "[`SmolRconClient`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.SmolRconClient.html")]
+#![cfg_attr(feature = "tokio", doc = "[`TokioRconClient`]: TokioRconClient")]
+#![cfg_attr(not(feature = "tokio"), doc = "[`TokioRconClient`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.TokioRconClient.html")]
+#![cfg_attr(feature = "tokio", doc = "[`RconCodec`]: RconCodec")]
+#![cfg_attr(not(feature = "tokio"), doc = "[`RconCodec`]: https://docs.rs/mc-rcon/latest/mc_rcon/struct.RconCodec.html")]
+#![cfg_attr(feature = "serde", doc = "[`CommandErrorRecord`]: CommandErrorRecord")]
+#![cfg_attr(not(feature = "serde"), doc = "[`CommandErrorRecord`]: https://docs.rs/mc-rcon/latest/mc_rcon/enum.CommandErrorRecord.html")]
+//! 
+//! # Upgrading from 0.1
+//! 
+//! Commands and passwords containing control characters, such as `\n`, `\r`, and `\t`, used to be sent as given,
+//! but are now rejected by default with [`CommandError::InvalidCommand`] or [`LogInError::InvalidPassword`],
+//! as are those containing NUL bytes, which corrupted the packet.
+//! Callers that relied on sending control characters should remove them from their commands,
+//! or call [`set_control_characters`](RconClient::set_control_characters) with [`ControlCharacters::Strip`] to have the client remove them;
+//! there is no setting that sends them unchanged.
+//! The asynchronous clients always reject them.
+//! 
+//! # Supported servers
+//! 
+//! This crate is tested against Minecraft: Java Edition's dedicated server, and against mock servers imitating it
+//! and Source engine servers (see [`Dialect`]).
+//! Server software for Java Edition, such as Paper and its forks, implements RCON in the same way,
+//! but some of the [command helpers](RconClient::tps) rely on commands that only such software has.
+//! 
+//! Minecraft: Bedrock Edition is not supported, because Bedrock Dedicated Server has no RCON server at all.
+//! Third-party Bedrock servers that offer RCON, such as PocketMine-MP and Nukkit, implement Valve's original protocol,
+//! so [`Dialect::Source`] is the best fit for them, but they are untested and their commands differ from Java Edition's.
 
-use std::{error::Error, fmt::{self, Debug, Display, Formatter}, io::{self, Read, Write}, mem::size_of, net::{TcpStream, ToSocketAddrs}, sync::atomic::{AtomicBool, AtomicI32, Ordering::SeqCst}};
+use std::{borrow::Cow, error::Error, fmt::{self, Debug, Display, Formatter}, hash::{Hash, Hasher}, io::{self, IoSlice, Read, Write}, marker::PhantomData, mem::{self, size_of}, net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs}, ops::Deref, panic::{self, AssertUnwindSafe}, sync::{atomic::{AtomicBool, AtomicI32, Ordering::SeqCst}, Mutex, MutexGuard, TryLockError}, time::{Duration, Instant}};
 
 use arrayvec::ArrayVec;
+use socket2::{Domain, Protocol, Socket, Type};
+use commands::{matchers, MinecraftVersion, ResponseMatcher};
+
+#[cfg(feature = "futures-io")]
+mod async_client;
+mod buffer;
+mod builder;
+mod circuit_breaker;
+mod filter;
+#[cfg(feature = "tokio")]
+mod codec;
+pub mod commands;
+pub mod formatting;
+mod fragments;
+mod happy_eyeballs;
+mod heartbeat;
+pub mod observers;
+mod packet;
+pub mod paper;
+mod password;
+mod pool;
+mod probe;
+mod recording;
+#[cfg(feature = "serde")]
+mod records;
+pub mod response;
+#[cfg(feature = "async-smol")]
+mod smol;
+pub mod snbt;
+mod split;
+mod stats;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tokio")]
+mod tokio_client;
+pub mod transcript;
+mod validate;
+
+#[cfg(feature = "futures-io")]
+pub use async_client::AsyncRconClient;
+pub use buffer::ResponseBuffer;
+pub use builder::{ConnectionConfig, RconClientBuilder};
+pub use circuit_breaker::{CircuitBreakerRconClient, CircuitState};
+pub use filter::CommandFilter;
+#[cfg(feature = "tokio")]
+pub use codec::RconCodec;
+pub use fragments::{ResponseFragments, ResponseReader};
+pub use heartbeat::HeartbeatHandle;
+pub use packet::{PacketDecodeError, RconPacket};
+pub use password::{LogInWith, Password};
+pub use pool::{batch_log_in, PoolConfig, PooledClient, RconPool};
+pub use probe::{probe, ProbeResult, PROBE_SAMPLE_LEN};
+pub use recording::{play_rcon_session, RecordingRconClient};
+#[cfg(feature = "serde")]
+pub use records::{CommandErrorRecord, IoErrorRecord, LogInErrorRecord, StopErrorRecord};
+#[cfg(feature = "async-smol")]
+pub use smol::SmolRconClient;
+pub use split::{SplitCommandError, SplitTemplate};
+pub use stats::RconStats;
+#[cfg(feature = "tokio")]
+pub use tokio_client::TokioRconClient;
+pub use validate::{validate_minecraft_command, CommandValidationError};
+use stats::StatsCounters;
+
+/// Emits a [`tracing`] event, or does nothing if the `tracing` feature is disabled.
+macro_rules! trace_event {
+  ($($arg:tt)*) => {
+    #[cfg(feature = "tracing")]
+    tracing::debug!($($arg)*);
+  };
+}
+
+/// Records a value on the current [`tracing`] span, or does nothing if the `tracing` feature is disabled.
+macro_rules! trace_record {
+  ($field:literal, $value:expr) => {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record($field, $value);
+  };
+}
 
 /// The default port used by Minecraft for RCON.
 /// 
 /// This crate does not use this value, it is simply here for convenience and completeness.
 pub const DEFAULT_RCON_PORT: u16 = 25575;
 
-/// The maximum number of payload bytes that an RCON server will accept.
+/// The type of the ids that a client gives its packets, as returned by [`RconClient::current_id`].
+/// 
+/// The RCON protocol's ids are 32-bit, but naming the type lets code like `let id: PacketId = client.current_id()`
+/// keep compiling if a later version of this crate supports wider ids.
+/// The types that mirror the bytes of a packet, [`RconPacket`] and [`RawPacketHeader`], keep their `i32` fields.
+pub type PacketId = i32;
+
+/// The maximum number of payload bytes that a vanilla RCON server will accept.
 /// 
-/// If users of this crate try to send passwords or commands longer than this,
+/// By default, if users of this crate try to send passwords or commands longer than this,
 /// they will get a [`LogInError::PasswordTooLong`] or a [`CommandError::CommandTooLong`],
 /// and nothing will be sent to the server.
+/// Servers that accept longer payloads can raise this limit with [`RconClient::set_max_outgoing_payload`].
 pub const MAX_OUTGOING_PAYLOAD_LEN: usize = 1446; // does not include nul terminator
 
+/// Returns the maximum number of bytes that can follow `prefix` in a command without exceeding [`MAX_OUTGOING_PAYLOAD_LEN`],
+/// or `None` if `prefix` is already too long to send.
+/// 
+/// This is useful for tools that build commands by appending to a fixed prefix:
+/// ```
+/// # use mc_rcon::max_command_len_with_prefix;
+/// let prefix = "execute as @a run ";
+/// let max_len = max_command_len_with_prefix(prefix).unwrap();
+/// assert_eq!(max_len, 1428);
+/// ```
+/// 
+/// Note that lengths are in bytes, not characters, so any command appended to `prefix` must be measured with [`str::len`].
+pub const fn max_command_len_with_prefix(prefix: &str) -> Option<usize> {
+  MAX_OUTGOING_PAYLOAD_LEN.checked_sub(prefix.len())
+}
+
 /// The maximum number of payload bytes that an RCON server will send in one packet.
 /// 
 /// Currently, users of this crate can expect command responses to have lengths less that or equal to this value,
 /// though that may change in the future given that servers may send multiple response packets.
 pub const MAX_INCOMING_PAYLOAD_LEN: usize = 4096; // does not include nul terminator
 
+/// The largest payload the RCON protocol allows in either direction, which is the most [`RconClient::set_max_outgoing_payload`] accepts.
+/// 
+/// This is the limit of the Source RCON protocol that Minecraft's is based on;
+/// Paper and several standalone RCON servers accept outgoing payloads up to this length.
+pub const PROTOCOL_MAX_PAYLOAD_LEN: usize = MAX_INCOMING_PAYLOAD_LEN;
+
+/// The minimum number of bytes in a valid RCON packet, including its length field.
+/// 
+/// This is 4 bytes of length, 4 bytes of id, 4 bytes of type, and the null terminator and padding that follow an empty payload.
+/// Note that the length field itself does not count its own 4 bytes,
+/// so the smallest value it can validly hold is `MIN_PACKET_LEN - 4`.
+pub const MIN_PACKET_LEN: usize = I32_LEN + HEADER_LEN;
+
+/// The largest value the length field of an RCON packet can hold.
+/// 
+/// The length field is a signed 32-bit integer, so no packet can be longer than this (plus the 4 bytes of the length field itself).
+/// This crate never sends packets anywhere near this long,
+/// and rejects incoming packets whose length field is negative with an [`InvalidData`](io::ErrorKind::InvalidData) error rather than panicking.
+pub const MAX_PACKET_LEN: i32 = i32::MAX;
+
+// every packet this crate sends must fit in the length field
+const _: () = assert!(HEADER_LEN + PROTOCOL_MAX_PAYLOAD_LEN <= MAX_PACKET_LEN as usize);
+
+/// The maximum number of packets a response may be split into by default. See [`RconClient::set_max_response_fragments`].
+pub const DEFAULT_MAX_RESPONSE_FRAGMENTS: u32 = 1024;
+
+/// The command used by default to detect the end of a fragmented response. See [`RconClient::set_sentinel_command`].
+pub const DEFAULT_SENTINEL_COMMAND: &str = "seed";
+
+/// How long a client refuses to log in again after the server rejects its password, by default. See [`RconClient::set_log_in_cooldown`].
+pub const DEFAULT_LOG_IN_COOLDOWN: Duration = Duration::from_secs(5);
+
+const I32_LEN: usize = size_of::<i32>();
+
 const HEADER_LEN: usize = 10;
 
+// the header counted by the length field is the id, the type, and the null terminator and padding after the payload
+const _: () = assert!(HEADER_LEN == I32_LEN + I32_LEN + 2, "HEADER_LEN does not match the packet layout");
+
+/// The buffer each outgoing packet is written into, sized for the length field, the header, and the longest payload any client may be configured to send.
+type OutBuf = ArrayVec<u8, {I32_LEN + HEADER_LEN + PROTOCOL_MAX_PAYLOAD_LEN}>;
+
+const fn capacity_of<const CAP: usize>(_: PhantomData<ArrayVec<u8, CAP>>) -> usize {
+  CAP
+}
+
+// the buffer must be able to hold every packet send accepts, or writing to it would panic
+const _: () = assert!(capacity_of(PhantomData::<OutBuf>) == I32_LEN + HEADER_LEN + PROTOCOL_MAX_PAYLOAD_LEN, "outgoing packet buffer does not fit the longest packet");
+
 const LOGIN_TYPE: i32 = 3;
 
 const COMMAND_TYPE: i32 = 2;
 
+const LOGIN_RESPONSE_TYPE: i32 = 2;
+
+const COMMAND_RESPONSE_TYPE: i32 = 0;
+
 /// A client that has connected to an RCON server.
 /// 
 /// See the [crate-level documentation](crate) for an example.
-#[derive(Debug)]
+/// 
+/// A client can be shared between threads:
+/// each exchange with the server (sending a packet and reading the whole response to it) holds an internal lock on the connection,
+/// so concurrent calls are sent one at a time and never interleave their packets.
+/// 
+/// `RconClient` also implements [`Write`], where each line written is sent as a command,
+/// and [`Read`], which reads the response to the last command sent that way.
+/// This is lossy, since responses to earlier lines are discarded, but allows using I/O utilities like [`io::copy`]:
+/// ```no_run
+/// # use std::{error::Error, fs::File, io::{self, Write}};
+/// # use mc_rcon::RconClient;
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut client = RconClient::connect("localhost:25575")?;
+/// client.log_in("SuperSecurePassword")?;
+/// io::copy(&mut File::open("commands.txt")?, &mut client)?;
+/// client.flush()?; // send the last line, even if it does not end with a line break
+/// #   Ok(())
+/// # }
+/// ```
 pub struct RconClient {
   
-  stream: TcpStream,
-  next_id: AtomicI32,
-  logged_in: AtomicBool
+  stream: Mutex<TcpStream>,
+  server_addr: Option<SocketAddr>,
+  dialect: Dialect,
+  last_id: AtomicI32,
+  last_fence_id: AtomicI32,
+  connected: AtomicBool,
+  logged_in: AtomicBool,
+  pending_log_in_id: AtomicI32,
+  sentinel_command: String,
+  ping_command: String,
+  reassembly_timeout: Option<Duration>,
+  read_timeout: Option<Duration>,
+  write_timeout: Option<Duration>,
+  response_grace_period: Option<Duration>,
+  unanswered_since: AtomicI32,
+  max_response_fragments: u32,
+  max_incoming_payload: usize,
+  max_outgoing_payload: usize,
+  control_characters: ControlCharacters,
+  terminator_mode: TerminatorMode,
+  log_in_cooldown: Duration,
+  last_bad_password: Mutex<Option<Instant>>,
+  stats: StatsCounters,
+  packet_observer: Option<PacketObserver>,
+  response_matcher: Box<dyn ResponseMatcher>,
+  command_filter: Option<CommandFilter>,
+  version_cache: Mutex<Option<Option<MinecraftVersion>>>,
+  line_buffer: Vec<u8>,
+  last_response: io::Cursor<Vec<u8>>
+  
+}
+
+impl Debug for RconClient {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    // the stream is only locked if it is free, since this may be called while it is in use (even by the same thread)
+    let addr = match self.stream.try_lock() {
+      Ok(stream) => stream.peer_addr().ok(),
+      Err(TryLockError::Poisoned(e)) => e.into_inner().peer_addr().ok(),
+      Err(TryLockError::WouldBlock) => None
+    };
+    f.debug_struct("RconClient")
+      .field("addr", &addr.map_or_else(|| "<unknown>".to_owned(), |addr| addr.to_string()))
+      .field("logged_in", &self.logged_in.load(SeqCst))
+      .field("next_id", &id_after(self.last_id.load(SeqCst)))
+      .finish()
+  }
   
 }
 
@@ -81,14 +403,200 @@ impl RconClient {
   /// Most notably, if the server is not running or RCON is not enabled,
   /// this method will error with [`ConnectionRefused`](std::io::ErrorKind::ConnectionRefused).
   pub fn connect<A: ToSocketAddrs>(server_addr: A) -> io::Result<RconClient> {
-    let stream = TcpStream::connect(server_addr)?;
+    RconClient::connect_with_dialect(server_addr, Dialect::Minecraft)
+  }
+  
+  /// Construct a `RconClient` and connect to a server at the given address, which speaks the given [`Dialect`] of RCON.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{Dialect, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect_with_dialect("localhost:27015", Dialect::Source)?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("{}", client.send_command("status")?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`connect`](RconClient::connect).
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "connect", skip_all, fields(server_addr)))]
+  pub fn connect_with_dialect<A: ToSocketAddrs>(server_addr: A, dialect: Dialect) -> io::Result<RconClient> {
+    RconClient::from_stream(TcpStream::connect(server_addr)?, dialect)
+  }
+  
+  /// Construct a `RconClient` and connect to a server at the given address from the given local address.
+  /// 
+  /// Normally, the operating system picks the address that the connection comes from;
+  /// this allows hosts with several network interfaces to choose one, for example to satisfy the server's firewall rules.
+  /// A port of 0 lets the operating system pick the local port.
+  /// If the server's address resolves to several addresses, only those in the same family (IPv4 or IPv6) as `local_addr` are tried.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect_from("192.168.1.10:0".parse()?, "10.0.0.5:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`connect`](RconClient::connect),
+  /// or with [`AddrNotAvailable`](std::io::ErrorKind::AddrNotAvailable) if `local_addr` is not an address of this host,
+  /// or with [`InvalidInput`](std::io::ErrorKind::InvalidInput) if the server's address has no addresses in the same family as `local_addr`.
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "connect", skip_all, fields(server_addr)))]
+  pub fn connect_from<A: ToSocketAddrs>(local_addr: SocketAddr, server_addr: A) -> io::Result<RconClient> {
+    let mut last_error = None;
+    for addr in server_addr.to_socket_addrs()?.filter(|addr| addr.is_ipv4() == local_addr.is_ipv4()) {
+      let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+      match socket.bind(&local_addr.into()).and_then(|()| socket.connect(&addr.into())) {
+        Ok(()) => return RconClient::from_stream(socket.into(), Dialect::Minecraft),
+        Err(e) => last_error = Some(e)
+      }
+    }
+    Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "server address has no addresses in the same family as the local address")))
+  }
+  
+  fn from_stream(stream: TcpStream, dialect: Dialect) -> io::Result<RconClient> {
     stream.set_nonblocking(false)?;
     stream.set_read_timeout(None)?;
-    Ok(RconClient { stream, next_id: AtomicI32::new(0), logged_in: AtomicBool::new(false) })
+    let max_outgoing_payload = match dialect {
+      Dialect::Minecraft => MAX_OUTGOING_PAYLOAD_LEN,
+      Dialect::Source => PROTOCOL_MAX_PAYLOAD_LEN
+    };
+    let server_addr = stream.peer_addr().ok();
+    #[cfg(feature = "tracing")]
+    if let Some(addr) = server_addr {
+      tracing::Span::current().record("server_addr", tracing::field::display(addr));
+    }
+    Ok(RconClient { stream: Mutex::new(stream), server_addr, dialect, last_id: AtomicI32::new(-1), last_fence_id: AtomicI32::new(-1), connected: AtomicBool::new(true), logged_in: AtomicBool::new(false), pending_log_in_id: AtomicI32::new(-1), sentinel_command: DEFAULT_SENTINEL_COMMAND.to_owned(), ping_command: String::new(), reassembly_timeout: None, read_timeout: None, write_timeout: None, response_grace_period: None, unanswered_since: AtomicI32::new(-1), max_response_fragments: DEFAULT_MAX_RESPONSE_FRAGMENTS, max_incoming_payload: MAX_INCOMING_PAYLOAD_LEN, max_outgoing_payload, control_characters: ControlCharacters::default(), terminator_mode: TerminatorMode::default(), log_in_cooldown: DEFAULT_LOG_IN_COOLDOWN, last_bad_password: Mutex::new(None), stats: StatsCounters::default(), packet_observer: None, response_matcher: Box::new(matchers::EN), command_filter: None, version_cache: Mutex::new(None), line_buffer: Vec::new(), last_response: io::Cursor::default() })
+  }
+  
+  /// Consumes this client and returns the underlying connection.
+  /// 
+  /// This allows the connection to be reused, for example to switch to another protocol after authenticating.
+  /// Note that the connection is in an unknown state relative to the RCON protocol:
+  /// in particular, if a previous operation failed part way through, part of a response may still be waiting to be read.
+  pub fn into_parts(self) -> TcpStream {
+    self.stream.into_inner().unwrap_or_else(|e| e.into_inner())
+  }
+  
+  /// Returns the id of the last packet this client sent, or -1 if it has not sent any (since negative ids are never used).
+  /// 
+  /// This allows the client's packets to be correlated with the server's logs.
+  /// Note that the end of a [fragmented response](RconClient::set_sentinel_command) is found by sending another packet,
+  /// so after a fragmented response this is the id of that packet, not of the command;
+  /// [`send_command_detailed`](RconClient::send_command_detailed) always gives the command's own id.
+  /// Also note that when the client is shared between threads, another thread may have sent a packet since this thread's last one.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let command = "seed";
+  /// client.send_command(command)?;
+  /// println!("sent command id={} cmd={}", client.current_id(), command);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn current_id(&self) -> PacketId {
+    self.last_id.load(SeqCst)
+  }
+  
+  /// Sets the id that this client will use for the next packet it sends.
+  /// 
+  /// The client numbers its packets from 0, counting up by one for each packet, including logins and the packets used to find the end of
+  /// [fragmented responses](RconClient::set_sentinel_command).
+  /// Negative ids are never used, since servers answer with -1 (and some with any negative id) to reject a login,
+  /// so after [`i32::MAX`] the count starts again from 0.
+  /// Setting the next id allows several clients' packets to be told apart when they are relayed through one connection,
+  /// but responses to earlier packets that the client is still waiting for must not be given the same id,
+  /// so it is best done before logging in.
+  /// After setting the next id to 0, [`current_id`](RconClient::current_id) returns -1 until another packet is sent.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.set_next_id(1_000_000);
+  /// client.log_in("SuperSecurePassword")?;
+  /// assert_eq!(client.current_id(), 1_000_000);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// The count wraps around to 0 after the largest id:
+  /// ```
+  /// # use std::{error::Error, io::{self, ErrorKind, Read, Write}, net::TcpListener, thread};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let listener = TcpListener::bind("127.0.0.1:0")?;
+  /// let addr = listener.local_addr()?;
+  /// // a server that accepts any login and answers every command with an empty response, collecting the ids it sees
+  /// let server = thread::spawn(move || -> io::Result<Vec<i32>> {
+  ///   let (mut stream, _) = listener.accept()?;
+  ///   let mut ids = Vec::new();
+  ///   loop {
+  ///     let mut length = [0; 4];
+  ///     match stream.read_exact(&mut length) {
+  ///       Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(ids),
+  ///       result => result?
+  ///     }
+  ///     let mut packet = vec![0; i32::from_le_bytes(length) as usize];
+  ///     stream.read_exact(&mut packet)?;
+  ///     let response_type = if packet[4] == 3 { 2 } else { 0 };
+  ///     stream.write_all(&[&[10, 0, 0, 0], &packet[..4], &[response_type, 0, 0, 0, 0, 0]].concat())?;
+  ///     ids.push(i32::from_le_bytes(packet[..4].try_into().unwrap()));
+  ///   }
+  /// });
+  /// let client = RconClient::connect(addr)?;
+  /// client.set_next_id(i32::MAX);
+  /// client.log_in("password")?;
+  /// for _ in 0..3 {
+  ///   client.send_command("seed")?;
+  /// }
+  /// assert_eq!(client.current_id(), 2);
+  /// drop(client);
+  /// assert_eq!(server.join().unwrap()?, [i32::MAX, 0, 1, 2]);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `id` is negative.
+  pub fn set_next_id(&self, id: PacketId) {
+    assert!(id >= 0, "id must not be negative");
+    self.last_id.store(id - 1, SeqCst);
+  }
+  
+  /// Returns the dialect of RCON this client speaks.
+  pub fn dialect(&self) -> Dialect {
+    self.dialect
   }
   
   /// Returns whether this client is logged in.
   /// 
+  /// The client is logged out if the server rejects a command, as Minecraft does when the session has ended,
+  /// or if the server closes the connection, after which the client can [reconnect](RconClient::reconnect) and log in again.
+  /// 
   /// Example:
   /// ```no_run
   /// # use std::error::Error;
@@ -106,159 +614,2001 @@ impl RconClient {
     self.logged_in.load(SeqCst)
   }
   
-  fn send_log_in(&self, password: &str) -> Result<(), LogInError> {
+  /// Returns whether this client is logged in, including whether that is unknown because a login attempt was interrupted.
+  /// See [`AuthState`] for details and an example.
+  pub fn auth_state(&self) -> AuthState {
     if self.is_logged_in() {
-      Err(LogInError::AlreadyLoggedIn)?
-    }
-    let SendResponse { good_auth, payload: _ } = self.send(LogInPacket, password)?;
-    if good_auth {
-      Ok(())
+      AuthState::LoggedIn
+    } else if self.pending_log_in_id.load(SeqCst) != -1 {
+      AuthState::Unknown
     } else {
-      Err(LogInError::BadPassword)
+      AuthState::LoggedOut
     }
   }
   
-  fn get_next_id(&self) -> i32 {
-    let mut id = self.next_id.fetch_add(1, SeqCst);
-    if id == -1 { // skip id -1 so that authentication failures can always be identified
-      id = self.next_id.fetch_add(1, SeqCst)
+  /// Returns the state of this client's connection and session as a single value.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{ConnectionState, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// assert_eq!(client.state(), ConnectionState::Connected);
+  /// client.log_in("SuperSecurePassword")?;
+  /// assert_eq!(client.state(), ConnectionState::LoggedIn);
+  /// client.disconnect()?;
+  /// assert_eq!(client.state(), ConnectionState::Disconnected);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn state(&self) -> ConnectionState {
+    if !self.connected.load(SeqCst) {
+      ConnectionState::Disconnected
+    } else if self.is_logged_in() {
+      ConnectionState::LoggedIn
+    } else {
+      ConnectionState::Connected
     }
-    id
   }
   
-  fn send<K: PacketKind>(&self, kind: K, payload: &str) -> Result<SendResponse, SendError> {
-    let _ = kind;
-    if payload.len() > MAX_OUTGOING_PAYLOAD_LEN {
-      Err(SendError::PayloadTooLong)?
+  /// Closes the connection to the server, after which every attempt to log in or send a command fails
+  /// with an I/O error of kind [`NotConnected`](io::ErrorKind::NotConnected).
+  /// 
+  /// If another thread is part way through an exchange with the server, this waits for it to finish first.
+  /// Calling this on a client that is already disconnected does nothing.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while shutting down the connection,
+  /// though the client is disconnected regardless.
+  pub fn disconnect(&self) -> io::Result<()> {
+    let stream = self.lock_stream();
+    self.logged_in.store(false, SeqCst);
+    self.pending_log_in_id.store(-1, SeqCst);
+    if !self.connected.swap(false, SeqCst) {
+      return Ok(())
     }
-    
-    const I32_LEN: usize = size_of::<i32>();
-    
-    let out_len = i32::try_from(HEADER_LEN + payload.len()).expect("payload is too long");
-    let out_id = self.get_next_id();
-    
-    let mut stream = &self.stream;
-    // Buffering this apparently helps prevent MC from reading a packet of length < 10 and consequently disconnecting
-    // I could use BufWriter, but in this case I know the exact max size, so this is probably cheaper (and I just like ArrayVec, and consequently take every opportunity to use it)
-    let mut out_buf: ArrayVec<u8, {I32_LEN + HEADER_LEN + MAX_OUTGOING_PAYLOAD_LEN}> = ArrayVec::new();
-    out_buf.write_all(&out_len.to_le_bytes())?;
-    out_buf.write_all(&out_id.to_le_bytes())?;
-    out_buf.write_all(&K::TYPE.to_le_bytes())?;
-    out_buf.write_all(payload.as_bytes())?;
-    out_buf.write_all(b"\0\0")?; // null terminator and padding
-    debug_assert_eq!(out_buf.len(), I32_LEN + HEADER_LEN + payload.len());
-    stream.write_all(&mut out_buf)?;
-    stream.flush()?;
-    
-    let mut in_len_bytes = [0; I32_LEN];
-    let mut in_id_bytes = [0; I32_LEN];
-    stream.read_exact(&mut in_len_bytes)?;
-    let in_len = i32::from_le_bytes(in_len_bytes);
-    stream.read_exact(&mut in_id_bytes)?;
-    let in_id = i32::from_le_bytes(in_id_bytes);
-    stream.read_exact(&mut [0; I32_LEN])?;
-    let payload_len = usize::try_from(in_len).expect("payload is too long") - HEADER_LEN;
-    let mut payload_buf = vec![0; payload_len];
-    stream.read_exact(&mut payload_buf)?;
-    stream.read_exact(&mut [0; 2])?; // expect null terminator and padding
-      
-    let good_auth = if in_id == -1 {
-      false
-    } else if in_id == out_id {
-      true
-    } else {
-      Err(io::Error::new(io::ErrorKind::InvalidData, K::INVLID_RESPONSE_ID_ERROR))?
-    };
-    
-    if K::ACCEPTS_LONG_RESPONSES && payload_len >= MAX_INCOMING_PAYLOAD_LEN {
-      const CAP_COMMAND: &'static str = "seed";
-      let cap_len = i32::try_from(HEADER_LEN + CAP_COMMAND.len()).expect("cap payload is somehow too long");
-      let cap_id = self.get_next_id();
-      let mut cap_buf: ArrayVec<u8, {I32_LEN + HEADER_LEN + CAP_COMMAND.len()}> = ArrayVec::new();
-      cap_buf.write_all(&cap_len.to_le_bytes())?;
-      cap_buf.write_all(&cap_id.to_le_bytes())?;
-      cap_buf.write_all(&K::TYPE.to_le_bytes())?;
-      cap_buf.write_all(CAP_COMMAND.as_bytes())?;
-      cap_buf.write_all(b"\0\0")?;
-      debug_assert_eq!(cap_buf.len(), I32_LEN + HEADER_LEN + CAP_COMMAND.len());
-      stream.write_all(&mut cap_buf)?;
-      stream.flush()?;
-      
-      loop {
-        stream.read_exact(&mut in_len_bytes)?;
-        let inner_in_len = i32::from_le_bytes(in_len_bytes);
-        stream.read_exact(&mut in_id_bytes)?;
-        let inner_in_id = i32::from_le_bytes(in_id_bytes);
-        stream.read_exact(&mut [0; I32_LEN])?;
-        let inner_payload_len = usize::try_from(inner_in_len).expect("payload is too long") - HEADER_LEN;
-        let mut inner_payload_buf = vec![0; inner_payload_len];
-        stream.read_exact(&mut inner_payload_buf)?;
-        stream.read_exact(&mut [0; 2])?;
-        
-        if inner_in_id == cap_id {
-          break
-        } else if inner_in_id == in_id {
-          payload_buf.append(&mut inner_payload_buf);
-        } else if inner_in_id == -1 {
-          Err(io::Error::new(io::ErrorKind::InvalidData, "client became deauthenticated between packets"))?
-        } else {
-          Err(io::Error::new(io::ErrorKind::InvalidData, K::INVLID_RESPONSE_ID_ERROR))?
-        }
-      }
+    match stream.shutdown(Shutdown::Both) {
+      Err(e) if e.kind() != io::ErrorKind::NotConnected => Err(e),
+      _ => Ok(())
     }
-    
-    let payload = String::from_utf8(payload_buf).expect("response payload is not ASCII");
-    Ok(SendResponse { good_auth, payload })
   }
   
-  /// Attempts to log into the server with the given password.
-  /// 
-  /// See the [crate-level documentation](crate) for an example.
-  /// 
-  /// # Errors
-  /// 
-  /// * If the password is longer than [`MAX_OUTGOING_PAYLOAD_LEN`], returns [`LogInError::PasswordTooLong`] and does not send anything to the server.
-  /// * If this client is already logged in, returns [`LogInError::AlreadyLoggedIn`] and does not send anything to the server.
-  /// * If the given password is successfully sent, and the server responds indicating failure, returns [`LogInError::BadPassword`].
-  /// * If any I/O errors occur, returns [`LogInError::IO`] with the error.
-  ///   This notably includes [`ConnectionAborted`](std::io::ErrorKind::ConnectionAborted) if the server has closed the connection.
-  pub fn log_in(&self, password: &str) -> Result<(), LogInError> {
-    self.send_log_in(password)?;
-    self.logged_in.store(true, SeqCst);
-    Ok(())
+  /// Replaces this client's connection with a new one to the same server, logging it out.
+  /// 
+  /// When the server closes the connection, for example because it restarted, the client is logged out and disconnected,
+  /// so commands fail with [`CommandError::NotLoggedIn`] and logins with an I/O error of kind [`NotConnected`](io::ErrorKind::NotConnected).
+  /// Reconnecting lets the client log in again while keeping its settings, [statistics](RconClient::stats), and [observer](RconClient::set_packet_observer).
+  /// This also works after [`disconnect`](RconClient::disconnect), or to abandon a connection that is still open.
+  /// The new connection is made from whichever local address the operating system picks,
+  /// even if this client was created with [`connect_from`](RconClient::connect_from).
+  /// If another thread is part way through an exchange with the server, this waits for it to finish first.
+  /// 
+  /// ```
+  /// # use std::{error::Error, io::{self, Read, Write}, net::{TcpListener, TcpStream}, thread};
+  /// # use mc_rcon::{ConnectionState, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// // answers a packet with an empty response of the given type, or "ok" to a command
+  /// fn answer(stream: &mut TcpStream, response_type: u8) -> io::Result<()> {
+  ///   let mut length = [0; 4];
+  ///   stream.read_exact(&mut length)?;
+  ///   let mut packet = vec![0; i32::from_le_bytes(length) as usize];
+  ///   stream.read_exact(&mut packet)?;
+  ///   let payload: &[u8] = if response_type == 0 { b"ok" } else { b"" };
+  ///   stream.write_all(&[&[10 + payload.len() as u8, 0, 0, 0], &packet[..4], &[response_type, 0, 0, 0], payload, &[0, 0]].concat())
+  /// }
+  /// 
+  /// let listener = TcpListener::bind("127.0.0.1:0")?;
+  /// let addr = listener.local_addr()?;
+  /// // a server that accepts a login and then restarts, closing the connection, before accepting another and answering a command
+  /// let server = thread::spawn(move || -> io::Result<()> {
+  ///   let (mut stream, _) = listener.accept()?;
+  ///   answer(&mut stream, 2)?;
+  ///   drop(stream);
+  ///   let (mut stream, _) = listener.accept()?;
+  ///   answer(&mut stream, 2)?;
+  ///   answer(&mut stream, 0)
+  /// });
+  /// let client = RconClient::connect(addr)?;
+  /// client.log_in("password")?;
+  /// assert!(client.send_command("seed").is_err()); // the server has restarted
+  /// assert_eq!(client.state(), ConnectionState::Disconnected);
+  /// client.reconnect()?;
+  /// client.log_in("password")?;
+  /// assert_eq!(client.send_command("seed")?, "ok");
+  /// server.join().unwrap()?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`connect`](RconClient::connect), in which case this client is left disconnected,
+  /// or with [`NotConnected`](io::ErrorKind::NotConnected) if the server's address could not be found when the client was created.
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "reconnect", skip_all, fields(server_addr)))]
+  pub fn reconnect(&self) -> io::Result<()> {
+    let mut stream = self.lock_stream();
+    self.logged_in.store(false, SeqCst);
+    self.pending_log_in_id.store(-1, SeqCst);
+    if self.connected.swap(false, SeqCst) {
+      let _ = stream.shutdown(Shutdown::Both);
+    }
+    let server_addr = self.server_addr.ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "server address is unknown"))?;
+    trace_record!("server_addr", tracing::field::display(server_addr));
+    let new_stream = TcpStream::connect(server_addr)?;
+    new_stream.set_read_timeout(self.read_timeout)?;
+    new_stream.set_write_timeout(self.write_timeout)?;
+    *stream = new_stream;
+    // nothing from the old connection can arrive on the new one
+    self.last_fence_id.store(-1, SeqCst);
+    self.unanswered_since.store(-1, SeqCst);
+    // the server may have been updated while it was down
+    *self.version_cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    self.connected.store(true, SeqCst);
+    Ok(())
+  }
+  
+  /// Returns a snapshot of this client's cumulative statistics.
+  /// 
+  /// The counters are updated as packets are sent and received, so they are accurate even when the client is used from several threads.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.send_command("seed")?;
+  /// let stats = client.stats();
+  /// println!("{} command(s), {} byte(s) read", stats.commands_sent, stats.bytes_read);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn stats(&self) -> RconStats {
+    self.stats.snapshot()
+  }
+  
+  /// Sets a function to be called with every packet this client writes or reads,
+  /// replacing any previously set observer.
+  /// 
+  /// The observer receives the direction of the packet, its header, and its payload (without the null terminator and padding).
+  /// It is called for every packet, including the extra packets this client sends and receives to handle
+  /// [fragmented responses](https://wiki.vg/RCON#Fragmentation),
+  /// which makes it useful for building protocol transcripts or debugging misbehaving servers.
+  /// Note that this includes the payload of login packets, i.e. the password.
+  /// 
+  /// If the observer panics, the panic is caught and discarded, and the client carries on as if the observer had returned normally.
+  /// See [`observers`] for ready-made observers.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, io};
+  /// # use mc_rcon::{RconClient, observers};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_packet_observer(observers::hex_dump(io::stderr()));
+  /// client.log_in("SuperSecurePassword")?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn set_packet_observer(&mut self, observer: PacketObserver) {
+    self.packet_observer = Some(observer);
+  }
+  
+  /// Removes the observer set by [`set_packet_observer`](RconClient::set_packet_observer), if any.
+  pub fn clear_packet_observer(&mut self) {
+    self.packet_observer = None;
+  }
+  
+  /// Returns the filter that limits which commands this client sends, if any.
+  /// See [`set_command_filter`](RconClient::set_command_filter) for details.
+  pub fn command_filter(&self) -> Option<&CommandFilter> {
+    self.command_filter.as_ref()
+  }
+  
+  /// Limits the commands this client sends to those that the given filter allows.
+  /// 
+  /// This lets a tool that only needs a few commands be given a client that cannot send any others, even by mistake:
+  /// every other command fails with [`CommandError::Forbidden`] without being sent,
+  /// including those sent by the [typed command wrappers](commands).
+  /// The filter sees each command as it will be sent, after [control characters](RconClient::set_control_characters) have been stripped.
+  /// The commands that the client sends by itself, like the [sentinel command](RconClient::set_sentinel_command)
+  /// and the [ping command](RconClient::set_ping_command) sent by [`ping`](RconClient::ping), are always allowed,
+  /// but sending the same command through any other method is filtered as usual.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, CommandFilter, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_command_filter(CommandFilter::whitelist(vec!["list".into(), "tps".into()]));
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("{}", client.send_command("list")?);
+  /// assert_eq!(client.send_command("stop"), Err(CommandError::Forbidden));
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// Neither stripped control characters nor the ping command get a command past the filter:
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{CommandError, CommandFilter, ControlCharacters, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let server = ScriptedServer::new("password", |command| format!("ran {}", command))?;
+  /// let mut client = RconClient::connect(server.local_addr())?;
+  /// client.set_control_characters(ControlCharacters::Strip);
+  /// client.set_command_filter(CommandFilter::blacklist(vec!["stop".into()]));
+  /// client.log_in("password")?;
+  /// assert_eq!(client.send_command("st\x07op"), Err(CommandError::Forbidden));
+  /// assert!(matches!(client.send_command_stream("st\x07op"), Err(CommandError::Forbidden)));
+  /// 
+  /// client.set_ping_command("list".into());
+  /// client.set_command_filter(CommandFilter::whitelist(vec!["tps".into()]));
+  /// client.ping()?;
+  /// assert_eq!(client.send_command("list"), Err(CommandError::Forbidden));
+  /// assert_eq!(client.send_command("tps")?, "ran tps");
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn set_command_filter(&mut self, filter: CommandFilter) {
+    self.command_filter = Some(filter);
+  }
+  
+  /// Removes the filter set by [`set_command_filter`](RconClient::set_command_filter), if any.
+  pub fn clear_command_filter(&mut self) {
+    self.command_filter = None;
+  }
+  
+  /// Returns the command this client sends to detect the end of a fragmented response.
+  /// 
+  /// See [`set_sentinel_command`](RconClient::set_sentinel_command) for details.
+  pub fn sentinel_command(&self) -> &str {
+    &self.sentinel_command
+  }
+  
+  /// Sets the command this client sends to detect the end of a fragmented response,
+  /// which is [`DEFAULT_SENTINEL_COMMAND`] by default.
+  /// 
+  /// RCON does not mark the last packet of a [fragmented response](https://wiki.vg/RCON#Fragmentation),
+  /// so when a response fills a whole packet, this client sends this command as well,
+  /// and treats the response to it as the end of the original response (the response to the sentinel itself is discarded).
+  /// The sentinel should therefore be a cheap command without side effects that the server accepts over RCON,
+  /// and whose response fits in a single packet.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_sentinel_command("version".into());
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `command` is longer than [`MAX_OUTGOING_PAYLOAD_LEN`].
+  pub fn set_sentinel_command(&mut self, command: String) {
+    assert!(command.len() <= MAX_OUTGOING_PAYLOAD_LEN, "sentinel command must be no longer than {} bytes", MAX_OUTGOING_PAYLOAD_LEN);
+    self.sentinel_command = command;
+  }
+  
+  /// Returns the command this client sends to measure latency. See [`ping`](RconClient::ping) for details.
+  pub fn ping_command(&self) -> &str {
+    &self.ping_command
+  }
+  
+  /// Sets the command this client sends to measure latency, which is the empty command by default.
+  /// 
+  /// Some servers dislike empty commands, in which case a cheap command without side effects, like `list`, can be used instead.
+  /// See [`ping`](RconClient::ping) for details.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `command` is longer than [`MAX_OUTGOING_PAYLOAD_LEN`].
+  pub fn set_ping_command(&mut self, command: String) {
+    assert!(command.len() <= MAX_OUTGOING_PAYLOAD_LEN, "ping command must be no longer than {} bytes", MAX_OUTGOING_PAYLOAD_LEN);
+    self.ping_command = command;
+  }
+  
+  /// Returns the maximum time this client waits for the rest of a fragmented response.
+  /// See [`set_reassembly_timeout`](RconClient::set_reassembly_timeout) for details.
+  pub fn reassembly_timeout(&self) -> Option<Duration> {
+    self.reassembly_timeout
+  }
+  
+  /// Sets the maximum time this client waits for the rest of a [fragmented response](https://wiki.vg/RCON#Fragmentation)
+  /// after receiving its first packet, or `None` (the default) to wait indefinitely.
+  /// 
+  /// If the deadline passes before the response is complete, the command fails with an I/O error of kind [`TimedOut`](io::ErrorKind::TimedOut).
+  /// In that case, the rest of the response may still arrive later, so the connection should not be used any further.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_reassembly_timeout(Some(Duration::from_secs(10)));
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn set_reassembly_timeout(&mut self, timeout: Option<Duration>) {
+    self.reassembly_timeout = timeout;
+  }
+  
+  /// Returns the maximum time this client waits for each packet from the server.
+  /// See [`set_read_timeout`](RconClient::set_read_timeout) for details.
+  pub fn read_timeout(&self) -> Option<Duration> {
+    self.read_timeout
+  }
+  
+  /// Sets the maximum time this client waits for each packet from the server, or `None` (the default) to wait indefinitely.
+  /// 
+  /// This bounds every read, including logins, commands, and each packet of a fragmented response,
+  /// so that a server that stops responding cannot block the client forever;
+  /// shorter limits, like the [reassembly timeout](RconClient::set_reassembly_timeout), still apply within it.
+  /// If it passes, the login or command fails with an I/O error of kind [`TimedOut`](io::ErrorKind::TimedOut).
+  /// In that case, the response may still arrive later, so the connection should not be used any further.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_read_timeout(Some(Duration::from_secs(30)))?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while setting the timeout on the connection, in which case the timeout is unchanged.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `timeout` is zero.
+  pub fn set_read_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+    assert!(timeout != Some(Duration::ZERO), "read timeout must be non-zero");
+    self.stream.get_mut().unwrap_or_else(|e| e.into_inner()).set_read_timeout(timeout)?;
+    self.read_timeout = timeout;
+    Ok(())
+  }
+  
+  /// Returns the maximum time this client waits to write a packet to the connection.
+  /// See [`set_write_timeout`](RconClient::set_write_timeout) for details.
+  pub fn write_timeout(&self) -> Option<Duration> {
+    self.write_timeout
+  }
+  
+  /// Sets the maximum time this client waits to write a packet to the connection, or `None` (the default) to wait indefinitely.
+  /// (A packet that only partly fits in the connection's buffers may take up to twice as long, as the timeout applies to each write.)
+  /// 
+  /// Writes normally complete immediately, but if the server stops reading from the connection
+  /// (for example, during a long garbage collection pause) and its buffers fill up, they block until it starts reading again.
+  /// With a timeout, the command instead fails with an I/O error of kind [`TimedOut`](io::ErrorKind::TimedOut).
+  /// In that case, part of the packet may have been sent, so the connection should not be used any further.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_write_timeout(Some(Duration::from_secs(10)))?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while setting the timeout on the connection, in which case the timeout is unchanged.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `timeout` is zero.
+  pub fn set_write_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+    assert!(timeout != Some(Duration::ZERO), "write timeout must be non-zero");
+    self.stream.get_mut().unwrap_or_else(|e| e.into_inner()).set_write_timeout(timeout)?;
+    self.write_timeout = timeout;
+    Ok(())
+  }
+  
+  /// Returns how long this client waits for a server to start responding to a command.
+  /// See [`set_response_grace_period`](RconClient::set_response_grace_period) for details.
+  pub fn response_grace_period(&self) -> Option<Duration> {
+    self.response_grace_period
+  }
+  
+  /// Sets how long this client waits for a server to start responding to a command, or `None` (the default) to wait indefinitely.
+  /// 
+  /// Some servers, such as Factorio in some configurations, do not respond at all to some commands,
+  /// which would otherwise leave the client waiting forever.
+  /// If the grace period passes without a response, the command succeeds with an empty response
+  /// (except for [`ping`](RconClient::ping), which fails with [`CommandError::TimedOut`] since there is no round-trip time to report),
+  /// and if the server responds later after all, the late response is discarded by the next command.
+  /// The grace period does not apply to logging in, or to the rest of a [fragmented response](RconClient::set_reassembly_timeout).
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:27015")?;
+  /// client.set_response_grace_period(Some(Duration::from_secs(2)));
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.send_command("/silent-command game.print('hello')")?; // Factorio sends no response to this
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `grace_period` is zero.
+  pub fn set_response_grace_period(&mut self, grace_period: Option<Duration>) {
+    assert!(grace_period != Some(Duration::ZERO), "response grace period must not be zero");
+    self.response_grace_period = grace_period;
+  }
+  
+  /// Returns the maximum number of packets this client accepts in one response.
+  /// See [`set_max_response_fragments`](RconClient::set_max_response_fragments) for details.
+  pub fn max_response_fragments(&self) -> u32 {
+    self.max_response_fragments
+  }
+  
+  /// Sets the maximum number of packets this client accepts in one [fragmented response](https://wiki.vg/RCON#Fragmentation),
+  /// which is [`DEFAULT_MAX_RESPONSE_FRAGMENTS`] by default.
+  /// 
+  /// If a server keeps sending packets past this limit, the command fails with an I/O error of kind [`InvalidData`](io::ErrorKind::InvalidData),
+  /// so that a misbehaving server cannot keep this client reading forever.
+  /// The connection should not be used any further after that, since the rest of the response may still arrive.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `max` is 0.
+  pub fn set_max_response_fragments(&mut self, max: u32) {
+    assert!(max > 0, "maximum response fragments must be at least 1");
+    self.max_response_fragments = max;
+  }
+  
+  /// Returns the number of payload bytes after which this client considers a response packet full,
+  /// which is [`MAX_INCOMING_PAYLOAD_LEN`] unless the client was built with [`with_buffer_sizes`](RconClient::with_buffer_sizes).
+  /// 
+  /// A full packet may be followed by more fragments of the same response, and each response is read into a buffer of at least this size.
+  pub fn max_incoming_payload(&self) -> usize {
+    self.max_incoming_payload
+  }
+  
+  /// Returns the longest password or command, in bytes, that this client will send.
+  /// See [`set_max_outgoing_payload`](RconClient::set_max_outgoing_payload) for details.
+  pub fn max_outgoing_payload(&self) -> usize {
+    self.max_outgoing_payload
+  }
+  
+  /// Sets the longest password or command, in bytes, that this client will send,
+  /// which is [`MAX_OUTGOING_PAYLOAD_LEN`] by default ([`PROTOCOL_MAX_PAYLOAD_LEN`] for the [Source dialect](Dialect::Source)).
+  /// 
+  /// Vanilla servers disconnect clients that send longer payloads than the default,
+  /// but Paper and several standalone RCON servers accept up to [`PROTOCOL_MAX_PAYLOAD_LEN`].
+  /// [`probe_max_payload`](RconClient::probe_max_payload) can discover a server's actual limit.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, PROTOCOL_MAX_PAYLOAD_LEN};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_max_outgoing_payload(PROTOCOL_MAX_PAYLOAD_LEN); // the server runs Paper
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `max` is greater than [`PROTOCOL_MAX_PAYLOAD_LEN`].
+  pub fn set_max_outgoing_payload(&mut self, max: usize) {
+    assert!(max <= PROTOCOL_MAX_PAYLOAD_LEN, "outgoing payload limit must be no greater than {} bytes", PROTOCOL_MAX_PAYLOAD_LEN);
+    self.max_outgoing_payload = max;
+  }
+  
+  /// Finds the longest command, in bytes, that the server at the given address accepts,
+  /// for use with [`set_max_outgoing_payload`](RconClient::set_max_outgoing_payload).
+  /// 
+  /// This binary-searches between [`MAX_OUTGOING_PAYLOAD_LEN`] and [`PROTOCOL_MAX_PAYLOAD_LEN`]
+  /// by sending the [default sentinel command](DEFAULT_SENTINEL_COMMAND) padded with spaces,
+  /// which has no side effects (though the server may reply that the padded command is invalid).
+  /// A command counts as too long if the server closes the connection or does not respond within `timeout`,
+  /// in which case this reconnects and logs in again before continuing,
+  /// so the server may log a few disconnections.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let max = RconClient::probe_max_payload("localhost:25575", "SuperSecurePassword", Duration::from_secs(5))?;
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_max_outgoing_payload(max);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`connect`](RconClient::connect) and [`log_in`](RconClient::log_in),
+  /// for the first connection or any of the reconnections.
+  pub fn probe_max_payload<A: ToSocketAddrs>(server_addr: A, password: impl LogInWith, timeout: Duration) -> Result<usize, LogInError> {
+    let server_addr: Vec<_> = server_addr.to_socket_addrs()?.collect();
+    let connect = || -> Result<RconClient, LogInError> {
+      let mut client = RconClient::connect(&server_addr[..])?;
+      client.set_max_outgoing_payload(PROTOCOL_MAX_PAYLOAD_LEN);
+      client.log_in_with_timeout(&password, timeout)?;
+      Ok(client)
+    };
+    let mut client = connect()?;
+    let (mut accepted, mut rejected) = (MAX_OUTGOING_PAYLOAD_LEN, PROTOCOL_MAX_PAYLOAD_LEN + 1);
+    while rejected - accepted > 1 {
+      let len = accepted + (rejected - accepted) / 2;
+      let command = format!("{:<len$}", DEFAULT_SENTINEL_COMMAND);
+      match client.send(CommandPacket, command.as_bytes(), None, Some(Instant::now() + timeout), Vec::new()) {
+        Ok(response) if response.good_auth => accepted = len,
+        _ => {
+          rejected = len;
+          client = connect()?;
+        }
+      }
+    }
+    Ok(accepted)
+  }
+  
+  /// Returns how this client treats control characters in outgoing commands and passwords.
+  /// See [`set_control_characters`](RconClient::set_control_characters) for details.
+  pub fn control_characters(&self) -> ControlCharacters {
+    self.control_characters
+  }
+  
+  /// Sets how this client treats control characters (other than NUL) in outgoing commands and passwords,
+  /// which is [`ControlCharacters::Reject`] by default.
+  /// 
+  /// Vanilla servers ignore line breaks and other control characters in commands,
+  /// but some plugin command frameworks interpret them in surprising ways, so by default they are not sent at all.
+  /// NUL bytes are always rejected, since they would corrupt the packet.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{ControlCharacters, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.set_control_characters(ControlCharacters::Strip);
+  /// client.log_in("SuperSecurePassword")?;
+  /// client.send_command("say hello\r\n")?; // sent as "say hello"
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn set_control_characters(&mut self, control_characters: ControlCharacters) {
+    self.control_characters = control_characters;
+  }
+  
+  /// Returns how this client treats the null terminator and padding at the end of each packet it receives.
+  /// See [`set_terminator_mode`](RconClient::set_terminator_mode) for details.
+  pub fn terminator_mode(&self) -> TerminatorMode {
+    self.terminator_mode
+  }
+  
+  /// Sets how this client treats the null terminator and padding at the end of each packet it receives,
+  /// which is [`TerminatorMode::Strict`] by default.
+  /// 
+  /// See [`TerminatorMode`] for details and an example.
+  pub fn set_terminator_mode(&mut self, mode: TerminatorMode) {
+    self.terminator_mode = mode;
+  }
+  
+  /// Returns how long this client refuses to log in again after the server rejects its password.
+  /// See [`set_log_in_cooldown`](RconClient::set_log_in_cooldown) for details.
+  pub fn log_in_cooldown(&self) -> Duration {
+    self.log_in_cooldown
+  }
+  
+  /// Sets how long this client refuses to log in again after the server rejects its password,
+  /// which is [`DEFAULT_LOG_IN_COOLDOWN`] by default.
+  /// 
+  /// Some servers, or firewalls in front of them, ban addresses that fail to authenticate too often,
+  /// so a loop that retries a wrong password as fast as it can may lock its own host out.
+  /// During the cooldown, [`log_in`](RconClient::log_in) fails with [`LogInError::Throttled`] without sending anything.
+  /// A cooldown of zero disables this protection.
+  /// The new cooldown also applies to a password that was already rejected.
+  /// 
+  /// Note that the cooldown belongs to the client, so a loop that connects a new client for each attempt should stop at the first
+  /// [`BadPassword`](LogInError::BadPassword) instead (see [`LogInError::is_recoverable`]).
+  /// 
+  /// Example:
+  /// ```
+  /// # use std::{error::Error, io::{self, Read, Write}, net::TcpListener, thread, time::Duration};
+  /// # use mc_rcon::{LogInError, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let listener = TcpListener::bind("127.0.0.1:0")?;
+  /// let addr = listener.local_addr()?;
+  /// // a server that rejects every password
+  /// let server = thread::spawn(move || -> io::Result<()> {
+  ///   let (mut stream, _) = listener.accept()?;
+  ///   let mut length = [0; 4];
+  ///   while stream.read_exact(&mut length).is_ok() {
+  ///     io::copy(&mut (&mut stream).take(i32::from_le_bytes(length) as u64), &mut io::sink())?;
+  ///     stream.write_all(&[10, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 2, 0, 0, 0, 0, 0])?;
+  ///   }
+  ///   Ok(())
+  /// });
+  /// let mut client = RconClient::connect(addr)?;
+  /// assert_eq!(client.log_in("wrong"), Err(LogInError::BadPassword));
+  /// assert!(matches!(client.log_in("wrong"), Err(LogInError::Throttled { .. }))); // nothing was sent
+  /// client.set_log_in_cooldown(Duration::ZERO);
+  /// assert_eq!(client.log_in("still wrong"), Err(LogInError::BadPassword));
+  /// drop(client);
+  /// server.join().unwrap()?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn set_log_in_cooldown(&mut self, cooldown: Duration) {
+    self.log_in_cooldown = cooldown;
+  }
+  
+  /// Returns the matcher used by the [typed command wrappers](commands) to interpret responses whose phrasing depends on the server's language.
+  /// 
+  /// This is [`matchers::EN`] unless changed with [`set_response_matcher`](RconClient::set_response_matcher).
+  pub fn response_matcher(&self) -> &dyn ResponseMatcher {
+    &*self.response_matcher
+  }
+  
+  /// Sets the matcher used by the [typed command wrappers](commands) to interpret responses whose phrasing depends on the server's language.
+  /// 
+  /// See [`matchers`] for how to write a matcher for servers that respond in languages other than English.
+  pub fn set_response_matcher<M: ResponseMatcher + 'static>(&mut self, matcher: M) {
+    self.response_matcher = Box::new(matcher);
+  }
+  
+  /// Starts recording every packet this client writes or reads to the given writer,
+  /// in the format described in [`transcript`].
+  /// 
+  /// The payloads of login packets are redacted, so the recording never contains the password.
+  /// Recording is implemented as a [packet observer](RconClient::set_packet_observer),
+  /// so this replaces any observer previously set, and [`clear_packet_observer`](RconClient::clear_packet_observer) stops the recording.
+  /// Errors writing to `writer` are ignored.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, fs::File};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let mut client = RconClient::connect("localhost:25575")?;
+  /// client.start_recording(File::create("session.rcon")?);
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("{}", client.send_command("seed")?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn start_recording<W: Write + Send + 'static>(&mut self, writer: W) {
+    self.set_packet_observer(observers::transcript(writer));
+  }
+  
+  fn send_log_in(&self, password: &str, deadline: Option<Instant>, reauth: bool) -> Result<(), LogInError> {
+    if !reauth && self.is_logged_in() {
+      Err(LogInError::AlreadyLoggedIn)?
+    }
+    let mut last_bad_password = self.last_bad_password.lock().unwrap_or_else(|e| e.into_inner());
+    if self.pending_log_in_id.load(SeqCst) != -1 {
+      if self.finish_pending_log_in(deadline)? {
+        return Ok(())
+      }
+      StatsCounters::increment(&self.stats.auth_failures);
+      *last_bad_password = Some(Instant::now());
+    }
+    if let Some(retry_after) = cooldown_remaining(*last_bad_password, self.log_in_cooldown) {
+      Err(LogInError::Throttled { retry_after })?
+    }
+    let last_id = self.current_id();
+    let SendResponse { good_auth, .. } = match self.send(LogInPacket, password.as_bytes(), None, deadline, Vec::new()) {
+      Ok(response) => response,
+      Err(e) => {
+        if matches!(e, SendError::IO(_)) && self.current_id() != last_id && self.connected.load(SeqCst) {
+          // the login may have reached the server, so its response may still arrive
+          self.pending_log_in_id.store(self.current_id(), SeqCst);
+        }
+        Err(e)?
+      }
+    };
+    if good_auth {
+      *last_bad_password = None;
+      Ok(())
+    } else {
+      StatsCounters::increment(&self.stats.auth_failures);
+      *last_bad_password = Some(Instant::now());
+      Err(LogInError::BadPassword)
+    }
+  }
+  
+  /// Reads the response to an earlier login whose response was not read, returning whether the server accepted it.
+  fn finish_pending_log_in(&self, deadline: Option<Instant>) -> Result<bool, LogInError> {
+    let stream = self.lock_stream();
+    if !self.connected.load(SeqCst) {
+      Err(io::Error::new(io::ErrorKind::NotConnected, "client has been disconnected"))?
+    }
+    let guard = match deadline {
+      Some(deadline) => {
+        let guard = ReadTimeoutGuard::new(&stream)?;
+        guard.limit_until(deadline)?;
+        Some(guard)
+      }
+      None => None
+    };
+    let id = self.pending_log_in_id.load(SeqCst);
+    let header = self.read_first_response(&stream, &LogInPacket, id, &mut Vec::new()).map_err(timed_out)?;
+    drop(guard);
+    self.pending_log_in_id.store(-1, SeqCst);
+    if header.id == id {
+      Ok(true)
+    } else if header.id == -1 {
+      Ok(false)
+    } else {
+      Err(io::Error::new(io::ErrorKind::InvalidData, LogInPacket::INVLID_RESPONSE_ID_ERROR))?
+    }
+  }
+  
+  fn get_next_id(&self) -> i32 {
+    // negative ids are skipped so that authentication failures can always be identified
+    let last_id = self.last_id.fetch_update(SeqCst, SeqCst, |id| Some(id_after(id))).expect("id update always succeeds");
+    id_after(last_id)
+  }
+  
+  /// Returns whether the response to a packet with the given id may still arrive after the current exchange.
+  fn is_id_in_flight(&self, id: i32) -> bool {
+    id == self.pending_log_in_id.load(SeqCst) || id == self.last_fence_id.load(SeqCst) || self.is_unanswered(id, id_after(self.current_id()))
+  }
+  
+  /// Locks the connection for the duration of an exchange with the server.
+  fn lock_stream(&self) -> MutexGuard<'_, TcpStream> {
+    // a panic part way through an exchange leaves the connection in an unknown state whether or not the lock is poisoned,
+    // which is the same as any other interrupted exchange, so poisoning is ignored
+    self.stream.lock().unwrap_or_else(|e| e.into_inner())
+  }
+  
+  /// Returns whether the connection looks usable without sending anything to the server:
+  /// it has not been closed by either side, and nothing is waiting to be read from an earlier exchange.
+  pub(crate) fn is_connection_idle(&self) -> bool {
+    let stream = self.lock_stream();
+    if !self.connected.load(SeqCst) || stream.set_nonblocking(true).is_err() {
+      return false
+    }
+    let peeked = stream.peek(&mut [0]);
+    let restored = stream.set_nonblocking(false).is_ok();
+    restored && matches!(peeked, Err(e) if e.kind() == io::ErrorKind::WouldBlock)
+  }
+  
+  fn write_packet(&self, mut stream: &TcpStream, id: i32, packet_type: i32, payload: &[u8]) -> io::Result<()> {
+    debug_assert!(payload.len() <= PROTOCOL_MAX_PAYLOAD_LEN, "payload length should have been checked by send");
+    let length = i32::try_from(HEADER_LEN + payload.len()).expect("payload is too long");
+    let header = RawPacketHeader { length, id, packet_type };
+    // write timeouts are reported like read timeouts
+    write_raw_packet_vectored(stream, &header, payload).map_err(|e| self.check_closed(timed_out(e)))?;
+    stream.flush().map_err(|e| self.check_closed(timed_out(e)))?;
+    StatsCounters::add(&self.stats.bytes_written, (I32_LEN + HEADER_LEN + payload.len()) as u64);
+    trace_event!(length, id, packet_type, "wrote packet header");
+    self.observe(Direction::Outgoing, &header, payload);
+    Ok(())
+  }
+  
+  fn read_response<K: PacketKind>(&self, stream: &TcpStream, kind: &K) -> io::Result<(RawPacketHeader, Vec<u8>)> {
+    let mut payload = Vec::new();
+    let header = self.read_response_into(stream, kind, &mut payload)?;
+    Ok((header, payload))
+  }
+  
+  /// Reads a response packet, appending its payload to `buf`.
+  fn read_response_into<K: PacketKind>(&self, stream: &TcpStream, kind: &K, buf: &mut Vec<u8>) -> io::Result<RawPacketHeader> {
+    let header = self.read_packet_into(stream, buf)?;
+    if header.packet_type != kind.response_type() {
+      Err(io::Error::new(io::ErrorKind::InvalidData, K::INVALID_RESPONSE_TYPE_ERROR))?
+    }
+    Ok(header)
+  }
+  
+  /// Reads the first response packet to the request with the given id,
+  /// or returns `None` if the request is a command that the server does not answer within the [grace period](RconClient::set_response_grace_period).
+  fn read_first_response_within<K: PacketKind>(&self, stream: &TcpStream, kind: &K, id: i32, buf: &mut Vec<u8>) -> io::Result<Option<RawPacketHeader>> {
+    let grace = match self.response_grace_period {
+      Some(grace) if K::TYPE == COMMAND_TYPE => grace,
+      _ => return self.read_first_response(stream, kind, id, buf).map(Some)
+    };
+    let grace_deadline = Instant::now() + grace;
+    let guard = ReadTimeoutGuard::new(stream)?;
+    guard.limit_until(grace_deadline)?;
+    let start = buf.len();
+    match self.read_first_response(stream, kind, id, buf).map_err(timed_out) {
+      Ok(header) => {
+        self.unanswered_since.store(-1, SeqCst);
+        Ok(Some(header))
+      }
+      Err(e) if e.kind() == io::ErrorKind::TimedOut && Instant::now() >= grace_deadline => {
+        buf.truncate(start);
+        // if the server answers after all, read_first_response skips the answer when reading the next one
+        let _ = self.unanswered_since.compare_exchange(-1, id, SeqCst, SeqCst);
+        Ok(None)
+      }
+      Err(e) => Err(e)
+    }
+  }
+  
+  /// Reads the first response packet to the request with the given id, appending its payload to `buf`.
+  /// 
+  /// This first discards late answers to commands that the server did not answer within the [grace period](RconClient::set_response_grace_period).
+  /// With the [Source dialect](Dialect::Source), it also discards the end of the previous response's fence,
+  /// and the empty packet that Source servers send before a login response.
+  fn read_first_response<K: PacketKind>(&self, stream: &TcpStream, kind: &K, id: i32, buf: &mut Vec<u8>) -> io::Result<RawPacketHeader> {
+    loop {
+      let start = buf.len();
+      let header = self.read_packet_into(stream, buf)?;
+      let stale_fence = header.id != -1 && header.id == self.last_fence_id.load(SeqCst);
+      let login_prelude = self.dialect == Dialect::Source && K::TYPE == LOGIN_TYPE && header.packet_type == COMMAND_RESPONSE_TYPE && buf.len() == start;
+      if stale_fence || login_prelude || self.is_unanswered(header.id, id) {
+        buf.truncate(start);
+      } else if header.packet_type != kind.response_type() {
+        Err(io::Error::new(io::ErrorKind::InvalidData, K::INVALID_RESPONSE_TYPE_ERROR))?
+      } else {
+        return Ok(header)
+      }
+    }
+  }
+  
+  /// Returns whether `id` belongs to a command that was sent before the one with `current_id`,
+  /// but after (or as) the first command since the last answered one that the server did not answer in time.
+  fn is_unanswered(&self, id: i32, current_id: i32) -> bool {
+    let since = self.unanswered_since.load(SeqCst);
+    // ids are compared by their distance back from the current one, so that this still works after they wrap around
+    since != -1 && id != -1 && (1..=current_id.wrapping_sub(since)).contains(&current_id.wrapping_sub(id))
+  }
+  
+  /// Returns whether the end of a command's response has to be found with a fence, given the length of its first packet.
+  fn needs_fence(&self, first_payload_len: usize) -> bool {
+    match self.dialect {
+      Dialect::Minecraft => first_payload_len >= self.max_incoming_payload,
+      Dialect::Source => true
+    }
+  }
+  
+  /// Sends the packet whose response marks the end of the previous command's response.
+  fn write_fence(&self, stream: &TcpStream, id: i32) -> io::Result<()> {
+    match self.dialect {
+      Dialect::Minecraft => self.write_packet(stream, id, COMMAND_TYPE, self.sentinel_command.as_bytes()),
+      Dialect::Source => {
+        // Source servers mirror an empty response packet, and then send another packet with the same id
+        self.last_fence_id.store(id, SeqCst);
+        self.write_packet(stream, id, COMMAND_RESPONSE_TYPE, b"")
+      }
+    }
+  }
+  
+  /// Reads a packet, appending its payload to `buf`.
+  fn read_packet_into(&self, stream: &TcpStream, buf: &mut Vec<u8>) -> io::Result<RawPacketHeader> {
+    let start = buf.len();
+    let header = read_raw_packet_into(stream, buf, self.terminator_mode).map_err(|e| self.check_closed(e))?;
+    let payload = &buf[start..];
+    StatsCounters::add(&self.stats.bytes_read, (I32_LEN + HEADER_LEN + payload.len()) as u64);
+    trace_event!(length = header.length, id = header.id, packet_type = header.packet_type, "read packet header");
+    self.observe(Direction::Incoming, &header, payload);
+    Ok(header)
+  }
+  
+  /// Logs this client out and marks it disconnected if `e` shows that the server has closed the connection, since the session ends with it.
+  fn check_closed(&self, e: io::Error) -> io::Error {
+    if is_closed(&e) {
+      self.logged_in.store(false, SeqCst);
+      self.connected.store(false, SeqCst);
+    }
+    e
+  }
+  
+  /// Waits until `deadline` for another response packet to the command with the given id, returning its payload,
+  /// or `None` if the deadline passes first.
+  fn read_follow_up(&self, request_id: i32, deadline: Instant) -> Result<Option<String>, CommandError> {
+    let stream = self.lock_stream();
+    let guard = ReadTimeoutGuard::new(&stream)?;
+    match guard.limit_until(deadline).and_then(|()| self.read_response(&stream, &CommandPacket)).map_err(timed_out) {
+      Ok((header, payload)) if header.id == request_id => Ok(Some(decode_payload(payload)?)),
+      Ok(_) => Err(io::Error::new(io::ErrorKind::InvalidData, CommandPacket::INVLID_RESPONSE_ID_ERROR))?,
+      Err(e) if e.kind() == io::ErrorKind::TimedOut => Ok(None),
+      Err(e) => Err(e)?
+    }
+  }
+  
+  fn observe(&self, direction: Direction, header: &RawPacketHeader, payload: &[u8]) {
+    if let Some(observer) = &self.packet_observer {
+      // a panicking observer must not leave the client half way through a packet, so the panic is simply discarded
+      let _ = panic::catch_unwind(AssertUnwindSafe(|| observer(direction, header, payload)));
+    }
+  }
+  
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "send", skip_all, fields(packet_type = K::TYPE, payload_len = payload.len(), request_id, response_len, packets)))]
+  fn send<K: PacketKind>(&self, kind: K, payload: &[u8], id: Option<i32>, deadline: Option<Instant>, buf: Vec<u8>) -> Result<SendResponse, SendError> {
+    let result = self.send_unrecorded(kind, payload, id, deadline, buf);
+    match &result {
+      Ok(response) if response.packets > 1 => StatsCounters::increment(&self.stats.fragmented_responses),
+      Ok(_) => {}
+      Err(SendError::IO(_)) => StatsCounters::increment(&self.stats.io_errors),
+      Err(SendError::PayloadTooLong) => StatsCounters::increment(&self.stats.oversized_payloads),
+      Err(SendError::InvalidPayload | SendError::ConnectionPoisoned) => {}
+    }
+    result
+  }
+  
+  /// Sends a packet with the given id (or the next one) and reads the whole response to it into `payload_buf`, which must be empty.
+  /// If `deadline` is given, the first response packet must arrive before it.
+  fn send_unrecorded<K: PacketKind>(&self, kind: K, payload: &[u8], id: Option<i32>, deadline: Option<Instant>, mut payload_buf: Vec<u8>) -> Result<SendResponse, SendError> {
+    debug_assert!(payload_buf.is_empty());
+    payload_buf.reserve(self.max_incoming_payload);
+    let payload = &*sanitize(payload, self.control_characters)?;
+    if payload.len() > self.max_outgoing_payload {
+      Err(SendError::PayloadTooLong)?
+    }
+    
+    let stream = self.lock_stream();
+    if !self.connected.load(SeqCst) {
+      Err(io::Error::new(io::ErrorKind::NotConnected, "client has been disconnected"))?
+    }
+    let response_guard = match deadline {
+      Some(deadline) => {
+        let guard = ReadTimeoutGuard::new(&stream)?;
+        guard.limit_until(deadline)?;
+        Some(guard)
+      }
+      None => None
+    };
+    
+    let out_id = id.unwrap_or_else(|| self.get_next_id());
+    trace_record!("request_id", out_id);
+    
+    let sent_at = Instant::now();
+    self.write_packet(&stream, out_id, K::TYPE, payload)?;
+    if K::TYPE == COMMAND_TYPE {
+      StatsCounters::increment(&self.stats.commands_sent);
+    }
+    
+    let Some(in_header) = self.read_first_response_within(&stream, &kind, out_id, &mut payload_buf).map_err(timed_out)? else {
+      trace_record!("packets", 0);
+      return Ok(SendResponse { good_auth: true, payload: payload_buf, packets: 0, bytes: 0, request_id: out_id, sent_at, received_at: Instant::now() })
+    };
+    drop(response_guard);
+    let in_id = in_header.id;
+    let mut packets = 1;
+    let mut bytes = I32_LEN + HEADER_LEN + payload_buf.len();
+    
+    let good_auth = if in_id == -1 {
+      false
+    } else if in_id == out_id {
+      true
+    } else {
+      Err(io::Error::new(io::ErrorKind::InvalidData, K::INVLID_RESPONSE_ID_ERROR))?
+    };
+    
+    if K::ACCEPTS_LONG_RESPONSES && self.needs_fence(payload_buf.len()) {
+      let cap_id = self.get_next_id();
+      self.write_fence(&stream, cap_id)?;
+      
+      let deadline = match self.reassembly_timeout {
+        Some(timeout) => Some((Instant::now() + timeout, ReadTimeoutGuard::new(&stream)?)),
+        None => None
+      };
+      loop {
+        if let Some((deadline, guard)) = &deadline {
+          guard.limit_until(*deadline)?;
+        }
+        let fragment_start = payload_buf.len();
+        let inner_header = self.read_response_into(&stream, &kind, &mut payload_buf).map_err(timed_out)?;
+        let inner_in_id = inner_header.id;
+        
+        if inner_in_id == cap_id {
+          payload_buf.truncate(fragment_start); // the sentinel's response is not part of the command's
+          break
+        } else if inner_in_id == in_id {
+          if packets >= self.max_response_fragments {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "response has too many fragments"))?
+          }
+          packets += 1;
+          bytes += I32_LEN + HEADER_LEN + payload_buf.len() - fragment_start;
+        } else if inner_in_id == -1 {
+          self.logged_in.store(false, SeqCst);
+          Err(io::Error::new(io::ErrorKind::InvalidData, "client became deauthenticated between packets"))?
+        } else {
+          Err(io::Error::new(io::ErrorKind::InvalidData, K::INVLID_RESPONSE_ID_ERROR))?
+        }
+      }
+    }
+    
+    let received_at = Instant::now();
+    trace_record!("response_len", payload_buf.len());
+    trace_record!("packets", packets);
+    Ok(SendResponse { good_auth, payload: payload_buf, packets, bytes, request_id: out_id, sent_at, received_at })
+  }
+  
+  /// Attempts to log into the server with the given password.
+  /// 
+  /// The password may be a plain string, or a [`Password`] to keep it from being accidentally logged.
+  /// See the [crate-level documentation](crate) for an example.
+  /// 
+  /// # Errors
+  /// 
+  /// * If the password is longer than the [outgoing payload limit](RconClient::set_max_outgoing_payload),
+  ///   returns [`LogInError::PasswordTooLong`] and does not send anything to the server.
+  /// * If the password contains a NUL byte, or a control character that this client [rejects](RconClient::set_control_characters),
+  ///   returns [`LogInError::InvalidPassword`] and does not send anything to the server.
+  /// * If this client is already logged in, returns [`LogInError::AlreadyLoggedIn`] and does not send anything to the server.
+  ///   A client stops being logged in if the server ends its session (see [`send_command`](RconClient::send_command)),
+  ///   after which this method may be called again.
+  /// * If the given password is successfully sent, and the server responds indicating failure, returns [`LogInError::BadPassword`].
+  /// * If the server rejected a password within the [cooldown](RconClient::set_log_in_cooldown),
+  ///   returns [`LogInError::Throttled`] and does not send anything to the server.
+  /// * If any I/O errors occur, returns [`LogInError::IO`] with the error.
+  ///   This notably includes [`ConnectionAborted`](std::io::ErrorKind::ConnectionAborted) if the server has closed the connection.
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "log_in", skip_all))]
+  pub fn log_in(&self, password: impl LogInWith) -> Result<(), LogInError> {
+    self.send_log_in(password.password(), None, false)?;
+    self.logged_in.store(true, SeqCst);
+    Ok(())
+  }
+  
+  /// Logs into the server again with the given password, whether or not this client is already logged in.
+  /// 
+  /// This is for servers that give different passwords different permissions, to switch from one to another on the same connection.
+  /// Packet ids keep counting up rather than starting over, so that a late response to an earlier packet is never mistaken for a response to a later one.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:27015")?;
+  /// client.log_in("moderator_password")?;
+  /// println!("{}", client.send_command("status")?);
+  /// client.reauth("admin_password")?;
+  /// println!("{}", client.send_command("changelevel de_dust2")?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`log_in`](RconClient::log_in), except that it never returns [`LogInError::AlreadyLoggedIn`].
+  /// If the server rejects the password, this client is logged out, as Minecraft ends the session in that case.
+  #[cfg_attr(feature = "tracing", tracing::instrument(name = "log_in", skip_all))]
+  pub fn reauth(&self, password: impl LogInWith) -> Result<(), LogInError> {
+    match self.send_log_in(password.password(), None, true) {
+      Ok(()) => {
+        self.logged_in.store(true, SeqCst);
+        Ok(())
+      }
+      Err(LogInError::BadPassword) => {
+        self.logged_in.store(false, SeqCst);
+        Err(LogInError::BadPassword)
+      }
+      Err(e) => Err(e)
+    }
+  }
+  
+  /// Attempts to log into the server with the given password, failing if the server does not respond within `timeout`.
+  /// 
+  /// This is like [`log_in`](RconClient::log_in), except that the connection's read timeout is temporarily shortened to `timeout`
+  /// (it is restored afterwards), which is useful for quickly detecting unresponsive servers.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in_with_timeout("SuperSecurePassword", Duration::from_secs(2))?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`log_in`](RconClient::log_in).
+  /// If the server does not respond in time, returns [`LogInError::IO`] with an error of kind [`TimedOut`](io::ErrorKind::TimedOut);
+  /// in that case, the server's response may still arrive later, and the next attempt to log in reads it first (see [`AuthState::Unknown`]).
+  pub fn log_in_with_timeout(&self, password: impl LogInWith, timeout: Duration) -> Result<(), LogInError> {
+    self.send_log_in(password.password(), Some(Instant::now() + timeout), false)?;
+    self.logged_in.store(true, SeqCst);
+    Ok(())
+  }
+  
+  /// Sends the given command to the server and returns its response.
+  /// 
+  /// See the [crate-level documentation](crate) for an example.
+  /// 
+  /// Valid commands are dependent on the server;
+  /// documentation on the commands offered by vanilla Minecraft can be found at <https://minecraft.wiki/w/Commands>.
+  /// Servers with mods or plugins may have other commands available.
+  /// Commands should not start with `/`; see [`validate_minecraft_command`].
+  /// 
+  /// The return value of this method is the response message from the server.
+  /// This crate makes no attempt to interpret that response;
+  /// in particular, this method will never error to indicate that the command failed:
+  /// a successful return only means that the server recieved this command and responded to it.
+  /// 
+  /// Each call allocates a new `String` for the response;
+  /// programs that send many commands can reuse one buffer for all of them with [`send_command_into`](RconClient::send_command_into).
+  /// 
+  /// # Errors
+  /// 
+  /// * If the command is longer than the [outgoing payload limit](RconClient::set_max_outgoing_payload),
+  ///   returns [`CommandError::CommandTooLong`] and does not send anything to the server.
+  /// * If the command contains a NUL byte, or a control character that this client [rejects](RconClient::set_control_characters),
+  ///   returns [`CommandError::InvalidCommand`] and does not send anything to the server.
+  /// * If this client is not logged in, returns [`CommandError::NotLoggedIn`] and does not send anything to the server.
+  /// * If the server responds indicating that this client is not authenticated (for example, because its session expired),
+  ///   returns [`CommandError::NotLoggedIn`] and marks this client as logged out, so that [`log_in`](RconClient::log_in) can be called again.
+  /// * If any I/O errors occur, returns [`CommandError::IO`] with the error.
+  ///   This notably includes [`ConnectionAborted`](std::io::ErrorKind::ConnectionAborted) if the server has closed the connection,
+  ///   and [`InvalidData`](std::io::ErrorKind::InvalidData) if the response is not valid UTF-8.
+  pub fn send_command(&self, command: &str) -> Result<String, CommandError> {
+    Ok(self.send_command_detailed(command)?.body)
+  }
+  
+  /// Sends the given command to the server like [`send_command`](RconClient::send_command),
+  /// but writes its response into `buf` (replacing its contents) and returns it.
+  /// 
+  /// Reusing one buffer across many commands avoids allocating for each response, once it has grown large enough:
+  /// ```no_run
+  /// # use std::{error::Error, thread, time::Duration};
+  /// # use mc_rcon::{RconClient, ResponseBuffer};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let mut buf = ResponseBuffer::new();
+  /// loop {
+  ///   let players = client.send_command_into("list", &mut buf)?;
+  ///   println!("{}", players);
+  ///   thread::sleep(Duration::from_millis(500));
+  /// }
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command).
+  /// If an error occurs, `buf` is left empty.
+  pub fn send_command_into<'a>(&self, command: &str, buf: &'a mut ResponseBuffer) -> Result<&'a str, CommandError> {
+    let response = self.send_command_as(CommandPacket, command.as_bytes(), buf.take_bytes())?;
+    Ok(buf.set(decode_payload(response.payload)?))
+  }
+  
+  /// Measures the round-trip time to the server.
+  /// 
+  /// This sends the [ping command](RconClient::set_ping_command) (by default, the empty command),
+  /// ignores its response (whatever it is, including an empty one),
+  /// and returns the time between sending the command and receiving the response.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// println!("latency: {:?}", client.ping()?);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// Unlike other commands, a ping that the server does not answer within the [grace period](RconClient::set_response_grace_period) fails,
+  /// rather than reporting the grace period as the round-trip time:
+  /// ```
+  /// # use std::{error::Error, thread, time::Duration};
+  /// # use mc_rcon::{CommandError, RconClient};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let server = ScriptedServer::new("password", |command| match command {
+  ///   "list" => {
+  ///     thread::sleep(Duration::from_millis(300)); // too slow
+  ///     "There are 0 of a max of 20 players online: ".to_owned()
+  ///   }
+  ///   _ => "Seed: [1]".to_owned()
+  /// })?;
+  /// let mut client = RconClient::connect(server.local_addr())?;
+  /// client.set_ping_command("list".into());
+  /// client.set_response_grace_period(Some(Duration::from_millis(100)));
+  /// client.log_in("password")?;
+  /// assert_eq!(client.ping(), Err(CommandError::TimedOut));
+  /// thread::sleep(Duration::from_millis(500));
+  /// assert_eq!(client.send_command("seed")?, "Seed: [1]"); // the late response to the ping is discarded
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// or with [`CommandError::TimedOut`] if a [grace period](RconClient::set_response_grace_period) is set and the server does not respond within it.
+  pub fn ping(&self) -> Result<Duration, CommandError> {
+    if !self.is_logged_in() {
+      Err(CommandError::NotLoggedIn)?
+    }
+    let response = self.send_command_unfiltered(CommandPacket, self.ping_command.as_bytes(), None, Vec::new())?;
+    if response.packets == 0 {
+      // the empty response was made up when the grace period ran out, so there is no round trip to report
+      Err(CommandError::TimedOut)?
+    }
+    Ok(response.received_at - response.sent_at)
+  }
+  
+  /// Sends each of the given commands to the server in order and returns one result per command sent.
+  /// 
+  /// A command that fails on its own, such as one that is too long, does not prevent the following commands from being sent.
+  /// However, if a command fails with [`CommandError::NotLoggedIn`] or [`CommandError::IO`],
+  /// every following command would fail as well, so this method stops and returns the results so far, ending with that error.
+  /// As with [`send_command`](RconClient::send_command), error messages embedded in the server's responses are not considered failures.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// for result in client.send_commands_sequential(&["time set day", "weather clear", "difficulty peaceful"]) {
+  ///   println!("{}", result?);
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn send_commands_sequential(&self, commands: &[&str]) -> Vec<Result<String, CommandError>> {
+    let mut results = Vec::with_capacity(commands.len());
+    for command in commands {
+      let result = self.send_command(command);
+      let fatal = matches!(result, Err(CommandError::NotLoggedIn | CommandError::IO(_)));
+      results.push(result);
+      if fatal {
+        break
+      }
+    }
+    results
+  }
+  
+  /// Sends the given command to the server and returns its response along with some metadata about the exchange.
+  /// 
+  /// This behaves exactly like [`send_command`](RconClient::send_command),
+  /// except that the returned [`CommandResponse`] also records how many packets the response spanned,
+  /// how many bytes were read, and when the command was sent and its response received.
+  /// [`CommandResponse`] dereferences to [`str`], so it can be used in place of the response text.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let response = client.send_command_detailed("help")?;
+  /// println!("{} packet(s) in {:?}", response.packets, response.rtt);
+  /// println!("{}", &*response);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// A response too long for one packet reports every packet it was reassembled from:
+  /// ```
+  /// # use std::{error::Error, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # #[cfg(feature = "testing")] {
+  /// # use mc_rcon::testing::ScriptedServer;
+  /// let server = ScriptedServer::new("password", |command| match command {
+  ///   "help" => "x".repeat(10000),
+  ///   _ => String::new()
+  /// })?;
+  /// let client = RconClient::connect(server.local_addr())?;
+  /// client.log_in("password")?;
+  /// client.set_next_id(42);
+  /// let response = client.send_command_detailed("help")?;
+  /// assert_eq!(response.len(), 10000);
+  /// assert_eq!(response.packets, 3); // 4096 + 4096 + 1808 bytes of text
+  /// assert_eq!(response.bytes, 10000 + 3 * 14); // each packet adds its length, id, type, and terminator
+  /// assert_eq!(response.request_id, 42);
+  /// assert!(response.rtt > Duration::ZERO && response.rtt < Duration::from_secs(5));
+  /// assert_eq!(response.rtt, response.received_at - response.sent_at);
+  /// drop(client);
+  /// server.join()?;
+  /// # }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// See [`send_command`](RconClient::send_command).
+  pub fn send_command_detailed(&self, command: &str) -> Result<CommandResponse, CommandError> {
+    Ok(self.send_command_as(CommandPacket, command.as_bytes(), Vec::new())?.into_command_response()?)
+  }
+  
+  /// Sends the given command to the server as raw bytes, and returns the raw bytes of its response.
+  /// 
+  /// This behaves like [`send_command`](RconClient::send_command), but neither the command nor the response has to be valid UTF-8,
+  /// which some plugins need for arguments in legacy encodings.
+  /// No UTF-8 validation is done, but the other checks that `send_command` makes still apply:
+  /// the client must be logged in, the command must pass the [command filter](RconClient::set_command_filter)
+  /// and fit within the [outgoing payload limit](RconClient::set_max_outgoing_payload),
+  /// and it must not contain NUL bytes.
+  /// Other control characters are handled according to [`set_control_characters`](RconClient::set_control_characters),
+  /// so by default a command containing them is rejected, and with [`ControlCharacters::Strip`] they are removed before sending.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let response = client.send_command_bytes(b"msg J\xfcrgen hello")?; // "Jürgen" in Windows-1252
+  /// println!("{}", String::from_utf8_lossy(&response));
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// See [`send_command`](RconClient::send_command).
+  pub fn send_command_bytes(&self, command: &[u8]) -> Result<Vec<u8>, CommandError> {
+    Ok(self.send_command_as(CommandPacket, command, Vec::new())?.payload)
+  }
+  
+  /// Sends the given command to the server even if this client is not logged in, and returns its response.
+  /// 
+  /// This is meant for testing how servers handle unauthenticated commands, which a compliant server rejects
+  /// by responding with an id of -1 (reported as [`CommandError::NotLoggedIn`]).
+  /// Some servers close the connection instead.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, CommandError};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// assert_eq!(client.send_command_unauthenticated("seed"), Err(CommandError::NotLoggedIn));
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command),
+  /// except that [`CommandError::NotLoggedIn`] is only returned if the server rejects the command.
+  pub fn send_command_unauthenticated(&self, command: &str) -> Result<String, CommandError> {
+    Ok(self.send_command_unchecked(CommandPacket, command.as_bytes(), None, Vec::new())?.into_command_response()?.body)
+  }
+  
+  /// Sends the given command to the server with the given packet id, returning the id and the server's response.
+  /// 
+  /// Unlike [`set_next_id`](RconClient::set_next_id), this does not change the ids of later packets,
+  /// which allows a relay to send each of its clients' commands with the id that the client chose.
+  /// If the response is [fragmented](RconClient::set_sentinel_command), the packet that finds its end still takes the next id as usual.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let (id, response) = client.send_command_with_id(42, "seed")?;
+  /// println!("response to {}: {}", id, response);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command).
+  /// If `id` is negative, or is the id of an earlier packet whose response may still arrive
+  /// (such as a command that was not answered within the [grace period](RconClient::set_response_grace_period)),
+  /// returns [`CommandError::InvalidArgument`] without sending anything.
+  pub fn send_command_with_id(&self, id: PacketId, command: &str) -> Result<(PacketId, String), CommandError> {
+    if !self.is_logged_in() {
+      Err(CommandError::NotLoggedIn)?
+    }
+    if id < 0 {
+      Err(CommandError::InvalidArgument(format!("packet id {} is negative", id)))?
+    }
+    if self.is_id_in_flight(id) {
+      Err(CommandError::InvalidArgument(format!("packet id {} is already in use", id)))?
+    }
+    let response = self.send_command_unchecked(CommandPacket, command.as_bytes(), Some(id), Vec::new())?;
+    Ok((response.request_id, response.into_command_response()?.body))
+  }
+  
+  /// Sends the given command to the server like [`send_command`](RconClient::send_command),
+  /// but expects the response packets to have the given packet type instead of the usual `0`.
+  /// 
+  /// Minecraft always responds to commands with packets of type `0`, but some other implementations of RCON use different types;
+  /// this allows such servers to be used by specifying the type they respond with.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:27015")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let status = client.send_command_expecting_type("status", 2)?;
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command`](RconClient::send_command).
+  /// If a response packet has a type other than `expected_response_type`,
+  /// returns [`CommandError::IO`] with an error of kind [`InvalidData`](io::ErrorKind::InvalidData).
+  pub fn send_command_expecting_type(&self, command: &str, expected_response_type: i32) -> Result<String, CommandError> {
+    let kind = CustomCommandPacket { response_type: expected_response_type };
+    Ok(self.send_command_as(kind, command.as_bytes(), Vec::new())?.into_command_response()?.body)
+  }
+  
+  /// Sends a command, reading its response into `buf`, which must be empty.
+  fn send_command_as<K: PacketKind>(&self, kind: K, command: &[u8], buf: Vec<u8>) -> Result<SendResponse, CommandError> {
+    if !self.is_logged_in() {
+      Err(CommandError::NotLoggedIn)?
+    }
+    self.send_command_unchecked(kind, command, None, buf)
+  }
+  
+  /// Sends a command without checking whether this client is logged in.
+  fn send_command_unchecked<K: PacketKind>(&self, kind: K, command: &[u8], id: Option<i32>, buf: Vec<u8>) -> Result<SendResponse, CommandError> {
+    // the filter must see the command exactly as it will be sent, or stripping control characters could sneak a forbidden command past it
+    let command = sanitize(command, self.control_characters)?;
+    self.check_command_filter(&command)?;
+    self.send_command_unfiltered(kind, &command, id, buf)
+  }
+  
+  /// Sends a command without checking whether this client is logged in or whether the [command filter](RconClient::set_command_filter) allows it.
+  fn send_command_unfiltered<K: PacketKind>(&self, kind: K, command: &[u8], id: Option<i32>, buf: Vec<u8>) -> Result<SendResponse, CommandError> {
+    let response = self.send(kind, command, id, None, buf)?;
+    if response.good_auth {
+      #[cfg(feature = "logging")]
+      if response.packets == 0 {
+        log::warn!("server did not respond to the command with id {} within the grace period", response.request_id);
+      } else if response.payload.is_empty() {
+        log::warn!("server sent an empty response to the command with id {}", response.request_id);
+      }
+      self.stats.record_success();
+      Ok(response)
+    } else {
+      StatsCounters::increment(&self.stats.auth_failures);
+      self.logged_in.store(false, SeqCst); // the server has ended our session, so allow logging in again
+      Err(CommandError::NotLoggedIn)
+    }
+  }
+  
+  /// Fails with [`CommandError::Forbidden`] if the [command filter](RconClient::set_command_filter) does not allow the command.
+  fn check_command_filter(&self, command: &[u8]) -> Result<(), CommandError> {
+    match &self.command_filter {
+      Some(filter) if !filter.allows(&String::from_utf8_lossy(command)) => Err(CommandError::Forbidden),
+      _ => Ok(())
+    }
+  }
+  
+  /// Sends a line written with [`Write`] as a command, keeping its response for [`Read`].
+  fn send_line(&mut self, line: &[u8]) -> io::Result<()> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    if line.iter().all(u8::is_ascii_whitespace) {
+      return Ok(())
+    }
+    let command = std::str::from_utf8(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let response = self.send_command(command).map_err(command_io_error)?;
+    self.last_response = io::Cursor::new(response.into_bytes());
+    Ok(())
+  }
+  
+}
+
+impl Write for RconClient {
+  
+  /// Sends each complete line in `buf`, along with any partial line from previous writes, as a command.
+  /// Blank lines are skipped.
+  /// 
+  /// If sending a command fails, the error is returned and the rest of `buf` is discarded.
+  /// Errors other than I/O errors are returned as [`InvalidInput`](io::ErrorKind::InvalidInput) errors
+  /// (or [`PermissionDenied`](io::ErrorKind::PermissionDenied) if not logged in or the [command filter](RconClient::set_command_filter) forbids the command)
+  /// wrapping the [`CommandError`].
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.line_buffer.extend_from_slice(buf);
+    while let Some(end) = self.line_buffer.iter().position(|&b| b == b'\n') {
+      let line: Vec<u8> = self.line_buffer.drain(..=end).collect();
+      if let Err(e) = self.send_line(&line[..end]) {
+        self.line_buffer.clear();
+        Err(e)?
+      }
+    }
+    Ok(buf.len())
+  }
+  
+  /// Sends any partial line remaining from previous writes as a command.
+  fn flush(&mut self) -> io::Result<()> {
+    let line = mem::take(&mut self.line_buffer);
+    self.send_line(&line)
+  }
+  
+}
+
+impl Read for RconClient {
+  
+  /// Reads the response to the last command sent with [`Write`], returning 0 once all of it has been read.
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    self.last_response.read(buf)
+  }
+  
+}
+
+/// Returns whether an I/O error may go away if the operation is tried again.
+fn is_transient(e: &io::Error) -> bool {
+  matches!(e.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted)
+}
+
+/// Converts a response payload to text, failing with [`InvalidData`](io::ErrorKind::InvalidData) if the server sent invalid UTF-8.
+pub(crate) fn decode_payload(payload: Vec<u8>) -> io::Result<String> {
+  String::from_utf8(payload).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Returns whether `e` shows that the other end has closed the connection.
+fn is_closed(e: &io::Error) -> bool {
+  matches!(e.kind(), io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted | io::ErrorKind::BrokenPipe)
+}
+
+/// Returns the packet id that follows `id`, wrapping around from [`i32::MAX`] to 0 so that negative ids are never used.
+fn id_after(id: i32) -> i32 {
+  if id == i32::MAX { 0 } else { id + 1 }
+}
+
+/// Returns how much of the log-in cooldown is left after a password was rejected at `rejected_at`, if any.
+fn cooldown_remaining(rejected_at: Option<Instant>, cooldown: Duration) -> Option<Duration> {
+  let remaining = (rejected_at? + cooldown).saturating_duration_since(Instant::now());
+  (!remaining.is_zero()).then_some(remaining)
+}
+
+/// Converts an error from sending a command through [`Write`], or from reading a [`ResponseReader`], into an I/O error.
+fn command_io_error(e: CommandError) -> io::Error {
+  match e {
+    CommandError::IO(e) => e,
+    CommandError::NotLoggedIn | CommandError::Forbidden => io::Error::new(io::ErrorKind::PermissionDenied, e),
+    e => io::Error::new(io::ErrorKind::InvalidInput, e)
+  }
+}
+
+fn write_raw_packet<W: Write>(mut writer: W, header: &RawPacketHeader, payload: &[u8]) -> io::Result<()> {
+  writer.write_all(&header.length.to_le_bytes())?;
+  writer.write_all(&header.id.to_le_bytes())?;
+  writer.write_all(&header.packet_type.to_le_bytes())?;
+  writer.write_all(payload)?;
+  writer.write_all(b"\0\0") // null terminator and padding
+}
+
+/// Writes a packet with a single vectored write, so that the payload does not have to be copied into a buffer first.
+/// 
+/// Sending a packet in pieces apparently can make Minecraft read a packet of length < 10 and consequently disconnect,
+/// so the first write always offers the whole packet.
+/// If the writer only accepts part of it (as writers without vectored writes do, accepting only the header),
+/// the rest is copied into one buffer and written from there.
+fn write_raw_packet_vectored<W: Write>(mut writer: W, header: &RawPacketHeader, payload: &[u8]) -> io::Result<()> {
+  let header_bytes = header_bytes(header);
+  let packet_len = header_bytes.len() + payload.len() + 2;
+  let written = loop {
+    match writer.write_vectored(&[IoSlice::new(&header_bytes), IoSlice::new(payload), IoSlice::new(b"\0\0")]) {
+      Ok(0) => Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write packet"))?,
+      Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+      result => break result?
+    }
+  };
+  if written < packet_len {
+    // I know the exact max size of the packets this client sends, so an ArrayVec is cheaper than a Vec here
+    let mut out_buf = OutBuf::new();
+    if write_raw_packet(&mut out_buf, header, payload).is_ok() {
+      writer.write_all(&out_buf[written..])?;
+    } else {
+      // only packets built by hand can be too long for the buffer
+      let mut bytes = Vec::with_capacity(packet_len);
+      write_raw_packet(&mut bytes, header, payload)?;
+      writer.write_all(&bytes[written..])?;
+    }
+  }
+  Ok(())
+}
+
+/// Returns the length, id, and type fields of a packet as they appear on the wire.
+fn header_bytes(header: &RawPacketHeader) -> [u8; 3 * I32_LEN] {
+  let mut bytes = [0; 3 * I32_LEN];
+  for (field_bytes, field) in bytes.chunks_exact_mut(I32_LEN).zip([header.length, header.id, header.packet_type]) {
+    field_bytes.copy_from_slice(&field.to_le_bytes());
+  }
+  bytes
+}
+
+fn read_raw_packet<R: Read>(reader: R) -> io::Result<(RawPacketHeader, Vec<u8>)> {
+  let mut payload = Vec::new();
+  let header = read_raw_packet_into(reader, &mut payload, TerminatorMode::Strict)?;
+  Ok((header, payload))
+}
+
+/// Reads a packet, appending its payload to `buf`.
+/// 
+/// Everything after the length field is read at once, so that a packet usually takes only two reads.
+fn read_raw_packet_into<R: Read>(mut reader: R, buf: &mut Vec<u8>, mode: TerminatorMode) -> io::Result<RawPacketHeader> {
+  let mut length_bytes = [0; I32_LEN];
+  reader.read_exact(&mut length_bytes)?;
+  let length = i32::from_le_bytes(length_bytes);
+  let start = buf.len();
+  let body_len = incoming_body_len(length, mode)?;
+  let eager_len = body_len.min(MAX_EAGER_BODY_LEN);
+  buf.resize(start + eager_len, 0);
+  reader.read_exact(&mut buf[start..])?;
+  if body_len > eager_len {
+    reader.take((body_len - eager_len) as u64).read_to_end(buf)?;
+    if buf.len() - start < body_len {
+      Err(io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"))?
+    }
+  }
+  split_packet_body(length, buf, start, mode)
+}
+
+/// The longest packet body that is allocated before it arrives.
+/// 
+/// A server can claim any length up to [`MAX_PACKET_LEN`], so the rest of a longer body is only allocated as it actually arrives.
+const MAX_EAGER_BODY_LEN: usize = HEADER_LEN + MAX_INCOMING_PAYLOAD_LEN;
+
+/// Parses the id and type from the body of a packet (everything after its length field) in `buf[start..]`,
+/// leaving only its payload there.
+/// 
+/// The body is exactly as long as the length field says, so the terminator is checked (or trimmed) rather than assumed to be two bytes.
+fn split_packet_body(length: i32, buf: &mut Vec<u8>, start: usize, mode: TerminatorMode) -> io::Result<RawPacketHeader> {
+  let field = |index: usize| i32::from_le_bytes(buf[start + index * I32_LEN..][..I32_LEN].try_into().expect("field is 4 bytes"));
+  let (id, packet_type) = (field(0), field(1));
+  let payload_start = start + 2 * I32_LEN;
+  match mode {
+    TerminatorMode::Strict => {
+      if !buf[payload_start..].ends_with(&[0, 0]) {
+        Err(io::Error::new(io::ErrorKind::InvalidData, PacketDecodeError::InvalidNullTerminator))?
+      }
+      buf.truncate(buf.len() - 2);
+    }
+    TerminatorMode::Lenient => {
+      let payload_end = buf[payload_start..].iter().rposition(|&byte| byte != 0).map_or(payload_start, |last| payload_start + last + 1);
+      buf.truncate(payload_end);
+    }
+  }
+  buf.drain(start..payload_start);
+  Ok(RawPacketHeader { length, id, packet_type })
+}
+
+/// Temporarily changes the read timeout of a stream, restoring the original timeout when dropped.
+struct ReadTimeoutGuard<'a> {
+  
+  stream: &'a TcpStream,
+  previous: Option<Duration>
+  
+}
+
+impl<'a> ReadTimeoutGuard<'a> {
+  
+  fn new(stream: &'a TcpStream) -> io::Result<ReadTimeoutGuard<'a>> {
+    let previous = stream.read_timeout()?;
+    Ok(ReadTimeoutGuard { stream, previous })
+  }
+  
+  /// Limits the read timeout so that reads fail once `deadline` passes, failing immediately if it already has.
+  fn limit_until(&self, deadline: Instant) -> io::Result<()> {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+      Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))?
+    }
+    let timeout = self.previous.map_or(remaining, |previous| previous.min(remaining));
+    self.stream.set_read_timeout(Some(timeout))
+  }
+  
+}
+
+impl Drop for ReadTimeoutGuard<'_> {
+  
+  fn drop(&mut self) {
+    let _ = self.stream.set_read_timeout(self.previous);
   }
   
-  /// Sends the given command to the server and returns its response.
-  /// 
-  /// See the [crate-level documentation](crate) for an example.
-  /// 
-  /// Valid commands are dependent on the server;
-  /// documentation on the commands offered by vanilla Minecraft can be found at <https://minecraft.wiki/w/Commands>.
-  /// Servers with mods or plugins may have other commands available.
-  /// 
-  /// The return value of this method is the response message from the server.
-  /// This crate makes no attempt to interpret that response;
-  /// in particular, this method will never error to indicate that the command failed:
-  /// a successful return only means that the server recieved this command and responded to it.
+}
+
+/// Read and write timeouts are reported as [`WouldBlock`](io::ErrorKind::WouldBlock) on some platforms, so this normalizes them to [`TimedOut`](io::ErrorKind::TimedOut).
+fn timed_out(e: io::Error) -> io::Error {
+  if e.kind() == io::ErrorKind::WouldBlock {
+    io::Error::new(io::ErrorKind::TimedOut, e)
+  } else {
+    e
+  }
+}
+
+/// The state of an [`RconClient`]'s connection and session, as returned by [`RconClient::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+  
+  /// The client has been [disconnected](RconClient::disconnect).
+  Disconnected,
+  /// The client is connected, but not logged in.
+  Connected,
+  /// The client is connected and logged in.
+  LoggedIn
+  
+}
+
+/// Whether an [`RconClient`] is logged in, as returned by [`RconClient::auth_state`].
+/// 
+/// If an attempt to log in fails with an I/O error after the password was sent (for example, because the server did not respond in time),
+/// the server may still have accepted it, so the client cannot tell whether it is logged in.
+/// The next attempt to log in first reads the server's response to the earlier one:
+/// if the server accepted it, the client is logged in without sending the password again;
+/// if the server rejected it, the attempt continues as though the earlier one had failed with [`LogInError::BadPassword`]
+/// (so it fails with [`LogInError::Throttled`] unless the [cooldown](RconClient::set_log_in_cooldown) is zero).
+/// If reading the response fails again, the state stays unknown, and a new connection is likely needed.
+/// 
+/// ```
+/// # use std::{error::Error, io::{self, Read, Write}, net::TcpListener, thread, time::Duration};
+/// # use mc_rcon::{AuthState, LogInError, RconClient};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let listener = TcpListener::bind("127.0.0.1:0")?;
+/// let addr = listener.local_addr()?;
+/// // a server that accepts the password "pw" (as id 0) after a delay, then answers a command (as id 1)
+/// let server = thread::spawn(move || -> io::Result<()> {
+///   let (mut stream, _) = listener.accept()?;
+///   stream.read_exact(&mut [0; 16])?;
+///   thread::sleep(Duration::from_millis(300));
+///   stream.write_all(&[10, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0])?;
+///   let mut command = [0; 18]; // "seed", which would be a login packet if the password were sent again
+///   stream.read_exact(&mut command)?;
+///   assert_eq!(command[8], 2);
+///   stream.write_all(&[12, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, b'o', b'k', 0, 0])
+/// });
+/// let client = RconClient::connect(addr)?;
+/// assert_eq!(client.auth_state(), AuthState::LoggedOut);
+/// let timed_out = client.log_in_with_timeout("pw", Duration::from_millis(50));
+/// assert!(matches!(timed_out, Err(LogInError::IO(e)) if e.kind() == io::ErrorKind::TimedOut));
+/// assert_eq!(client.auth_state(), AuthState::Unknown);
+/// client.log_in("pw")?; // reads the late response instead of sending the password again
+/// assert_eq!(client.auth_state(), AuthState::LoggedIn);
+/// assert_eq!(client.send_command("seed")?, "ok");
+/// drop(client);
+/// server.join().unwrap()?;
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthState {
+  
+  /// The client is not logged in.
+  LoggedOut,
+  /// An attempt to log in was interrupted after the password was sent, so the server may or may not have accepted it.
+  Unknown,
+  /// The client is logged in.
+  LoggedIn
+  
+}
+
+/// A variant of the RCON protocol, which an [`RconClient`] is [constructed](RconClient::connect_with_dialect) to speak.
+/// 
+/// Servers for other games use the same packet format as Minecraft, which adopted it from Valve's Source engine,
+/// but differ in how they log in and end responses.
+/// The asynchronous client only speaks the Minecraft dialect.
+/// 
+/// There is no Bedrock dialect, because Bedrock Dedicated Server does not implement RCON;
+/// see the [crate documentation](crate#supported-servers).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Dialect {
+  
+  /// The dialect of Minecraft servers.
   /// 
-  /// # Errors
+  /// A response is fragmented only if a packet is full,
+  /// in which case the end of the response is found with the [sentinel command](RconClient::set_sentinel_command).
+  /// Outgoing payloads are limited to [`MAX_OUTGOING_PAYLOAD_LEN`] bytes by default.
+  #[default]
+  Minecraft,
+  /// The dialect of Source engine servers, such as Counter-Strike 2.
   /// 
-  /// * If the command is longer than [`MAX_OUTGOING_PAYLOAD_LEN`], returns [`CommandError::CommandTooLong`] and does not send anything to the server.
-  /// * If this client is not logged in, returns [`CommandError::NotLoggedIn`] and does not send anything to the server.
-  /// * If any I/O errors occur, returns [`CommandError::IO`] with the error.
-  ///   This notably includes [`ConnectionAborted`](std::io::ErrorKind::ConnectionAborted) if the server has closed the connection.
-  pub fn send_command(&self, command: &str) -> Result<String, CommandError> {
-    if !self.is_logged_in() {
-      Err(CommandError::NotLoggedIn)?
-    }
-    let SendResponse { good_auth, payload } = self.send(CommandPacket, command)?;
-    if good_auth {
-      Ok(payload)
-    } else {
-      Err(CommandError::NotLoggedIn)
-    }
+  /// The end of every response is found by sending an empty `SERVERDATA_RESPONSE_VALUE` packet,
+  /// which the server mirrors after the response (the sentinel command is not used),
+  /// and the empty packet that the server sends before a login response is skipped.
+  /// Outgoing payloads are limited to [`PROTOCOL_MAX_PAYLOAD_LEN`] bytes by default.
+  Source
+  
+}
+
+/// How a client treats control characters other than NUL in outgoing commands and passwords.
+/// See [`RconClient::set_control_characters`].
+/// 
+/// These are the C0 control characters, from `\x01` to `\x1f`, which include tabs and line breaks.
+/// With the default, [`Reject`](ControlCharacters::Reject), all of these are refused before anything is sent:
+/// ```
+/// # use std::{error::Error, net::TcpListener};
+/// # use mc_rcon::{LogInErrorKind, RconClient};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let listener = TcpListener::bind("127.0.0.1:0")?;
+/// let client = RconClient::connect(listener.local_addr()?)?;
+/// for password in ["pass\0word", "password\n", "password\r\n"] {
+///   assert_eq!(client.log_in(password).unwrap_err().kind(), LogInErrorKind::InvalidPassword);
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ControlCharacters {
+  
+  /// Fail with [`CommandError::InvalidCommand`] or [`LogInError::InvalidPassword`] without sending anything.
+  #[default]
+  Reject,
+  /// Remove them before sending.
+  Strip
+  
+}
+
+/// How a client treats the null terminator and padding at the end of each packet it receives.
+/// See [`RconClient::set_terminator_mode`].
+/// 
+/// The body of a packet is always read to exactly the length its length field declares,
+/// so a malformed terminator never causes the client to wait for bytes that are not coming or to read part of the next packet.
+/// With the default, [`Strict`](TerminatorMode::Strict), a packet that does not end with two null bytes is rejected,
+/// while with [`Lenient`](TerminatorMode::Lenient) it is accepted:
+/// ```
+/// # use std::{error::Error, io::{self, Read, Write}, net::TcpListener, thread};
+/// # use mc_rcon::{CommandError, RconClient, TerminatorMode};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// // a payload of "ok" followed by no null bytes, by only one, and by four (which strict mode reads as "ok\0\0")
+/// for (ending, strict_response) in [(&b""[..], None), (b"\0", None), (b"\0\0\0\0", Some("ok\0\0"))] {
+///   for mode in [TerminatorMode::Strict, TerminatorMode::Lenient] {
+///     let listener = TcpListener::bind("127.0.0.1:0")?;
+///     let addr = listener.local_addr()?;
+///     let server = thread::spawn(move || -> io::Result<()> {
+///       let (mut stream, _) = listener.accept()?;
+///       stream.read_exact(&mut [0; 16])?; // logging in with "pw", as id 0
+///       stream.write_all(&[10, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0])?;
+///       stream.read_exact(&mut [0; 16])?; // sending "hi", as id 1
+///       stream.write_all(&[10 + ending.len() as u8, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, b'o', b'k'])?;
+///       stream.write_all(ending)
+///     });
+///     let mut client = RconClient::connect(addr)?;
+///     client.set_terminator_mode(mode);
+///     client.log_in("pw")?;
+///     match (mode, client.send_command("hi")) {
+///       (TerminatorMode::Strict, Ok(response)) => assert_eq!(Some(&*response), strict_response),
+///       (TerminatorMode::Strict, Err(CommandError::IO(e))) if strict_response.is_none() => assert_eq!(e.kind(), io::ErrorKind::InvalidData),
+///       (TerminatorMode::Lenient, Ok(response)) => assert_eq!(response, "ok"),
+///       (_, result) => panic!("unexpected result {:?}", result)
+///     }
+///     server.join().unwrap()?;
+///   }
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TerminatorMode {
+  
+  /// Reject packets that do not end with two null bytes with an [`InvalidData`](io::ErrorKind::InvalidData) error,
+  /// as Minecraft always sends them.
+  /// Any further null bytes before those two are kept as part of the payload.
+  #[default]
+  Strict,
+  /// Accept packets whose payload is followed by any number of null bytes, including none,
+  /// as servers for some other games (notably Palworld, and some builds of ARK) send them.
+  /// All null bytes at the end of the declared length are removed from the payload,
+  /// including any that a server counts as part of it.
+  Lenient
+  
+}
+
+fn is_control(byte: &u8) -> bool {
+  *byte < 0x20
+}
+
+/// Rejects payloads containing NUL, and handles other control characters as given.
+fn sanitize(payload: &[u8], control_characters: ControlCharacters) -> Result<Cow<'_, [u8]>, SendError> {
+  if payload.contains(&0) {
+    Err(SendError::InvalidPayload)?
+  }
+  if !payload.iter().any(is_control) {
+    return Ok(Cow::Borrowed(payload))
   }
+  match control_characters {
+    ControlCharacters::Reject => Err(SendError::InvalidPayload),
+    ControlCharacters::Strip => Ok(Cow::Owned(payload.iter().copied().filter(|b| !is_control(b)).collect()))
+  }
+}
+
+/// A function that observes raw packets. See [`RconClient::set_packet_observer`].
+pub type PacketObserver = Box<dyn Fn(Direction, &RawPacketHeader, &[u8]) + Send + Sync>;
+
+/// The direction in which a packet travelled. See [`RconClient::set_packet_observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+  
+  /// The packet was sent from the client to the server.
+  Outgoing,
+  /// The packet was sent from the server to the client.
+  Incoming
+  
+}
+
+/// The header fields of a raw RCON packet. See [`RconClient::set_packet_observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawPacketHeader {
   
+  /// The length field, which counts every byte of the packet after itself.
+  pub length: i32,
+  /// The packet id.
+  pub id: i32,
+  /// The packet type.
+  pub packet_type: i32
+  
+}
+
+/// Validates the length field of an incoming packet, which must be between `MIN_PACKET_LEN - 4` and [`MAX_PACKET_LEN`]
+/// (or may be 2 less, leaving no room for the terminator, in [lenient mode](TerminatorMode::Lenient)),
+/// and returns the length of the body (everything after the length field) it implies.
+fn incoming_body_len(in_len: i32, mode: TerminatorMode) -> io::Result<usize> {
+  let min_len = match mode {
+    TerminatorMode::Strict => HEADER_LEN,
+    TerminatorMode::Lenient => 2 * I32_LEN
+  };
+  match usize::try_from(in_len) {
+    Ok(in_len) if in_len >= min_len => Ok(in_len),
+    Ok(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "response packet length is shorter than the minimum packet length")),
+    Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "response packet length is negative"))
+  }
 }
 
 trait PacketKind {
@@ -267,8 +2617,16 @@ trait PacketKind {
   
   const TYPE: i32;
   
+  const RESPONSE_TYPE: i32;
+  
   const INVLID_RESPONSE_ID_ERROR: &'static str;
   
+  const INVALID_RESPONSE_TYPE_ERROR: &'static str;
+  
+  fn response_type(&self) -> i32 {
+    Self::RESPONSE_TYPE
+  }
+  
 }
 
 struct LogInPacket;
@@ -279,8 +2637,12 @@ impl PacketKind for LogInPacket {
   
   const TYPE: i32 = LOGIN_TYPE;
   
+  const RESPONSE_TYPE: i32 = LOGIN_RESPONSE_TYPE;
+  
   const INVLID_RESPONSE_ID_ERROR: &'static str = "response packet id mismatched with login packet id";
   
+  const INVALID_RESPONSE_TYPE_ERROR: &'static str = "response packet type is not a login response";
+  
 }
 
 struct CommandPacket;
@@ -291,19 +2653,126 @@ impl PacketKind for CommandPacket {
   
   const TYPE: i32 = COMMAND_TYPE;
   
+  const RESPONSE_TYPE: i32 = COMMAND_RESPONSE_TYPE;
+  
   const INVLID_RESPONSE_ID_ERROR: &'static str = "response packet id mismatched with command packet id";
   
+  const INVALID_RESPONSE_TYPE_ERROR: &'static str = "response packet type is not a command response";
+  
+}
+
+/// A command packet whose responses have a nonstandard packet type.
+struct CustomCommandPacket {
+  
+  response_type: i32
+  
+}
+
+impl PacketKind for CustomCommandPacket {
+  
+  const ACCEPTS_LONG_RESPONSES: bool = true;
+  
+  const TYPE: i32 = COMMAND_TYPE;
+  
+  const RESPONSE_TYPE: i32 = COMMAND_RESPONSE_TYPE;
+  
+  const INVLID_RESPONSE_ID_ERROR: &'static str = CommandPacket::INVLID_RESPONSE_ID_ERROR;
+  
+  const INVALID_RESPONSE_TYPE_ERROR: &'static str = "response packet type is not the expected command response type";
+  
+  fn response_type(&self) -> i32 {
+    self.response_type
+  }
+  
 }
 
 #[derive(Debug)]
 struct SendResponse {
   
   good_auth: bool,
-  payload: String
+  payload: Vec<u8>,
+  packets: u32,
+  bytes: usize,
+  request_id: i32,
+  sent_at: Instant,
+  received_at: Instant
+  
+}
+
+impl SendResponse {
+  
+  fn into_command_response(self) -> io::Result<CommandResponse> {
+    let SendResponse { payload, packets, bytes, request_id, sent_at, received_at, .. } = self;
+    let body = decode_payload(payload)?;
+    Ok(CommandResponse { body, packets, bytes, rtt: received_at - sent_at, sent_at, received_at, request_id })
+  }
+  
+}
+
+/// A command response together with metadata about how it was received.
+/// 
+/// Returned by [`RconClient::send_command_detailed`].
+/// This dereferences to the response text, so it can be used wherever a [`str`] is expected.
+/// 
+/// With the `serde` feature, this can be serialized, without [`sent_at`](CommandResponse::sent_at) and [`received_at`](CommandResponse::received_at)
+/// (which only have meaning within the running program).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CommandResponse {
+  
+  /// The response message from the server.
+  pub body: String,
+  /// The number of response packets the body was reassembled from.
+  /// 
+  /// This is greater than 1 only if the response was [fragmented](https://wiki.vg/RCON#Fragmentation).
+  pub packets: u32,
+  /// The total number of bytes read from the connection for this response, including packet headers.
+  pub bytes: usize,
+  /// The time between sending the command and receiving the complete response.
+  /// 
+  /// This is `received_at - sent_at`.
+  pub rtt: Duration,
+  /// When the command was sent.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub sent_at: Instant,
+  /// When the complete response was received.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub received_at: Instant,
+  /// The id of the packet that carried the command.
+  pub request_id: PacketId
+  
+}
+
+impl Deref for CommandResponse {
+  
+  type Target = str;
+  
+  fn deref(&self) -> &str {
+    &self.body
+  }
+  
+}
+
+impl Display for CommandResponse {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    Display::fmt(&self.body, f)
+  }
+  
+}
+
+impl From<CommandResponse> for String {
+  
+  fn from(response: CommandResponse) -> Self {
+    response.body
+  }
   
 }
 
 /// A failed attempt to log in. See [`RconClient::log_in`] for details.
+/// 
+/// Two `LogInError`s are equal if they are the same variant,
+/// where two [`IO`](LogInError::IO) errors are considered the same if they have the same [`kind`](io::Error::kind).
 #[derive(Debug)]
 pub enum LogInError {
   
@@ -311,10 +2780,90 @@ pub enum LogInError {
   IO(io::Error),
   /// The password was too long.
   PasswordTooLong,
+  /// The password contained a NUL byte or a rejected [control character](RconClient::set_control_characters).
+  InvalidPassword,
   /// The client is already logged in.
   AlreadyLoggedIn,
   /// The password was incorrect.
-  BadPassword
+  BadPassword,
+  /// The server rejected a password recently, so this client did not try again. See [`RconClient::set_log_in_cooldown`].
+  Throttled {
+    
+    /// How long until the cooldown ends.
+    retry_after: Duration
+    
+  },
+  /// An earlier exchange with the server was interrupted part way through, so the connection can no longer be used.
+  /// This only happens with the asynchronous client (see its documentation on cancellation).
+  ConnectionPoisoned
+  
+}
+
+impl LogInError {
+  
+  /// Returns the kind of this error, which can be matched on without destructuring the error.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, io};
+  /// # use mc_rcon::{RconClient, LogInErrorKind};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// if let Err(e) = client.log_in("SuperSecurePassword") {
+  ///   match e.kind() {
+  ///     LogInErrorKind::BadPassword => eprintln!("wrong password"),
+  ///     LogInErrorKind::Io(io::ErrorKind::TimedOut) => eprintln!("server did not respond"),
+  ///     _ => Err(e)?
+  ///   }
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn kind(&self) -> LogInErrorKind {
+    match self {
+      LogInError::IO(e) => LogInErrorKind::Io(e.kind()),
+      LogInError::PasswordTooLong => LogInErrorKind::PasswordTooLong,
+      LogInError::InvalidPassword => LogInErrorKind::InvalidPassword,
+      LogInError::AlreadyLoggedIn => LogInErrorKind::AlreadyLoggedIn,
+      LogInError::BadPassword => LogInErrorKind::BadPassword,
+      LogInError::Throttled { .. } => LogInErrorKind::Throttled,
+      LogInError::ConnectionPoisoned => LogInErrorKind::ConnectionPoisoned
+    }
+  }
+  
+  /// Returns whether this error may be transient, so that trying again with the same client may succeed.
+  /// 
+  /// This is the case for I/O errors of kind [`TimedOut`](io::ErrorKind::TimedOut), [`WouldBlock`](io::ErrorKind::WouldBlock),
+  /// and [`Interrupted`](io::ErrorKind::Interrupted).
+  /// Other I/O errors usually mean that the connection is lost,
+  /// and the other variants would happen again (or, for [`AlreadyLoggedIn`](LogInError::AlreadyLoggedIn), need no retry).
+  /// In particular, [`BadPassword`](LogInError::BadPassword) and [`Throttled`](LogInError::Throttled) are never recoverable,
+  /// since retrying a rejected password may get the client's address banned.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, thread, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// loop {
+  ///   match client.log_in_with_timeout("SuperSecurePassword", Duration::from_secs(5)) {
+  ///     Ok(()) => break,
+  ///     Err(e) if e.is_recoverable() => thread::sleep(Duration::from_secs(1)),
+  ///     Err(e) => Err(e)?
+  ///   }
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn is_recoverable(&self) -> bool {
+    match self {
+      LogInError::IO(e) => is_transient(e),
+      LogInError::PasswordTooLong | LogInError::InvalidPassword | LogInError::AlreadyLoggedIn | LogInError::BadPassword | LogInError::Throttled { .. } | LogInError::ConnectionPoisoned => false
+    }
+  }
   
 }
 
@@ -331,7 +2880,9 @@ impl From<SendError> for LogInError {
   fn from(e: SendError) -> Self {
     match e {
       SendError::IO(e) => LogInError::IO(e),
-      SendError::PayloadTooLong => LogInError::PasswordTooLong
+      SendError::PayloadTooLong => LogInError::PasswordTooLong,
+      SendError::InvalidPayload => LogInError::InvalidPassword,
+      SendError::ConnectionPoisoned => LogInError::ConnectionPoisoned
     }
   }
   
@@ -342,9 +2893,12 @@ impl Display for LogInError {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     match self {
       LogInError::IO(e) => Display::fmt(e, f),
-      LogInError::PasswordTooLong => write!(f, "password must be no longer than {} bytes", MAX_OUTGOING_PAYLOAD_LEN),
+      LogInError::PasswordTooLong => write!(f, "password is longer than the outgoing payload limit"),
+      LogInError::InvalidPassword => write!(f, "password must not contain NUL or control characters"),
       LogInError::AlreadyLoggedIn => write!(f, "tried to log in when already logged in"),
-      LogInError::BadPassword => write!(f, "tried to log in with incorrect password")
+      LogInError::BadPassword => write!(f, "tried to log in with incorrect password"),
+      LogInError::Throttled { retry_after } => write!(f, "not logging in again so soon after an incorrect password; retry after {:?}", retry_after),
+      LogInError::ConnectionPoisoned => write!(f, "connection was left unusable by an interrupted exchange")
     }
   }
   
@@ -352,7 +2906,59 @@ impl Display for LogInError {
 
 impl Error for LogInError {}
 
+impl PartialEq for LogInError {
+  
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (LogInError::IO(a), LogInError::IO(b)) => a.kind() == b.kind(),
+      _ => mem::discriminant(self) == mem::discriminant(other)
+    }
+  }
+  
+}
+
+impl Eq for LogInError {}
+
+impl Hash for LogInError {
+  
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    mem::discriminant(self).hash(state);
+    if let LogInError::IO(e) = self {
+      e.kind().hash(state);
+    }
+  }
+  
+}
+
+/// The kind of a [`LogInError`], as returned by [`LogInError::kind`].
+/// 
+/// Each variant corresponds to the [`LogInError`] variant of the same name,
+/// with [`Io`](LogInErrorKind::Io) holding the [`kind`](io::Error::kind) of the I/O error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogInErrorKind {
+  
+  /// An I/O error of the given kind occurred.
+  Io(io::ErrorKind),
+  /// The password was too long.
+  PasswordTooLong,
+  /// The password contained a NUL byte or a rejected [control character](RconClient::set_control_characters).
+  InvalidPassword,
+  /// The client is already logged in.
+  AlreadyLoggedIn,
+  /// The password was incorrect.
+  BadPassword,
+  /// The server rejected a password recently, so the client did not try again.
+  Throttled,
+  /// An earlier exchange with the server was interrupted part way through, so the connection can no longer be used.
+  /// This only happens with the asynchronous client (see its documentation on cancellation).
+  ConnectionPoisoned
+  
+}
+
 /// A failed attempt to send a command. See [`RconClient::send_command`] for details.
+/// 
+/// Two `CommandError`s are equal if they are the same variant with equal contents,
+/// where two [`IO`](CommandError::IO) errors are considered the same if they have the same [`kind`](io::Error::kind).
 #[derive(Debug)]
 pub enum CommandError {
   
@@ -360,8 +2966,109 @@ pub enum CommandError {
   IO(io::Error),
   /// The command was too long.
   CommandTooLong,
+  /// The command contained a NUL byte or a rejected [control character](RconClient::set_control_characters).
+  InvalidCommand,
   /// The client is not logged in.
-  NotLoggedIn
+  NotLoggedIn,
+  /// An argument given to one of the [typed command wrappers](commands) was invalid, so nothing was sent.
+  /// 
+  /// This contains a description of the problem.
+  InvalidArgument(String),
+  /// The server does not support the command sent by one of the [typed command wrappers](commands).
+  UnsupportedCommand,
+  /// The server's response was not in the format expected by one of the [typed command wrappers](commands).
+  /// 
+  /// This contains the response.
+  UnexpectedResponse(String),
+  /// The command was not sent because the circuit of a [`CircuitBreakerRconClient`] is open.
+  CircuitOpen,
+  /// The command was not sent because the client's [command filter](RconClient::set_command_filter) does not allow it.
+  Forbidden,
+  /// An earlier exchange with the server was interrupted part way through, so the connection can no longer be used.
+  /// This only happens with the asynchronous client (see its documentation on cancellation).
+  ConnectionPoisoned,
+  /// The server did not finish responding before the deadline given to one of the asynchronous client's timed methods,
+  /// or did not answer [`RconClient::ping`] within the [grace period](RconClient::set_response_grace_period).
+  /// 
+  /// [`RconClient`] reports other timeouts as [`IO`](CommandError::IO) errors of kind [`TimedOut`](io::ErrorKind::TimedOut) instead.
+  TimedOut
+  
+}
+
+impl CommandError {
+  
+  /// Returns the kind of this error, which can be matched on without destructuring the error.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, io};
+  /// # use mc_rcon::{RconClient, CommandErrorKind};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// match client.send_command("seed") {
+  ///   Ok(response) => println!("{}", response),
+  ///   Err(e) => match e.kind() {
+  ///     CommandErrorKind::NotLoggedIn => eprintln!("session ended"),
+  ///     CommandErrorKind::Io(io::ErrorKind::TimedOut) => eprintln!("server did not respond"),
+  ///     _ => Err(e)?
+  ///   }
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn kind(&self) -> CommandErrorKind {
+    match self {
+      CommandError::IO(e) => CommandErrorKind::Io(e.kind()),
+      CommandError::CommandTooLong => CommandErrorKind::CommandTooLong,
+      CommandError::InvalidCommand => CommandErrorKind::InvalidCommand,
+      CommandError::NotLoggedIn => CommandErrorKind::NotLoggedIn,
+      CommandError::InvalidArgument(_) => CommandErrorKind::InvalidArgument,
+      CommandError::UnsupportedCommand => CommandErrorKind::UnsupportedCommand,
+      CommandError::UnexpectedResponse(_) => CommandErrorKind::UnexpectedResponse,
+      CommandError::CircuitOpen => CommandErrorKind::CircuitOpen,
+      CommandError::Forbidden => CommandErrorKind::Forbidden,
+      CommandError::ConnectionPoisoned => CommandErrorKind::ConnectionPoisoned,
+      CommandError::TimedOut => CommandErrorKind::TimedOut
+    }
+  }
+  
+  /// Returns whether this error may be transient, so that trying again with the same client may succeed.
+  /// 
+  /// This is the case for I/O errors of kind [`TimedOut`](io::ErrorKind::TimedOut), [`WouldBlock`](io::ErrorKind::WouldBlock),
+  /// and [`Interrupted`](io::ErrorKind::Interrupted), and for [`CircuitOpen`](CommandError::CircuitOpen) once the circuit closes again.
+  /// Other I/O errors usually mean that the connection is lost,
+  /// [`NotLoggedIn`](CommandError::NotLoggedIn) needs the client to [log in](RconClient::log_in) again first,
+  /// [`TimedOut`](CommandError::TimedOut) leaves the asynchronous client [poisoned](CommandError::ConnectionPoisoned),
+  /// and the other variants would happen again.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::{error::Error, thread, time::Duration};
+  /// # use mc_rcon::RconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let seed = loop {
+  ///   match client.send_command("seed") {
+  ///     Ok(seed) => break seed,
+  ///     Err(e) if e.is_recoverable() => thread::sleep(Duration::from_secs(1)),
+  ///     Err(e) => Err(e)?
+  ///   }
+  /// };
+  /// println!("{}", seed);
+  /// #   Ok(())
+  /// # }
+  /// ```
+  pub fn is_recoverable(&self) -> bool {
+    match self {
+      CommandError::IO(e) => is_transient(e),
+      CommandError::CircuitOpen => true,
+      CommandError::CommandTooLong | CommandError::InvalidCommand | CommandError::NotLoggedIn | CommandError::InvalidArgument(_) | CommandError::UnsupportedCommand | CommandError::UnexpectedResponse(_) | CommandError::Forbidden | CommandError::ConnectionPoisoned | CommandError::TimedOut => false
+    }
+  }
   
 }
 
@@ -378,7 +3085,9 @@ impl From<SendError> for CommandError {
   fn from(e: SendError) -> Self {
     match e {
       SendError::IO(e) => CommandError::IO(e),
-      SendError::PayloadTooLong => CommandError::CommandTooLong
+      SendError::PayloadTooLong => CommandError::CommandTooLong,
+      SendError::InvalidPayload => CommandError::InvalidCommand,
+      SendError::ConnectionPoisoned => CommandError::ConnectionPoisoned
     }
   }
   
@@ -389,8 +3098,16 @@ impl Display for CommandError {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     match self {
       CommandError::IO(e) => Display::fmt(e, f),
-      CommandError::CommandTooLong => write!(f, "command must be no longer than {} bytes", MAX_OUTGOING_PAYLOAD_LEN),
-      CommandError::NotLoggedIn => write!(f, "tried to send a command before logging in")
+      CommandError::CommandTooLong => write!(f, "command is longer than the outgoing payload limit"),
+      CommandError::InvalidCommand => write!(f, "command must not contain NUL or control characters"),
+      CommandError::NotLoggedIn => write!(f, "tried to send a command before logging in"),
+      CommandError::InvalidArgument(message) => write!(f, "{}", message),
+      CommandError::UnsupportedCommand => write!(f, "server does not support the command"),
+      CommandError::UnexpectedResponse(response) => write!(f, "unexpected response from server: {:?}", response),
+      CommandError::CircuitOpen => write!(f, "command not sent because the server has failed too many times recently"),
+      CommandError::Forbidden => write!(f, "command not sent because the command filter does not allow it"),
+      CommandError::ConnectionPoisoned => write!(f, "connection was left unusable by an interrupted exchange"),
+      CommandError::TimedOut => write!(f, "server did not respond before the deadline")
     }
   }
   
@@ -398,11 +3115,76 @@ impl Display for CommandError {
 
 impl Error for CommandError {}
 
+impl PartialEq for CommandError {
+  
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (CommandError::IO(a), CommandError::IO(b)) => a.kind() == b.kind(),
+      (CommandError::InvalidArgument(a), CommandError::InvalidArgument(b)) => a == b,
+      (CommandError::UnexpectedResponse(a), CommandError::UnexpectedResponse(b)) => a == b,
+      _ => mem::discriminant(self) == mem::discriminant(other)
+    }
+  }
+  
+}
+
+impl Eq for CommandError {}
+
+impl Hash for CommandError {
+  
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    mem::discriminant(self).hash(state);
+    match self {
+      CommandError::IO(e) => e.kind().hash(state),
+      CommandError::InvalidArgument(message) => message.hash(state),
+      CommandError::UnexpectedResponse(response) => response.hash(state),
+      _ => {}
+    }
+  }
+  
+}
+
+/// The kind of a [`CommandError`], as returned by [`CommandError::kind`].
+/// 
+/// Each variant corresponds to the [`CommandError`] variant of the same name,
+/// with [`Io`](CommandErrorKind::Io) holding the [`kind`](io::Error::kind) of the I/O error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandErrorKind {
+  
+  /// An I/O error of the given kind occurred.
+  Io(io::ErrorKind),
+  /// The command was too long.
+  CommandTooLong,
+  /// The command contained a NUL byte or a rejected [control character](RconClient::set_control_characters).
+  InvalidCommand,
+  /// The client is not logged in.
+  NotLoggedIn,
+  /// An argument given to one of the [typed command wrappers](commands) was invalid, so nothing was sent.
+  InvalidArgument,
+  /// The server does not support the command sent by one of the [typed command wrappers](commands).
+  UnsupportedCommand,
+  /// The server's response was not in the format expected by one of the [typed command wrappers](commands).
+  UnexpectedResponse,
+  /// The command was not sent because the circuit of a [`CircuitBreakerRconClient`] is open.
+  CircuitOpen,
+  /// The command was not sent because the client's [command filter](RconClient::set_command_filter) does not allow it.
+  Forbidden,
+  /// An earlier exchange with the server was interrupted part way through, so the connection can no longer be used.
+  /// This only happens with the asynchronous client (see its documentation on cancellation).
+  ConnectionPoisoned,
+  /// The server did not finish responding before the deadline given to one of the asynchronous client's timed methods.
+  TimedOut
+  
+}
+
 #[derive(Debug)]
 enum SendError {
   
   IO(io::Error),
-  PayloadTooLong
+  PayloadTooLong,
+  InvalidPayload,
+  #[cfg_attr(not(feature = "futures-io"), allow(dead_code))]
+  ConnectionPoisoned
   
 }
 
@@ -0,0 +1,65 @@
+//! Ready-made packet observers for use with [`RconClient::set_packet_observer`](crate::RconClient::set_packet_observer).
+
+use std::{io::Write, sync::Mutex, time::Instant};
+
+use crate::{transcript::TranscriptRecord, Direction, PacketObserver, RawPacketHeader, HEADER_LEN, LOGIN_TYPE};
+
+/// Returns an observer that writes a hex dump of every packet to the given writer.
+/// 
+/// Each packet is written as a line describing its direction and header,
+/// followed by its payload in rows of 16 bytes, with a printable ASCII rendering alongside.
+/// Errors writing to `writer` are ignored.
+/// 
+/// For example, sending the command `seed` with id 1 produces:
+/// 
+/// ```text
+/// >> length=14 id=1 type=2
+/// 0000  73 65 65 64                                      |seed|
+/// ```
+pub fn hex_dump<W: Write + Send + 'static>(writer: W) -> PacketObserver {
+  let writer = Mutex::new(writer);
+  Box::new(move |direction, header, payload| {
+    let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+    let arrow = match direction {
+      Direction::Outgoing => ">>",
+      Direction::Incoming => "<<"
+    };
+    let _ = writeln!(writer, "{} length={} id={} type={}", arrow, header.length, header.id, header.packet_type);
+    for (i, row) in payload.chunks(16).enumerate() {
+      let hex: Vec<_> = row.iter().map(|b| format!("{:02x}", b)).collect();
+      let ascii: String = row.iter().map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }).collect();
+      let _ = writeln!(writer, "{:04x}  {:<47}  |{}|", i * 16, hex.join(" "), ascii);
+    }
+    let _ = writer.flush();
+  })
+}
+
+/// Returns an observer that writes every packet to the given writer in the format described in [`transcript`](crate::transcript),
+/// with the payloads of login packets redacted.
+/// 
+/// This is the observer installed by [`RconClient::start_recording`](crate::RconClient::start_recording).
+/// Errors writing to `writer` are ignored.
+pub fn transcript<W: Write + Send + 'static>(writer: W) -> PacketObserver {
+  transcript_with(writer, true)
+}
+
+/// Returns an observer like [`transcript`], except that login packets are recorded as is, including the password.
+pub fn transcript_unredacted<W: Write + Send + 'static>(writer: W) -> PacketObserver {
+  transcript_with(writer, false)
+}
+
+fn transcript_with<W: Write + Send + 'static>(writer: W, redact_log_in: bool) -> PacketObserver {
+  let writer = Mutex::new(writer);
+  let start = Instant::now();
+  Box::new(move |direction, header, payload| {
+    let (header, payload) = if redact_log_in && direction == Direction::Outgoing && header.packet_type == LOGIN_TYPE {
+      (RawPacketHeader { length: HEADER_LEN as i32, ..*header }, &[][..])
+    } else {
+      (*header, payload)
+    };
+    let record = TranscriptRecord { elapsed: start.elapsed(), direction, header, payload: payload.to_vec() };
+    let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+    let _ = record.write_to(&mut *writer);
+    let _ = writer.flush();
+  })
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+use std::{error::Error, fmt::{self, Display, Formatter}, io::{self, Write}};
+
+use crate::{write_raw_packet, write_raw_packet_vectored, RawPacketHeader, COMMAND_TYPE, HEADER_LEN, I32_LEN, MIN_PACKET_LEN};
+
+/// A whole RCON packet, which can be converted to and from its wire format.
+/// 
+/// [`RconClient`](crate::RconClient) handles packets itself, so this is only needed to work with the protocol directly,
+/// for example in a mock server.
+/// 
+/// A packet is laid out as its length field, id, and type (each a little-endian `i32`),
+/// followed by its payload, a null terminator, and a padding byte:
+/// ```
+/// # use mc_rcon::RconPacket;
+/// let packet = RconPacket::new(7, 2, b"seed".to_vec());
+/// let bytes = packet.encode();
+/// assert_eq!(bytes, [
+///   14, 0, 0, 0, // length: id (4) + type (4) + payload (4) + terminator and padding (2)
+///   7, 0, 0, 0, // id
+///   2, 0, 0, 0, // type
+///   b's', b'e', b'e', b'd', // payload
+///   0, 0 // null terminator and padding
+/// ]);
+/// assert_eq!(RconPacket::decode(&bytes), Ok(packet));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RconPacket {
+  
+  /// The length field, which counts every byte of the packet after itself.
+  pub length: i32,
+  /// The packet id.
+  pub id: i32,
+  /// The packet type.
+  pub packet_type: i32,
+  /// The payload, without its null terminator and padding.
+  pub payload: Vec<u8>
+  
+}
+
+impl RconPacket {
+  
+  /// Construct a packet with the given fields, and the length field that matches the payload.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if the payload is too long for the length field to hold.
+  pub fn new(id: i32, packet_type: i32, payload: Vec<u8>) -> RconPacket {
+    let length = i32::try_from(HEADER_LEN + payload.len()).expect("payload is too long");
+    RconPacket { length, id, packet_type, payload }
+  }
+  
+  /// Construct a command packet with id 0, as [`RconClient::send_command`](crate::RconClient::send_command) would send.
+  /// 
+  /// The server gives its response the same id, so set [`id`](RconPacket::id) to tell the responses to several commands apart.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if the command is too long for the length field to hold.
+  pub fn command(command: &str) -> RconPacket {
+    RconPacket::new(0, COMMAND_TYPE, command.as_bytes().to_vec())
+  }
+  
+  /// Returns the header fields of this packet.
+  pub fn header(&self) -> RawPacketHeader {
+    RawPacketHeader { length: self.length, id: self.id, packet_type: self.packet_type }
+  }
+  
+  /// Returns this packet as it appears on the wire.
+  /// 
+  /// The length field is written as it is, even if it does not match the payload.
+  pub fn encode(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(I32_LEN + HEADER_LEN + self.payload.len());
+    write_raw_packet(&mut bytes, &self.header(), &self.payload).expect("writing to a Vec does not fail");
+    bytes
+  }
+  
+  /// Writes this packet to `writer`, laid out as with [`encode`](RconPacket::encode).
+  /// 
+  /// Like [`RconClient`](crate::RconClient), this offers the whole packet to the writer in one vectored write,
+  /// so that the payload is not copied into a buffer first.
+  /// If the writer only accepts part of it, as writers without vectored writes do, the rest is written from one buffer:
+  /// ```
+  /// # use std::io::{self, IoSlice, Write};
+  /// # use mc_rcon::RconPacket;
+  /// # 
+  /// # // records each write, and accepts vectored writes whole only if `vectored` is set
+  /// # struct Shim { writes: Vec<Vec<u8>>, vectored: bool }
+  /// # impl Write for Shim {
+  /// #   fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.writes.push(buf.to_vec()); Ok(buf.len()) }
+  /// #   fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+  /// #     if self.vectored { self.write(&bufs.iter().flat_map(|buf| buf.iter().copied()).collect::<Vec<_>>()) } else { self.write(bufs.iter().find(|buf| !buf.is_empty()).map_or(&[][..], |buf| &buf[..])) }
+  /// #   }
+  /// #   fn flush(&mut self) -> io::Result<()> { Ok(()) }
+  /// # }
+  /// let packet = RconPacket::command("say hello");
+  /// let mut vectored = Shim { writes: Vec::new(), vectored: true };
+  /// packet.write_to(&mut vectored)?;
+  /// assert_eq!(vectored.writes, [packet.encode()]);
+  /// 
+  /// let mut plain = Shim { writes: Vec::new(), vectored: false };
+  /// packet.write_to(&mut plain)?;
+  /// assert_eq!(plain.writes.iter().map(Vec::len).collect::<Vec<_>>(), [12, 11]); // the header, then the rest
+  /// assert_eq!(plain.writes.concat(), packet.encode());
+  /// # Ok::<(), io::Error>(())
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors from the writer.
+  pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+    write_raw_packet_vectored(writer, &self.header(), &self.payload)
+  }
+  
+  /// Parses a single whole packet from its wire format.
+  /// 
+  /// ```
+  /// # use mc_rcon::{PacketDecodeError, RconPacket};
+  /// assert_eq!(RconPacket::decode(&[10, 0, 0, 0, 0, 0, 0, 0]), Err(PacketDecodeError::TooShort));
+  /// assert_eq!(RconPacket::decode(&[9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]), Err(PacketDecodeError::InvalidLength));
+  /// assert_eq!(RconPacket::decode(&[10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, b'!', 0]), Err(PacketDecodeError::InvalidNullTerminator));
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// * If `bytes` is shorter than [`MIN_PACKET_LEN`] or than its length field implies, returns [`PacketDecodeError::TooShort`].
+  /// * If the length field is too small to be valid, or `bytes` is longer than it implies, returns [`PacketDecodeError::InvalidLength`].
+  /// * If the payload is not followed by two null bytes, returns [`PacketDecodeError::InvalidNullTerminator`].
+  pub fn decode(bytes: &[u8]) -> Result<RconPacket, PacketDecodeError> {
+    if bytes.len() < MIN_PACKET_LEN {
+      Err(PacketDecodeError::TooShort)?
+    }
+    let field = |index: usize| i32::from_le_bytes(bytes[index * I32_LEN..][..I32_LEN].try_into().expect("field is 4 bytes"));
+    let (length, id, packet_type) = (field(0), field(1), field(2));
+    let total_len = match usize::try_from(length) {
+      Ok(length) if length >= HEADER_LEN => I32_LEN + length,
+      _ => Err(PacketDecodeError::InvalidLength)?
+    };
+    if bytes.len() < total_len {
+      Err(PacketDecodeError::TooShort)?
+    } else if bytes.len() > total_len {
+      Err(PacketDecodeError::InvalidLength)?
+    }
+    let (payload, terminator) = bytes[I32_LEN * 3..].split_at(total_len - MIN_PACKET_LEN);
+    if terminator != [0, 0] {
+      Err(PacketDecodeError::InvalidNullTerminator)?
+    }
+    Ok(RconPacket { length, id, packet_type, payload: payload.to_vec() })
+  }
+  
+}
+
+/// A failed attempt to parse a packet. See [`RconPacket::decode`] for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PacketDecodeError {
+  
+  /// There were too few bytes for a whole packet.
+  TooShort,
+  /// The length field was invalid, or did not match the number of bytes.
+  InvalidLength,
+  /// The payload was not followed by a null terminator and padding.
+  InvalidNullTerminator
+  
+}
+
+impl Display for PacketDecodeError {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      PacketDecodeError::TooShort => write!(f, "packet is incomplete"),
+      PacketDecodeError::InvalidLength => write!(f, "packet length field is invalid"),
+      PacketDecodeError::InvalidNullTerminator => write!(f, "packet payload is not null-terminated")
+    }
+  }
+  
+}
+
+impl Error for PacketDecodeError {}
\ No newline at end of file
@@ -0,0 +1,71 @@
+//! Support for servers running [Paper](https://papermc.io) or one of its forks.
+//! 
+//! Paper answers RCON commands like vanilla Minecraft does, but adds commands of its own.
+//! [`PaperRconClient`] exposes them as typed methods.
+
+use crate::{CommandError, RconClient};
+
+/// A wrapper around an [`RconClient`] connected to a Paper server, with methods for Paper's commands.
+/// 
+/// The wrapped client can still be used for any other command through [`inner`](PaperRconClient::inner);
+/// the typed methods of [`RconClient`], like [`tps`](RconClient::tps), give more detail than the methods here.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::error::Error;
+/// # use mc_rcon::{RconClient, paper::PaperRconClient};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = RconClient::connect("localhost:25575")?;
+/// client.log_in("SuperSecurePassword")?;
+/// let client = PaperRconClient::new(client);
+/// let [last_1m, last_5m, last_15m] = client.get_tps()?;
+/// println!("TPS: {} (1m), {} (5m), {} (15m)", last_1m, last_5m, last_15m);
+/// println!("online: {}", client.get_online_players()?.join(", "));
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PaperRconClient {
+  
+  inner: RconClient
+  
+}
+
+impl PaperRconClient {
+  
+  /// Wraps `inner`, which should be connected to a Paper server.
+  pub fn new(inner: RconClient) -> Self {
+    Self { inner }
+  }
+  
+  /// Returns the wrapped client, which can be used directly to log in or to send any other command.
+  pub fn inner(&self) -> &RconClient {
+    &self.inner
+  }
+  
+  /// Consumes this wrapper and returns the wrapped client.
+  pub fn into_inner(self) -> RconClient {
+    self.inner
+  }
+  
+  /// Queries the server's average ticks per second over the last 1, 5, and 15 minutes, in that order, with Paper's `tps` command.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::tps`].
+  pub fn get_tps(&self) -> Result<[f64; 3], CommandError> {
+    let report = self.inner.tps()?;
+    Ok([report.last_1m, report.last_5m, report.last_15m])
+  }
+  
+  /// Gets the names of the players online with `list`.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::list`].
+  pub fn get_online_players(&self) -> Result<Vec<String>, CommandError> {
+    Ok(self.inner.list()?.players)
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// A password that is never shown when formatted, to prevent accidentally logging it.
+/// 
+/// Both [`Display`] and [`Debug`] show `[REDACTED]` instead of the password;
+/// the password itself is only available through [`as_str`](Password::as_str).
+/// 
+/// ```
+/// # use mc_rcon::Password;
+/// let password = Password::new("SuperSecurePassword");
+/// assert_eq!(password.to_string(), "[REDACTED]");
+/// assert_eq!(format!("{:?}", password), "Password([REDACTED])");
+/// assert_eq!(password.as_str(), "SuperSecurePassword");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Password(String);
+
+impl Password {
+  
+  /// Wraps the given password.
+  pub fn new(password: impl Into<String>) -> Self {
+    Self(password.into())
+  }
+  
+  /// Returns the password itself.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+  
+}
+
+impl Display for Password {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "[REDACTED]")
+  }
+  
+}
+
+impl Debug for Password {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "Password([REDACTED])")
+  }
+  
+}
+
+impl From<String> for Password {
+  
+  fn from(password: String) -> Self {
+    Self(password)
+  }
+  
+}
+
+impl From<&str> for Password {
+  
+  fn from(password: &str) -> Self {
+    Self(password.to_owned())
+  }
+  
+}
+
+/// A password that can be used to log in with [`RconClient::log_in`](crate::RconClient::log_in).
+/// 
+/// This is implemented for [`Password`] as well as plain strings.
+pub trait LogInWith {
+  
+  /// Returns the password to send to the server.
+  fn password(&self) -> &str;
+  
+}
+
+impl LogInWith for Password {
+  
+  fn password(&self) -> &str {
+    self.as_str()
+  }
+  
+}
+
+impl LogInWith for str {
+  
+  fn password(&self) -> &str {
+    self
+  }
+  
+}
+
+impl LogInWith for String {
+  
+  fn password(&self) -> &str {
+    self
+  }
+  
+}
+
+impl<T: LogInWith + ?Sized> LogInWith for &T {
+  
+  fn password(&self) -> &str {
+    (**self).password()
+  }
+  
+}
\ No newline at end of file
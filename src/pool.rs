@@ -0,0 +1,306 @@
+use std::{collections::VecDeque, fmt::{self, Debug, Formatter}, io, net::{SocketAddr, ToSocketAddrs}, ops::Deref, panic, sync::{Condvar, Mutex, MutexGuard}, thread, time::{Duration, Instant}};
+
+use crate::{cooldown_remaining, ConnectionState, LogInError, Password, RconClient, DEFAULT_LOG_IN_COOLDOWN};
+
+/// The limits of an [`RconPool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolConfig {
+  
+  /// The number of connections opened when the pool is created, which are kept open even when idle.
+  pub min: usize,
+  /// The maximum number of connections open at once, whether idle or in use.
+  pub max: usize,
+  /// How long a connection may be idle before it is closed (unless that would leave fewer than [`min`](PoolConfig::min) open).
+  pub idle_timeout: Duration,
+  /// How long a connection may be idle before it is [pinged](RconClient::ping) to check that the server still answers before it is handed out.
+  /// 
+  /// Connections that have been used more recently are only checked for having been closed, which needs no round trip to the server.
+  pub health_check_after: Duration
+  
+}
+
+impl Default for PoolConfig {
+  
+  /// Returns a configuration with no minimum, a maximum of 4 connections, an idle timeout of 5 minutes,
+  /// and health checks after 30 seconds idle.
+  fn default() -> Self {
+    PoolConfig { min: 0, max: 4, idle_timeout: Duration::from_secs(300), health_check_after: Duration::from_secs(30) }
+  }
+  
+}
+
+/// A pool of logged-in clients connected to the same server, for programs that send commands from many threads at once.
+/// 
+/// A single [`RconClient`] can be shared between threads, but it handles one command at a time;
+/// a pool lets several commands be in flight at once without paying for a new connection and login for each.
+/// [`get`](RconPool::get) hands out an idle client, or connects and logs in a new one if none are idle,
+/// and the client returns to the pool when the [`PooledClient`] is dropped.
+/// 
+/// Before a client is handed out, it is checked: clients whose connection has been closed,
+/// that have been logged out, or (after [`health_check_after`](PoolConfig::health_check_after)) that fail a [ping](RconClient::ping)
+/// are discarded and replaced.
+/// Clients that hit an I/O error while they were in use are discarded when they are returned.
+/// Idle connections are closed after [`idle_timeout`](PoolConfig::idle_timeout), whenever the pool is next used.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::{error::Error, sync::Arc, thread};
+/// # use mc_rcon::{PoolConfig, RconPool};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let pool = Arc::new(RconPool::new("localhost:25575", "SuperSecurePassword", PoolConfig { min: 1, max: 8, ..PoolConfig::default() })?);
+/// let handles: Vec<_> = (0..16).map(|i| {
+///   let pool = Arc::clone(&pool);
+///   thread::spawn(move || -> Result<String, Box<dyn Error + Send + Sync>> {
+///     Ok(pool.get()?.send_command(&format!("scoreboard players get player{} kills", i))?)
+///   })
+/// }).collect();
+/// for handle in handles {
+///   println!("{}", handle.join().unwrap().map_err(|e| e.to_string())?);
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+pub struct RconPool {
+  
+  server_addr: Vec<SocketAddr>,
+  password: Password,
+  config: PoolConfig,
+  state: Mutex<PoolState>,
+  returned: Condvar,
+  last_bad_password: Mutex<Option<Instant>>
+  
+}
+
+struct PoolState {
+  
+  /// Idle clients, least recently used first.
+  idle: VecDeque<IdleClient>,
+  /// The number of clients open, both idle and in use (including ones that are still connecting).
+  open: usize
+  
+}
+
+struct IdleClient {
+  
+  client: RconClient,
+  since: Instant
+  
+}
+
+impl Debug for RconPool {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let state = self.lock_state();
+    f.debug_struct("RconPool")
+      .field("server_addr", &self.server_addr)
+      .field("config", &self.config)
+      .field("idle", &state.idle.len())
+      .field("open", &state.open)
+      .finish_non_exhaustive()
+  }
+  
+}
+
+impl RconPool {
+  
+  /// Creates a pool of clients connected to the server at the given address and logged in with the given password,
+  /// and opens [`min`](PoolConfig::min) connections.
+  /// 
+  /// The initial connections are [logged in at once](batch_log_in), so creating the pool takes about as long as a single login.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::connect`] and [`RconClient::log_in`], for any of the initial connections,
+  /// or with [`io::ErrorKind::InvalidInput`] if the address does not resolve to any addresses.
+  /// 
+  /// # Panics
+  /// 
+  /// Panics if `config.max` is 0, or if `config.min` is greater than `config.max`.
+  pub fn new<A: ToSocketAddrs>(server_addr: A, password: impl Into<Password>, config: PoolConfig) -> Result<RconPool, LogInError> {
+    assert!(config.max > 0, "pool must allow at least 1 connection");
+    assert!(config.min <= config.max, "pool minimum must be no greater than its maximum");
+    let server_addr: Vec<_> = server_addr.to_socket_addrs()?.collect();
+    if server_addr.is_empty() {
+      Err(io::Error::new(io::ErrorKind::InvalidInput, "could not resolve to any addresses"))?
+    }
+    let pool = RconPool { server_addr, password: password.into(), config, state: Mutex::new(PoolState { idle: VecDeque::new(), open: 0 }), returned: Condvar::new(), last_bad_password: Mutex::new(None) };
+    let clients = (0..config.min).map(|_| RconClient::connect(&pool.server_addr[..])).collect::<io::Result<Vec<_>>>()?;
+    for result in batch_log_in(&clients, pool.password.as_str()) {
+      result?;
+    }
+    let since = Instant::now();
+    let idle = clients.into_iter().map(|client| IdleClient { client, since }).collect();
+    *pool.lock_state() = PoolState { idle, open: config.min };
+    Ok(pool)
+  }
+  
+  /// Returns this pool's configuration.
+  pub fn config(&self) -> PoolConfig {
+    self.config
+  }
+  
+  /// Returns the number of connections open, both idle and in use.
+  pub fn open_connections(&self) -> usize {
+    self.lock_state().open
+  }
+  
+  /// Returns the number of idle connections.
+  pub fn idle_connections(&self) -> usize {
+    self.lock_state().idle.len()
+  }
+  
+  /// Takes a healthy client from the pool, or connects and logs in a new one if there are none.
+  /// 
+  /// If [`max`](PoolConfig::max) connections are already in use, this blocks until one is returned.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::connect`] and [`RconClient::log_in`], if a new connection is needed.
+  /// Like a single client, the pool does not try to log in again for [`DEFAULT_LOG_IN_COOLDOWN`] after the server rejects the password,
+  /// failing with [`LogInError::Throttled`] instead.
+  pub fn get(&self) -> Result<PooledClient<'_>, LogInError> {
+    let mut state = self.lock_state();
+    loop {
+      self.close_expired(&mut state);
+      if let Some(idle) = state.idle.pop_back() { // the most recently used client is the least likely to have been closed
+        drop(state);
+        if self.is_healthy(&idle) {
+          return Ok(PooledClient::new(self, idle.client))
+        }
+        drop(idle);
+        state = self.lock_state();
+        state.open -= 1;
+      } else if state.open < self.config.max {
+        state.open += 1;
+        drop(state);
+        return match self.connect() {
+          Ok(client) => Ok(PooledClient::new(self, client)),
+          Err(e) => {
+            self.lock_state().open -= 1;
+            self.returned.notify_one();
+            Err(e)
+          }
+        }
+      } else {
+        state = self.returned.wait(state).unwrap_or_else(|e| e.into_inner());
+      }
+    }
+  }
+  
+  fn connect(&self) -> Result<RconClient, LogInError> {
+    // each connection is a new client, so the pool keeps its own cooldown
+    let last_bad_password = *self.last_bad_password.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(retry_after) = cooldown_remaining(last_bad_password, DEFAULT_LOG_IN_COOLDOWN) {
+      Err(LogInError::Throttled { retry_after })?
+    }
+    let client = RconClient::connect(&self.server_addr[..])?;
+    match client.log_in(&self.password) {
+      Ok(()) => Ok(client),
+      Err(LogInError::BadPassword) => {
+        *self.last_bad_password.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+        Err(LogInError::BadPassword)
+      }
+      Err(e) => Err(e)
+    }
+  }
+  
+  fn is_healthy(&self, idle: &IdleClient) -> bool {
+    idle.client.is_logged_in() && idle.client.is_connection_idle() && (idle.since.elapsed() < self.config.health_check_after || idle.client.ping().is_ok())
+  }
+  
+  /// Closes clients that have been idle for longer than the idle timeout, while more than the minimum are open.
+  fn close_expired(&self, state: &mut PoolState) {
+    while state.open > self.config.min && state.idle.front().is_some_and(|idle| idle.since.elapsed() >= self.config.idle_timeout) {
+      state.idle.pop_front();
+      state.open -= 1;
+    }
+  }
+  
+  fn lock_state(&self) -> MutexGuard<'_, PoolState> {
+    self.state.lock().unwrap_or_else(|e| e.into_inner())
+  }
+  
+}
+
+/// A client borrowed from an [`RconPool`], which returns to the pool when dropped. See [`RconPool::get`].
+/// 
+/// This dereferences to the [`RconClient`], so commands can be sent with it directly.
+/// If an I/O error occurs while it is in use, or it is logged out, the client is closed instead of being returned,
+/// and the pool connects a new one when one is next needed.
+pub struct PooledClient<'a> {
+  
+  pool: &'a RconPool,
+  client: Option<RconClient>,
+  io_errors: u64
+  
+}
+
+impl<'a> PooledClient<'a> {
+  
+  fn new(pool: &'a RconPool, client: RconClient) -> Self {
+    let io_errors = client.stats().io_errors;
+    PooledClient { pool, client: Some(client), io_errors }
+  }
+  
+}
+
+impl Debug for PooledClient<'_> {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("PooledClient").field(&**self).finish()
+  }
+  
+}
+
+impl Deref for PooledClient<'_> {
+  
+  type Target = RconClient;
+  
+  fn deref(&self) -> &RconClient {
+    self.client.as_ref().expect("client is only taken when dropped")
+  }
+  
+}
+
+impl Drop for PooledClient<'_> {
+  
+  fn drop(&mut self) {
+    let client = self.client.take().expect("client is only taken when dropped");
+    let broken = client.state() != ConnectionState::LoggedIn || client.stats().io_errors != self.io_errors;
+    let mut state = self.pool.lock_state();
+    if broken {
+      state.open -= 1;
+    } else {
+      state.idle.push_back(IdleClient { client, since: Instant::now() });
+    }
+    drop(state);
+    self.pool.returned.notify_one();
+  }
+  
+}
+
+/// Logs in each of the given clients with the same password, all at once, and returns the result for each client in the same order.
+/// 
+/// Each login is done on its own thread, so logging in many clients takes about as long as logging in one,
+/// rather than one round trip to the server per client.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::error::Error;
+/// # use mc_rcon::{batch_log_in, RconClient};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let clients = (0..10).map(|_| RconClient::connect("localhost:25575")).collect::<Result<Vec<_>, _>>()?;
+/// for result in batch_log_in(&clients, "SuperSecurePassword") {
+///   result?;
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+pub fn batch_log_in(clients: &[RconClient], password: &str) -> Vec<Result<(), LogInError>> {
+  thread::scope(|scope| {
+    let handles: Vec<_> = clients.iter().map(|client| scope.spawn(move || client.log_in(password))).collect();
+    handles.into_iter().map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e))).collect()
+  })
+}
\ No newline at end of file
@@ -0,0 +1,133 @@
+use std::{io::{self, ErrorKind, Read, Write}, net::{TcpStream, ToSocketAddrs}, time::{Duration, Instant}};
+
+use crate::{timed_out, write_raw_packet, Dialect, RawPacketHeader, COMMAND_RESPONSE_TYPE, HEADER_LEN, I32_LEN, LOGIN_RESPONSE_TYPE, LOGIN_TYPE, MAX_INCOMING_PAYLOAD_LEN};
+
+/// The most bytes of an unrecognized response that [`probe`] returns in [`ProbeResult::NotRcon`].
+pub const PROBE_SAMPLE_LEN: usize = 64;
+
+/// What [`probe`] found at an address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProbeResult {
+  
+  /// The server answered the login attempt with a properly framed RCON packet.
+  ValidRcon {
+    
+    /// The dialect the server appears to speak, judging by its first packet.
+    dialect: Dialect
+    
+  },
+  /// The server closed the connection without sending anything,
+  /// which some RCON servers do instead of rejecting a login attempt (though other servers may close it too).
+  LikelyRconAuthReject,
+  /// The server sent something that is not an RCON packet, such as an HTTP response.
+  NotRcon {
+    
+    /// The first bytes the server sent, up to [`PROBE_SAMPLE_LEN`] of them.
+    sample: Vec<u8>
+    
+  },
+  /// The server accepted the connection, but did not send anything before the timeout.
+  TimedOut
+  
+}
+
+/// Checks whether the server at the given address speaks RCON, without risking waiting for it indefinitely.
+/// 
+/// This connects to the server, attempts to log in with an empty password (which no RCON server accepts), and classifies the response.
+/// Everything, including connecting, must happen within `timeout`,
+/// so this is suitable for scanning ports that might be running anything, like Minecraft query ports or web panels.
+/// 
+/// Example:
+/// ```
+/// # use std::{error::Error, io::Write, net::TcpListener, thread, time::Duration};
+/// # use mc_rcon::{probe, ProbeResult};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let listener = TcpListener::bind("127.0.0.1:0")?;
+/// let addr = listener.local_addr()?;
+/// thread::spawn(move || listener.accept()?.0.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n"));
+/// match probe(addr, Duration::from_secs(5))? {
+///   ProbeResult::ValidRcon { .. } => println!("{} speaks RCON", addr),
+///   ProbeResult::NotRcon { sample } => assert!(sample.starts_with(b"HTTP/1.1")),
+///   result => println!("{} might not speak RCON: {:?}", addr, result)
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+/// 
+/// # Errors
+/// 
+/// Returns any I/O errors that occur while connecting to the server, sending the login attempt, or reading the response,
+/// except those that indicate one of the [`ProbeResult`]s.
+/// In particular, if the connection is not established before the timeout, the error is [`TimedOut`](ErrorKind::TimedOut).
+pub fn probe<A: ToSocketAddrs>(server_addr: A, timeout: Duration) -> io::Result<ProbeResult> {
+  let deadline = Instant::now() + timeout;
+  let mut stream = connect_before(server_addr, deadline)?;
+  stream.set_write_timeout(Some(remaining(deadline)?))?;
+  let mut packet = Vec::new();
+  write_raw_packet(&mut packet, &RawPacketHeader { length: HEADER_LEN as i32, id: 0, packet_type: LOGIN_TYPE }, b"")?;
+  stream.write_all(&packet)?;
+  
+  let mut buf = Vec::new();
+  let mut chunk = [0; PROBE_SAMPLE_LEN];
+  loop {
+    if let Some(result) = classify(&buf) {
+      return Ok(result)
+    }
+    let read = remaining(deadline).and_then(|remaining| stream.set_read_timeout(Some(remaining))).and_then(|()| stream.read(&mut chunk));
+    match read.map_err(timed_out) {
+      Ok(0) if buf.is_empty() => return Ok(ProbeResult::LikelyRconAuthReject),
+      Ok(0) => return Ok(not_rcon(&buf)),
+      Ok(read) => buf.extend_from_slice(&chunk[..read]),
+      Err(e) if e.kind() == ErrorKind::Interrupted => {}
+      Err(e) if matches!(e.kind(), ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted) && buf.is_empty() => return Ok(ProbeResult::LikelyRconAuthReject),
+      Err(e) if e.kind() == ErrorKind::TimedOut && buf.is_empty() => return Ok(ProbeResult::TimedOut),
+      Err(e) if e.kind() == ErrorKind::TimedOut => return Ok(not_rcon(&buf)), // part of a packet, or something else entirely
+      Err(e) => Err(e)?
+    }
+  }
+}
+
+/// Connects to the first of the given addresses that accepts a connection before `deadline`.
+fn connect_before<A: ToSocketAddrs>(server_addr: A, deadline: Instant) -> io::Result<TcpStream> {
+  let mut last_error = io::Error::new(ErrorKind::InvalidInput, "could not resolve to any addresses");
+  for addr in server_addr.to_socket_addrs()? {
+    match TcpStream::connect_timeout(&addr, remaining(deadline)?) {
+      Ok(stream) => return Ok(stream),
+      Err(e) => last_error = e
+    }
+  }
+  Err(last_error)
+}
+
+/// Returns the time left until `deadline`, or a [`TimedOut`](ErrorKind::TimedOut) error if it has passed.
+fn remaining(deadline: Instant) -> io::Result<Duration> {
+  match deadline.saturating_duration_since(Instant::now()) {
+    remaining if remaining.is_zero() => Err(io::Error::new(ErrorKind::TimedOut, "timed out")),
+    remaining => Ok(remaining)
+  }
+}
+
+/// Classifies the bytes received so far, or returns `None` if more are needed.
+fn classify(buf: &[u8]) -> Option<ProbeResult> {
+  let field = |index: usize| buf.get(index * I32_LEN..(index + 1) * I32_LEN).map(|bytes| i32::from_le_bytes(bytes.try_into().expect("field is 4 bytes")));
+  let length = field(0)?;
+  let body_len = match usize::try_from(length) {
+    Ok(length) if (HEADER_LEN..=HEADER_LEN + MAX_INCOMING_PAYLOAD_LEN).contains(&length) => length,
+    _ => return Some(not_rcon(buf))
+  };
+  let body = buf.get(I32_LEN..I32_LEN + body_len)?;
+  let (id, packet_type) = (field(1)?, field(2)?);
+  let payload_len = body_len - HEADER_LEN;
+  let well_formed = (id == 0 || id == -1) && body.ends_with(&[0, 0]);
+  let dialect = match packet_type {
+    LOGIN_RESPONSE_TYPE => Dialect::Minecraft,
+    COMMAND_RESPONSE_TYPE if payload_len == 0 => Dialect::Source, // Source servers send an empty response before the login response
+    _ => return Some(not_rcon(buf))
+  };
+  Some(if well_formed { ProbeResult::ValidRcon { dialect } } else { not_rcon(buf) })
+}
+
+fn not_rcon(buf: &[u8]) -> ProbeResult {
+  ProbeResult::NotRcon { sample: buf[..buf.len().min(PROBE_SAMPLE_LEN)].to_vec() }
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use std::{io::{self, BufRead, BufReader, ErrorKind, Read, Write}, sync::Mutex};
+
+use crate::{CommandError, RconClient};
+
+/// A wrapper around an [`RconClient`] that records each command it sends, along with the response, in a simple text format.
+/// 
+/// Each exchange is written as:
+/// 
+/// ```text
+/// CMD: <command>
+/// RSP: <response>
+/// ---
+/// ```
+/// 
+/// where the response may span several lines.
+/// Recorded sessions can be read back with [`play_rcon_session`], for example to check a mock server against a real one.
+/// 
+/// Only commands sent through [`send_command`](RecordingRconClient::send_command) are recorded,
+/// and only if the server responds to them.
+/// To record every packet instead, including logging in, see [`RconClient::start_recording`].
+/// 
+/// Example:
+/// ```no_run
+/// # use std::{error::Error, fs::File};
+/// # use mc_rcon::{RconClient, RecordingRconClient};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = RconClient::connect("localhost:25575")?;
+/// client.log_in("SuperSecurePassword")?;
+/// let client = RecordingRconClient::new(client, File::create("session.txt")?);
+/// client.send_command("list")?;
+/// client.send_command("seed")?;
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RecordingRconClient<W: Write> {
+  
+  inner: RconClient,
+  writer: Mutex<W>
+  
+}
+
+impl<W: Write> RecordingRconClient<W> {
+  
+  /// Wraps `inner`, recording its exchanges to `writer`.
+  pub fn new(inner: RconClient, writer: W) -> Self {
+    Self { inner, writer: Mutex::new(writer) }
+  }
+  
+  /// Returns the wrapped client, which can be used directly to log in or to send commands without recording them.
+  pub fn inner(&self) -> &RconClient {
+    &self.inner
+  }
+  
+  /// Consumes this wrapper and returns the wrapped client and the writer.
+  pub fn into_inner(self) -> (RconClient, W) {
+    (self.inner, self.writer.into_inner().unwrap_or_else(|e| e.into_inner()))
+  }
+  
+  /// Sends the given command with [`RconClient::send_command`], then records it along with its response.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::send_command`],
+  /// and with [`CommandError::IO`] if the exchange could not be recorded (in which case the command has already been sent).
+  pub fn send_command(&self, command: &str) -> Result<String, CommandError> {
+    let response = self.inner.send_command(command)?;
+    let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+    write!(writer, "CMD: {}\nRSP: {}\n---\n", command, response)?;
+    writer.flush()?;
+    Ok(response)
+  }
+  
+}
+
+/// Reads a session recorded by a [`RecordingRconClient`], returning each command along with its response.
+/// 
+/// Example:
+/// ```
+/// # use std::error::Error;
+/// # use mc_rcon::play_rcon_session;
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let recorded = "CMD: seed\nRSP: Seed: [42]\n---\nCMD: help gamemode\nRSP: /gamemode <gamemode>\n/gamemode <gamemode> <target>\n---\n";
+/// let session = play_rcon_session(recorded.as_bytes())?;
+/// assert_eq!(session[0], ("seed".to_owned(), "Seed: [42]".to_owned()));
+/// assert_eq!(session[1].1, "/gamemode <gamemode>\n/gamemode <gamemode> <target>");
+/// #   Ok(())
+/// # }
+/// ```
+/// 
+/// Note that a response containing a line that is exactly `---` cannot be read back correctly,
+/// since that line ends each exchange.
+/// 
+/// # Errors
+/// 
+/// Returns any I/O errors that occur while reading.
+/// If the session is malformed, or ends part way through an exchange, the error is [`InvalidData`](ErrorKind::InvalidData).
+pub fn play_rcon_session<R: Read>(reader: R) -> io::Result<Vec<(String, String)>> {
+  let malformed = || io::Error::new(ErrorKind::InvalidData, "recorded RCON session is malformed");
+  let mut lines = BufReader::new(reader).lines();
+  let mut session = Vec::new();
+  while let Some(line) = lines.next() {
+    let command = line?.strip_prefix("CMD: ").ok_or_else(malformed)?.to_owned();
+    let mut response = lines.next().ok_or_else(malformed)??.strip_prefix("RSP: ").ok_or_else(malformed)?.to_owned();
+    loop {
+      let line = lines.next().ok_or_else(malformed)??;
+      if line == "---" {
+        break
+      }
+      response.push('\n');
+      response.push_str(&line);
+    }
+    session.push((command, response));
+  }
+  Ok(session)
+}
\ No newline at end of file
@@ -0,0 +1,216 @@
+//! Serializable copies of this crate's errors, for the `serde` feature.
+//! 
+//! Errors can hold [`io::Error`]s, which cannot be serialized, so each error enum has a record type that mirrors it,
+//! with I/O errors recorded as their [`kind`](io::Error::kind) and message.
+//! The public data types, like [`CommandResponse`](crate::CommandResponse), [`ListResponse`](crate::response::minecraft::ListResponse),
+//! [`TpsReport`](crate::commands::TpsReport), [`BanEntry`](crate::commands::BanEntry), and [`RconStats`](crate::RconStats),
+//! implement `Serialize` and `Deserialize` themselves (except that [`CommandResponse`](crate::CommandResponse) is only `Serialize`).
+//! 
+//! ```
+//! # use std::{error::Error, time::{Duration, Instant, SystemTime}};
+//! # use mc_rcon::{CommandResponse, RconStats, commands::{BanEntry, MsptReport, MsptStats, TpsReport}, response::minecraft::ListResponse};
+//! # use serde::{de::DeserializeOwned, Serialize};
+//! # 
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Result<T, serde_json::Error> {
+//!   serde_json::from_str(&serde_json::to_string(value)?)
+//! }
+//! 
+//! let list = ListResponse { online: 1, max: 20, players: vec!["Steve".into()] };
+//! assert_eq!(round_trip(&list)?, list);
+//! let tps = TpsReport { last_1m: 20.0, last_5m: 19.5, last_15m: 19.98 };
+//! assert_eq!(round_trip(&tps)?, tps);
+//! let stats = MsptStats { avg: Some(6.2), min: Some(3.1), median: None, p95: None, max: Some(13.1) };
+//! let mspt = MsptReport { last_5s: stats, last_10s: stats, last_1m: MsptStats::default() };
+//! assert_eq!(round_trip(&mspt)?, mspt);
+//! let ban = BanEntry { target: "Steve".into(), source: "Rcon".into(), reason: "Griefing".into() };
+//! assert_eq!(round_trip(&ban)?, ban);
+//! let stats = RconStats { commands_sent: 3, bytes_read: 120, last_success: Some(SystemTime::now()), ..RconStats::default() };
+//! assert_eq!(round_trip(&stats)?, stats);
+//! 
+//! let now = Instant::now();
+//! let response = CommandResponse { body: "Seed: [42]".into(), packets: 1, bytes: 24, rtt: Duration::from_millis(3), sent_at: now, received_at: now, request_id: 7 };
+//! let json = serde_json::to_string(&response)?;
+//! assert_eq!(json, r#"{"body":"Seed: [42]","packets":1,"bytes":24,"rtt":{"secs":0,"nanos":3000000},"request_id":7}"#);
+//! #   Ok(())
+//! # }
+//! ```
+
+use std::{io, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{commands::StopError, CommandError, LogInError};
+
+/// A serializable copy of an [`io::Error`].
+/// 
+/// ```
+/// # use std::io;
+/// # use mc_rcon::IoErrorRecord;
+/// let record = IoErrorRecord::from(&io::Error::new(io::ErrorKind::TimedOut, "server did not respond"));
+/// assert_eq!(record, IoErrorRecord { kind: "TimedOut".into(), message: "server did not respond".into() });
+/// assert_eq!(serde_json::from_str::<IoErrorRecord>(&serde_json::to_string(&record).unwrap()).unwrap(), record);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IoErrorRecord {
+  
+  /// The name of the error's [`ErrorKind`](io::ErrorKind), like `"ConnectionRefused"`.
+  pub kind: String,
+  /// The error's message.
+  pub message: String
+  
+}
+
+impl From<&io::Error> for IoErrorRecord {
+  
+  fn from(e: &io::Error) -> Self {
+    IoErrorRecord { kind: format!("{:?}", e.kind()), message: e.to_string() }
+  }
+  
+}
+
+/// A serializable copy of a [`LogInError`].
+/// 
+/// Each variant corresponds to the [`LogInError`] variant of the same name.
+/// 
+/// ```
+/// # use mc_rcon::{LogInError, LogInErrorRecord};
+/// let record = LogInErrorRecord::from(&LogInError::BadPassword);
+/// assert_eq!(serde_json::to_string(&record).unwrap(), r#""BadPassword""#);
+/// assert_eq!(serde_json::from_str::<LogInErrorRecord>(r#""BadPassword""#).unwrap(), record);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LogInErrorRecord {
+  
+  /// An I/O error occured.
+  IO(IoErrorRecord),
+  /// The password was too long.
+  PasswordTooLong,
+  /// The password contained a NUL byte or a rejected control character.
+  InvalidPassword,
+  /// The client is already logged in.
+  AlreadyLoggedIn,
+  /// The password was incorrect.
+  BadPassword,
+  /// The server rejected a password recently, so the client did not try again.
+  Throttled {
+    
+    /// How long until the cooldown ends.
+    retry_after: Duration
+    
+  },
+  /// An earlier exchange with the server was interrupted part way through, so the connection can no longer be used.
+  ConnectionPoisoned
+  
+}
+
+impl From<&LogInError> for LogInErrorRecord {
+  
+  fn from(e: &LogInError) -> Self {
+    match e {
+      LogInError::IO(e) => LogInErrorRecord::IO(e.into()),
+      LogInError::PasswordTooLong => LogInErrorRecord::PasswordTooLong,
+      LogInError::InvalidPassword => LogInErrorRecord::InvalidPassword,
+      LogInError::AlreadyLoggedIn => LogInErrorRecord::AlreadyLoggedIn,
+      LogInError::BadPassword => LogInErrorRecord::BadPassword,
+      LogInError::Throttled { retry_after } => LogInErrorRecord::Throttled { retry_after: *retry_after },
+      LogInError::ConnectionPoisoned => LogInErrorRecord::ConnectionPoisoned
+    }
+  }
+  
+}
+
+/// A serializable copy of a [`CommandError`].
+/// 
+/// Each variant corresponds to the [`CommandError`] variant of the same name.
+/// 
+/// ```
+/// # use std::io;
+/// # use mc_rcon::{CommandError, CommandErrorRecord};
+/// let e = CommandError::IO(io::Error::new(io::ErrorKind::ConnectionAborted, "server closed the connection"));
+/// let json = serde_json::to_string(&CommandErrorRecord::from(&e)).unwrap();
+/// assert_eq!(json, r#"{"IO":{"kind":"ConnectionAborted","message":"server closed the connection"}}"#);
+/// assert_eq!(serde_json::from_str::<CommandErrorRecord>(&json).unwrap(), CommandErrorRecord::from(&e));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CommandErrorRecord {
+  
+  /// An I/O error occurred.
+  IO(IoErrorRecord),
+  /// The command was too long.
+  CommandTooLong,
+  /// The command contained a NUL byte or a rejected control character.
+  InvalidCommand,
+  /// The client is not logged in.
+  NotLoggedIn,
+  /// An argument given to one of the typed command wrappers was invalid, so nothing was sent.
+  InvalidArgument(String),
+  /// The server does not support the command sent by one of the typed command wrappers.
+  UnsupportedCommand,
+  /// The server's response was not in the format expected by one of the typed command wrappers.
+  UnexpectedResponse(String),
+  /// The command was not sent because the circuit of a circuit breaker is open.
+  CircuitOpen,
+  /// The command was not sent because the client's command filter does not allow it.
+  Forbidden,
+  /// An earlier exchange with the server was interrupted part way through, so the connection can no longer be used.
+  ConnectionPoisoned,
+  /// The server did not finish responding before the deadline.
+  TimedOut
+  
+}
+
+impl From<&CommandError> for CommandErrorRecord {
+  
+  fn from(e: &CommandError) -> Self {
+    match e {
+      CommandError::IO(e) => CommandErrorRecord::IO(e.into()),
+      CommandError::CommandTooLong => CommandErrorRecord::CommandTooLong,
+      CommandError::InvalidCommand => CommandErrorRecord::InvalidCommand,
+      CommandError::NotLoggedIn => CommandErrorRecord::NotLoggedIn,
+      CommandError::InvalidArgument(message) => CommandErrorRecord::InvalidArgument(message.clone()),
+      CommandError::UnsupportedCommand => CommandErrorRecord::UnsupportedCommand,
+      CommandError::UnexpectedResponse(response) => CommandErrorRecord::UnexpectedResponse(response.clone()),
+      CommandError::CircuitOpen => CommandErrorRecord::CircuitOpen,
+      CommandError::Forbidden => CommandErrorRecord::Forbidden,
+      CommandError::ConnectionPoisoned => CommandErrorRecord::ConnectionPoisoned,
+      CommandError::TimedOut => CommandErrorRecord::TimedOut
+    }
+  }
+  
+}
+
+/// A serializable copy of a [`StopError`].
+/// 
+/// Each variant corresponds to the [`StopError`] variant of the same name.
+/// 
+/// ```
+/// # use mc_rcon::{CommandError, StopErrorRecord, commands::StopError};
+/// let record = StopErrorRecord::from(&StopError::Command(CommandError::NotLoggedIn));
+/// let json = serde_json::to_string(&record).unwrap();
+/// assert_eq!(json, r#"{"Command":"NotLoggedIn"}"#);
+/// assert_eq!(serde_json::from_str::<StopErrorRecord>(&json).unwrap(), record);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StopErrorRecord {
+  
+  /// The `stop` command could not be sent.
+  Command(CommandErrorRecord),
+  /// The `stop` command was sent, but the server did not close the connection in time.
+  TimedOut,
+  /// The `stop` command was sent, but an I/O error occurred while waiting for the server to close the connection.
+  IO(IoErrorRecord)
+  
+}
+
+impl From<&StopError> for StopErrorRecord {
+  
+  fn from(e: &StopError) -> Self {
+    match e {
+      StopError::Command(e) => StopErrorRecord::Command(e.into()),
+      StopError::TimedOut => StopErrorRecord::TimedOut,
+      StopError::IO(e) => StopErrorRecord::IO(e.into())
+    }
+  }
+  
+}
\ No newline at end of file
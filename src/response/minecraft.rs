@@ -0,0 +1,80 @@
+//! Parsers for the responses of vanilla Minecraft and common server software like Paper.
+
+use std::{error::Error, fmt::{self, Display, Formatter}};
+
+use crate::formatting::strip_codes;
+
+/// A failure to parse a response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+  
+  /// The response is not in any of the formats the parser recognizes.
+  UnrecognizedFormat
+  
+}
+
+impl Display for ParseError {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseError::UnrecognizedFormat => write!(f, "response is not in a recognized format")
+    }
+  }
+  
+}
+
+impl Error for ParseError {}
+
+/// The response to the `list` command. See [`parse_list_response`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListResponse {
+  
+  /// The number of players online.
+  pub online: u32,
+  /// The maximum number of players that can be online.
+  pub max: u32,
+  /// The names of the players online.
+  /// 
+  /// Some servers only list a limited number of names, or none at all, so this may be shorter than [`online`](ListResponse::online).
+  pub players: Vec<String>
+  
+}
+
+/// Parses the response to the `list` command.
+/// 
+/// This recognizes the formats used by current and older versions of vanilla Minecraft, as well as Bukkit derivatives like Paper
+/// (including their grouped player lists), and ignores [formatting codes](crate::formatting).
+/// 
+/// ```
+/// # use mc_rcon::response::minecraft::{parse_list_response, ListResponse};
+/// let list = parse_list_response("There are 2 of a max of 20 players online: Steve, Alex").unwrap();
+/// assert_eq!(list, ListResponse { online: 2, max: 20, players: vec!["Steve".into(), "Alex".into()] });
+/// ```
+/// 
+/// # Errors
+/// 
+/// Returns [`ParseError::UnrecognizedFormat`] if the response is not in a recognized format.
+pub fn parse_list_response(s: &str) -> Result<ListResponse, ParseError> {
+  let text = strip_codes(s);
+  // "There are 2 of a max of 20 players online: Steve, Alex" in current versions,
+  // "There are 2/20 players online:Steve, Alex" in older versions,
+  // or "There are 2 out of maximum 20 players online." followed by lines like "default: Steve, Alex" in Bukkit derivatives
+  let rest = text.trim_start().strip_prefix("There are ").ok_or(ParseError::UnrecognizedFormat)?;
+  let (counts, players) = rest.split_once(" players online").ok_or(ParseError::UnrecognizedFormat)?;
+  let (online, max) = [" of a max of ", " of a max ", " out of maximum ", "/"]
+    .into_iter()
+    .find_map(|separator| counts.split_once(separator))
+    .ok_or(ParseError::UnrecognizedFormat)?;
+  let count = |count: &str| count.trim().parse().map_err(|_| ParseError::UnrecognizedFormat);
+  let players = players
+    .trim_start_matches(['.', ':'])
+    .lines()
+    // grouped lists prefix each line with the group name, and player names never contain colons
+    .flat_map(|line| line.rsplit(':').next().unwrap_or(line).split(','))
+    .map(str::trim)
+    .filter(|name| !name.is_empty())
+    .map(str::to_owned)
+    .collect();
+  Ok(ListResponse { online: count(online)?, max: count(max)?, players })
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+//! Parsers for the responses to common commands.
+//! 
+//! Unlike the [typed command wrappers](crate::commands), these only interpret response text,
+//! so they can be used with responses obtained in any way, such as from [`send_command`](crate::RconClient::send_command).
+
+pub mod minecraft;
\ No newline at end of file
@@ -0,0 +1,83 @@
+use std::{io, time::Instant};
+
+use async_io::Timer;
+use async_net::{AsyncToSocketAddrs, TcpStream};
+use futures_lite::future;
+
+use crate::{AsyncRconClient, CommandError};
+
+/// An [`AsyncRconClient`] for [`smol`](https://docs.rs/smol) and any other executor that can drive [`async-io`](https://docs.rs/async-io),
+/// such as `async-std` or `async-executor`, available with the `async-smol` feature.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::error::Error;
+/// # use mc_rcon::SmolRconClient;
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # futures_lite::future::block_on(async {
+/// let mut client = SmolRconClient::connect("localhost:25575").await?;
+/// client.log_in("SuperSecurePassword").await?;
+/// println!("{}", client.send_command("seed").await?);
+/// #   Ok(())
+/// # })
+/// # }
+/// ```
+pub type SmolRconClient = AsyncRconClient<TcpStream>;
+
+impl AsyncRconClient<TcpStream> {
+  
+  /// Construct a `SmolRconClient` and connect to a server at the given address.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::connect`](crate::RconClient::connect).
+  pub async fn connect<A: AsyncToSocketAddrs>(server_addr: A) -> io::Result<SmolRconClient> {
+    Ok(AsyncRconClient::new(TcpStream::connect(server_addr).await?))
+  }
+  
+  /// Construct a `SmolRconClient` and connect to a server at the given address, failing if that is not done by `deadline`.
+  /// 
+  /// The deadline covers resolving the address as well as connecting.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`connect`](AsyncRconClient::connect),
+  /// or with an error of kind [`TimedOut`](io::ErrorKind::TimedOut) if `deadline` passes first.
+  pub async fn connect_deadline<A: AsyncToSocketAddrs>(server_addr: A, deadline: Instant) -> io::Result<SmolRconClient> {
+    future::or(Self::connect(server_addr), async {
+      Timer::at(deadline).await;
+      Err(io::Error::new(io::ErrorKind::TimedOut, "timed out connecting to the server"))
+    }).await
+  }
+  
+  /// Sends the given command to the server and returns its response, failing if the whole exchange is not done by `deadline`.
+  /// 
+  /// Taking an [`Instant`] rather than a duration allows one time budget to be shared between several commands:
+  /// ```no_run
+  /// # use std::{error::Error, time::{Duration, Instant}};
+  /// # use mc_rcon::SmolRconClient;
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// # futures_lite::future::block_on(async {
+  /// let deadline = Instant::now() + Duration::from_secs(5);
+  /// let mut client = SmolRconClient::connect_deadline("localhost:25575", deadline).await?;
+  /// client.log_in("SuperSecurePassword").await?;
+  /// client.send_command_deadline("save-all", deadline).await?;
+  /// client.send_command_deadline("stop", deadline).await?;
+  /// #   Ok(())
+  /// # })
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`send_command_until`](AsyncRconClient::send_command_until),
+  /// with [`CommandError::TimedOut`] if `deadline` passes first.
+  pub async fn send_command_deadline(&mut self, command: &str, deadline: Instant) -> Result<String, CommandError> {
+    self.send_command_until(command, async {
+      Timer::at(deadline).await;
+    }).await
+  }
+  
+}
\ No newline at end of file
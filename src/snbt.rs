@@ -0,0 +1,394 @@
+//! A parser and serializer for [SNBT](https://minecraft.wiki/w/NBT_format#SNBT_format),
+//! the text format Minecraft uses to display NBT data, for example in response to `data get`.
+//! 
+//! SNBT is similar to JSON, but compound keys may be unquoted,
+//! numbers carry a suffix indicating their type (like `1b`, `2s`, or `3L`),
+//! and there are typed arrays (like `[I; 1, 2, 3]`).
+//! 
+//! Example:
+//! ```
+//! # use mc_rcon::snbt::SnbtValue;
+//! let value: SnbtValue = "{Count: 1b, id: \"minecraft:stone\"}".parse().unwrap();
+//! assert_eq!(value.get("Count"), Some(&SnbtValue::Byte(1)));
+//! assert_eq!(value.to_string(), "{Count: 1b, id: \"minecraft:stone\"}");
+//! ```
+
+use std::{collections::BTreeMap, error::Error, fmt::{self, Display, Formatter}, str::FromStr};
+
+/// An SNBT value.
+/// 
+/// Values can be parsed from SNBT with [`str::parse`], and are [displayed](Display) as SNBT,
+/// such that displaying a value and parsing the result gives back an equal value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnbtValue {
+  
+  /// A byte, written like `1b`. Booleans (`true` and `false`) are parsed as bytes as well.
+  Byte(i8),
+  /// A short, written like `1s`.
+  Short(i16),
+  /// An int, written like `1`.
+  Int(i32),
+  /// A long, written like `1L`.
+  Long(i64),
+  /// A float, written like `1.0f`.
+  Float(f32),
+  /// A double, written like `1.0d` or `1.0`.
+  Double(f64),
+  /// A string, written like `"abc"` or `'abc'`, or unquoted if it contains only letters, digits, and `_-.+`.
+  String(String),
+  /// A list of values, written like `[1, 2, 3]`.
+  List(Vec<SnbtValue>),
+  /// A compound of named values, written like `{a: 1, b: 2}`.
+  Compound(BTreeMap<String, SnbtValue>),
+  /// A byte array, written like `[B; 1b, 2b, 3b]`.
+  ByteArray(Vec<i8>),
+  /// An int array, written like `[I; 1, 2, 3]`.
+  IntArray(Vec<i32>),
+  /// A long array, written like `[L; 1L, 2L, 3L]`.
+  LongArray(Vec<i64>)
+  
+}
+
+impl SnbtValue {
+  
+  /// Returns the value with the given key if this is a compound containing it.
+  pub fn get(&self, key: &str) -> Option<&SnbtValue> {
+    match self {
+      SnbtValue::Compound(compound) => compound.get(key),
+      _ => None
+    }
+  }
+  
+  /// Returns the value at the given index if this is a list with at least that many elements.
+  pub fn index(&self, index: usize) -> Option<&SnbtValue> {
+    match self {
+      SnbtValue::List(list) => list.get(index),
+      _ => None
+    }
+  }
+  
+  /// Returns this value as an `i64` if it is a byte, short, int, or long.
+  pub fn as_i64(&self) -> Option<i64> {
+    match *self {
+      SnbtValue::Byte(n) => Some(n.into()),
+      SnbtValue::Short(n) => Some(n.into()),
+      SnbtValue::Int(n) => Some(n.into()),
+      SnbtValue::Long(n) => Some(n),
+      _ => None
+    }
+  }
+  
+  /// Returns this value as an `f64` if it is any kind of number.
+  pub fn as_f64(&self) -> Option<f64> {
+    match *self {
+      SnbtValue::Float(n) => Some(n.into()),
+      SnbtValue::Double(n) => Some(n),
+      _ => self.as_i64().map(|n| n as f64)
+    }
+  }
+  
+  /// Returns this value as a string slice if it is a string.
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      SnbtValue::String(s) => Some(s),
+      _ => None
+    }
+  }
+  
+}
+
+impl FromStr for SnbtValue {
+  
+  type Err = SnbtError;
+  
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut parser = Parser { input: s, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < s.len() {
+      Err(parser.error("unexpected trailing characters"))?
+    }
+    Ok(value)
+  }
+  
+}
+
+impl Display for SnbtValue {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      SnbtValue::Byte(n) => write!(f, "{}b", n),
+      SnbtValue::Short(n) => write!(f, "{}s", n),
+      SnbtValue::Int(n) => write!(f, "{}", n),
+      SnbtValue::Long(n) => write!(f, "{}L", n),
+      SnbtValue::Float(n) => write!(f, "{:?}f", n),
+      SnbtValue::Double(n) => write!(f, "{:?}d", n),
+      SnbtValue::String(s) => write_quoted(f, s),
+      SnbtValue::List(list) => write_list(f, "", list),
+      SnbtValue::Compound(compound) => {
+        write!(f, "{{")?;
+        for (i, (key, value)) in compound.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          if !key.is_empty() && key.chars().all(is_unquoted_char) {
+            write!(f, "{}", key)?;
+          } else {
+            write_quoted(f, key)?;
+          }
+          write!(f, ": {}", value)?;
+        }
+        write!(f, "}}")
+      }
+      SnbtValue::ByteArray(array) => write_list(f, "B;", &array.iter().map(|&n| SnbtValue::Byte(n)).collect::<Vec<_>>()),
+      SnbtValue::IntArray(array) => write_list(f, "I;", &array.iter().map(|&n| SnbtValue::Int(n)).collect::<Vec<_>>()),
+      SnbtValue::LongArray(array) => write_list(f, "L;", &array.iter().map(|&n| SnbtValue::Long(n)).collect::<Vec<_>>())
+    }
+  }
+  
+}
+
+fn write_quoted(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+  write!(f, "\"")?;
+  for c in s.chars() {
+    match c {
+      '"' | '\\' => write!(f, "\\{}", c)?,
+      '\n' => write!(f, "\\n")?,
+      '\r' => write!(f, "\\r")?,
+      '\t' => write!(f, "\\t")?,
+      _ => write!(f, "{}", c)?
+    }
+  }
+  write!(f, "\"")
+}
+
+fn write_list(f: &mut Formatter<'_>, prefix: &str, list: &[SnbtValue]) -> fmt::Result {
+  write!(f, "[{}", prefix)?;
+  for (i, value) in list.iter().enumerate() {
+    if i > 0 {
+      write!(f, ", ")?;
+    } else if !prefix.is_empty() {
+      write!(f, " ")?;
+    }
+    write!(f, "{}", value)?;
+  }
+  write!(f, "]")
+}
+
+fn is_unquoted_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || "_-.+".contains(c)
+}
+
+/// A failure to parse SNBT.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SnbtError {
+  
+  /// The byte offset in the input at which the error occurred.
+  pub position: usize,
+  /// A description of the error.
+  pub message: &'static str
+  
+}
+
+impl Display for SnbtError {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "invalid SNBT at position {}: {}", self.position, self.message)
+  }
+  
+}
+
+impl Error for SnbtError {}
+
+struct Parser<'a> {
+  
+  input: &'a str,
+  pos: usize
+  
+}
+
+impl Parser<'_> {
+  
+  fn error(&self, message: &'static str) -> SnbtError {
+    SnbtError { position: self.pos, message }
+  }
+  
+  fn peek(&self) -> Option<char> {
+    self.input[self.pos..].chars().next()
+  }
+  
+  fn skip_whitespace(&mut self) {
+    let rest = &self.input[self.pos..];
+    self.pos += rest.len() - rest.trim_start().len();
+  }
+  
+  fn eat(&mut self, c: char) -> bool {
+    self.skip_whitespace();
+    if self.peek() == Some(c) {
+      self.pos += c.len_utf8();
+      true
+    } else {
+      false
+    }
+  }
+  
+  fn expect(&mut self, c: char, message: &'static str) -> Result<(), SnbtError> {
+    if self.eat(c) {
+      Ok(())
+    } else {
+      Err(self.error(message))
+    }
+  }
+  
+  fn value(&mut self) -> Result<SnbtValue, SnbtError> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some('{') => self.compound(),
+      Some('[') => self.list(),
+      Some('"' | '\'') => Ok(SnbtValue::String(self.quoted()?)),
+      Some(_) => self.scalar(),
+      None => Err(self.error("expected a value"))
+    }
+  }
+  
+  fn compound(&mut self) -> Result<SnbtValue, SnbtError> {
+    self.expect('{', "expected '{'")?;
+    let mut compound = BTreeMap::new();
+    if !self.eat('}') {
+      loop {
+        self.skip_whitespace();
+        let key = match self.peek() {
+          Some('"' | '\'') => self.quoted()?,
+          _ => self.unquoted().to_owned()
+        };
+        if key.is_empty() {
+          Err(self.error("expected a key"))?
+        }
+        self.expect(':', "expected ':'")?;
+        let value = self.value()?;
+        compound.insert(key, value);
+        if self.eat('}') {
+          break
+        }
+        self.expect(',', "expected ',' or '}'")?;
+      }
+    }
+    Ok(SnbtValue::Compound(compound))
+  }
+  
+  fn list(&mut self) -> Result<SnbtValue, SnbtError> {
+    self.expect('[', "expected '['")?;
+    let rest = &self.input[self.pos..];
+    let array_type = ['B', 'I', 'L'].into_iter().find(|&t| rest.trim_start().strip_prefix(t).is_some_and(|rest| rest.trim_start().starts_with(';')));
+    if array_type.is_some() {
+      self.skip_whitespace();
+      self.pos += 1; // array type
+      self.expect(';', "expected ';'")?;
+    }
+    let mut values = Vec::new();
+    if !self.eat(']') {
+      loop {
+        values.push(self.value()?);
+        if self.eat(']') {
+          break
+        }
+        self.expect(',', "expected ',' or ']'")?;
+      }
+    }
+    let array_error = self.error("typed array contains an element of the wrong type");
+    match array_type {
+      Some('B') => values.into_iter().map(|value| match value {
+        SnbtValue::Byte(n) => Ok(n),
+        _ => Err(array_error.clone())
+      }).collect::<Result<_, _>>().map(SnbtValue::ByteArray),
+      Some('I') => values.into_iter().map(|value| match value {
+        SnbtValue::Int(n) => Ok(n),
+        _ => Err(array_error.clone())
+      }).collect::<Result<_, _>>().map(SnbtValue::IntArray),
+      Some(_) => values.into_iter().map(|value| match value {
+        SnbtValue::Long(n) => Ok(n),
+        _ => Err(array_error.clone())
+      }).collect::<Result<_, _>>().map(SnbtValue::LongArray),
+      None => Ok(SnbtValue::List(values))
+    }
+  }
+  
+  fn quoted(&mut self) -> Result<String, SnbtError> {
+    let quote = self.peek().ok_or_else(|| self.error("expected a string"))?;
+    self.pos += 1;
+    let mut s = String::new();
+    let mut chars = self.input[self.pos..].char_indices();
+    while let Some((i, c)) = chars.next() {
+      match c {
+        '\\' => {
+          let escaped = match chars.next() {
+            Some((_, c @ ('\\' | '"' | '\''))) => c,
+            Some((_, 'n')) => '\n',
+            Some((_, 'r')) => '\r',
+            Some((_, 't')) => '\t',
+            Some((_, 'b')) => '\u{8}',
+            Some((_, 'f')) => '\u{c}',
+            Some((j, 'u')) => {
+              let hex = self.input.get(self.pos + j + 1..self.pos + j + 5).ok_or_else(|| SnbtError { position: self.pos + j, message: "invalid unicode escape" })?;
+              let c = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32).ok_or_else(|| SnbtError { position: self.pos + j, message: "invalid unicode escape" })?;
+              for _ in 0..4 {
+                chars.next();
+              }
+              c
+            }
+            _ => Err(SnbtError { position: self.pos + i, message: "invalid escape sequence" })?
+          };
+          s.push(escaped);
+        }
+        c if c == quote => {
+          self.pos += i + 1;
+          return Ok(s)
+        }
+        c => s.push(c)
+      }
+    }
+    Err(SnbtError { position: self.input.len(), message: "unterminated string" })
+  }
+  
+  fn unquoted(&mut self) -> &str {
+    let rest = &self.input[self.pos..];
+    let len = rest.find(|c| !is_unquoted_char(c)).unwrap_or(rest.len());
+    self.pos += len;
+    &rest[..len]
+  }
+  
+  fn scalar(&mut self) -> Result<SnbtValue, SnbtError> {
+    let start = self.pos;
+    let token = self.unquoted();
+    if token.is_empty() {
+      return Err(SnbtError { position: start, message: "unexpected character" })
+    }
+    Ok(parse_scalar(token))
+  }
+  
+}
+
+fn parse_scalar(token: &str) -> SnbtValue {
+  match token {
+    "true" => return SnbtValue::Byte(1),
+    "false" => return SnbtValue::Byte(0),
+    _ => {}
+  }
+  let (digits, suffix) = match token.char_indices().last() {
+    Some((i, c)) if c.is_ascii_alphabetic() => (&token[..i], Some(c.to_ascii_lowercase())),
+    _ => (token, None)
+  };
+  let is_integer = !digits.is_empty() && digits.strip_prefix(['-', '+']).unwrap_or(digits).chars().all(|c| c.is_ascii_digit());
+  let is_decimal = !digits.is_empty() && digits.contains(|c: char| c.is_ascii_digit()) && digits.chars().all(|c| c.is_ascii_digit() || "-+.eE".contains(c));
+  let number = match suffix {
+    Some('b') if is_integer => digits.parse().ok().map(SnbtValue::Byte),
+    Some('s') if is_integer => digits.parse().ok().map(SnbtValue::Short),
+    Some('l') if is_integer => digits.parse().ok().map(SnbtValue::Long),
+    Some('f') if is_decimal => digits.parse().ok().map(SnbtValue::Float),
+    Some('d') if is_decimal => digits.parse().ok().map(SnbtValue::Double),
+    None if is_integer => digits.parse().ok().map(SnbtValue::Int),
+    None if is_decimal => digits.parse().ok().map(SnbtValue::Double),
+    _ => None
+  };
+  number.unwrap_or_else(|| SnbtValue::String(token.to_owned()))
+}
\ No newline at end of file
@@ -0,0 +1,130 @@
+use std::{error::Error, fmt::{self, Display, Formatter}};
+
+use crate::{CommandError, RconClient};
+
+/// How [`RconClient::send_command_split`] builds commands from a list of items.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SplitTemplate {
+  
+  prefix: String,
+  separator: Option<String>
+  
+}
+
+impl SplitTemplate {
+  
+  /// A template that sends one command per item, made of `prefix` followed by the item,
+  /// for commands that only accept a single item, like `whitelist add`.
+  pub fn one_per_command(prefix: impl Into<String>) -> Self {
+    Self { prefix: prefix.into(), separator: None }
+  }
+  
+  /// A template that packs as many items as fit into each command, made of `prefix` followed by the items joined with `separator`.
+  pub fn joined(prefix: impl Into<String>, separator: impl Into<String>) -> Self {
+    Self { prefix: prefix.into(), separator: Some(separator.into()) }
+  }
+  
+  /// Returns the text each command starts with.
+  pub fn prefix(&self) -> &str {
+    &self.prefix
+  }
+  
+  /// Returns the text between items in the same command, or `None` if each item is sent in its own command.
+  pub fn separator(&self) -> Option<&str> {
+    self.separator.as_deref()
+  }
+  
+  /// Builds the commands for `items`, each no longer than `max_len`, along with the number of items in each.
+  fn commands(&self, items: &[&str], max_len: usize) -> Result<Vec<(String, usize)>, CommandError> {
+    let mut commands: Vec<(String, usize)> = Vec::new();
+    for item in items {
+      if let (Some(separator), Some((command, count))) = (&self.separator, commands.last_mut()) {
+        if command.len() + separator.len() + item.len() <= max_len {
+          command.push_str(separator);
+          command.push_str(item);
+          *count += 1;
+          continue
+        }
+      }
+      let command = format!("{}{}", self.prefix, item);
+      if command.len() > max_len {
+        Err(CommandError::CommandTooLong)?
+      }
+      commands.push((command, 1));
+    }
+    Ok(commands)
+  }
+  
+}
+
+/// A failed attempt to send a split command. See [`RconClient::send_command_split`] for details.
+#[derive(Debug)]
+pub struct SplitCommandError {
+  
+  /// The number of items that were sent (and responded to) before the error.
+  pub items_sent: usize,
+  /// The responses to the commands that were sent before the error, in order.
+  pub responses: Vec<String>,
+  /// The error that stopped the remaining commands from being sent.
+  pub error: CommandError
+  
+}
+
+impl Display for SplitCommandError {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "failed after sending {} items: {}", self.items_sent, self.error)
+  }
+  
+}
+
+impl Error for SplitCommandError {
+  
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(&self.error)
+  }
+  
+}
+
+impl RconClient {
+  
+  /// Sends commands built from `template` for each of `items`, packing as many items into each command as the template and the
+  /// [outgoing payload limit](RconClient::set_max_outgoing_payload) allow, and returns the responses to each command in order.
+  /// 
+  /// Example:
+  /// ```no_run
+  /// # use std::error::Error;
+  /// # use mc_rcon::{RconClient, SplitTemplate};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// let client = RconClient::connect("localhost:25575")?;
+  /// client.log_in("SuperSecurePassword")?;
+  /// let players = ["Steve", "Alex", "Notch"];
+  /// for response in client.send_command_split(SplitTemplate::one_per_command("whitelist add "), &players)? {
+  ///   println!("{}", response);
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// If any item is too long to fit in a command by itself, returns a [`SplitCommandError`] with [`CommandError::CommandTooLong`]
+  /// without sending anything.
+  /// Otherwise, if any command fails in the same cases as [`send_command`](RconClient::send_command),
+  /// stops and returns a [`SplitCommandError`] with that error, along with how many items were sent before it.
+  pub fn send_command_split(&self, template: SplitTemplate, items: &[&str]) -> Result<Vec<String>, SplitCommandError> {
+    let commands = template.commands(items, self.max_outgoing_payload()).map_err(|error| SplitCommandError { items_sent: 0, responses: Vec::new(), error })?;
+    let mut responses = Vec::with_capacity(commands.len());
+    let mut items_sent = 0;
+    for (command, count) in commands {
+      match self.send_command(&command) {
+        Ok(response) => responses.push(response),
+        Err(error) => return Err(SplitCommandError { items_sent, responses, error })
+      }
+      items_sent += count;
+    }
+    Ok(responses)
+  }
+  
+}
\ No newline at end of file
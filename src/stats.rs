@@ -0,0 +1,73 @@
+use std::{sync::atomic::{AtomicU64, Ordering::Relaxed}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+/// A snapshot of the cumulative statistics of an [`RconClient`](crate::RconClient).
+/// 
+/// See [`RconClient::stats`](crate::RconClient::stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RconStats {
+  
+  /// The number of commands sent to the server, not counting the extra commands sent to handle fragmented responses.
+  pub commands_sent: u64,
+  /// The number of bytes written to the connection.
+  pub bytes_written: u64,
+  /// The number of bytes read from the connection.
+  pub bytes_read: u64,
+  /// The number of responses that spanned more than one packet.
+  pub fragmented_responses: u64,
+  /// The number of operations that failed due to an I/O error.
+  pub io_errors: u64,
+  /// The number of passwords or commands that were not sent because they were too long.
+  pub oversized_payloads: u64,
+  /// The number of times the server rejected the client's authentication,
+  /// either by refusing a password or by refusing a command from a client whose session had ended.
+  pub auth_failures: u64,
+  /// The time at which the last successful command response was received, if any.
+  pub last_success: Option<SystemTime>
+  
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+  
+  pub(crate) commands_sent: AtomicU64,
+  pub(crate) bytes_written: AtomicU64,
+  pub(crate) bytes_read: AtomicU64,
+  pub(crate) fragmented_responses: AtomicU64,
+  pub(crate) io_errors: AtomicU64,
+  pub(crate) oversized_payloads: AtomicU64,
+  pub(crate) auth_failures: AtomicU64,
+  last_success_micros: AtomicU64 // 0 means never
+  
+}
+
+impl StatsCounters {
+  
+  pub(crate) fn increment(counter: &AtomicU64) {
+    Self::add(counter, 1);
+  }
+  
+  pub(crate) fn add(counter: &AtomicU64, n: u64) {
+    counter.fetch_add(n, Relaxed);
+  }
+  
+  pub(crate) fn record_success(&self) {
+    let micros = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| u64::try_from(d.as_micros()).unwrap_or(u64::MAX));
+    self.last_success_micros.store(micros.max(1), Relaxed);
+  }
+  
+  pub(crate) fn snapshot(&self) -> RconStats {
+    let last_success_micros = self.last_success_micros.load(Relaxed);
+    RconStats {
+      commands_sent: self.commands_sent.load(Relaxed),
+      bytes_written: self.bytes_written.load(Relaxed),
+      bytes_read: self.bytes_read.load(Relaxed),
+      fragmented_responses: self.fragmented_responses.load(Relaxed),
+      io_errors: self.io_errors.load(Relaxed),
+      oversized_payloads: self.oversized_payloads.load(Relaxed),
+      auth_failures: self.auth_failures.load(Relaxed),
+      last_success: (last_success_micros != 0).then(|| UNIX_EPOCH + Duration::from_micros(last_success_micros))
+    }
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,237 @@
+//! Utilities for testing code that uses this crate without a real Minecraft server.
+
+use std::{collections::HashMap, io::{self, ErrorKind, Read, Write}, net::{SocketAddr, TcpListener}, thread::{self, JoinHandle}};
+
+use crate::{read_raw_packet, transcript::{TranscriptReader, TranscriptRecord}, write_raw_packet, Dialect, Direction, RawPacketHeader, COMMAND_RESPONSE_TYPE, COMMAND_TYPE, HEADER_LEN, LOGIN_RESPONSE_TYPE, LOGIN_TYPE, MAX_INCOMING_PAYLOAD_LEN};
+
+/// A server that replays the server side of a recorded [transcript](crate::transcript) to a single client.
+/// 
+/// The server listens on an unused local port and accepts one connection.
+/// For each packet the transcript records the client sending, it waits for the client to send a packet of the same type,
+/// and then sends the packets the transcript records the client receiving before the next packet the client sent.
+/// Packet ids in the replayed responses are rewritten to match the ids that the connected client actually used,
+/// and the payloads the client sends are not checked (which means that redacted login packets replay fine).
+/// Once the transcript is exhausted, or the client disconnects, the server closes the connection.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::{error::Error, fs::File};
+/// # use mc_rcon::{RconClient, testing::ReplayServer};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let server = ReplayServer::from_transcript(File::open("session.rcon")?)?;
+/// let client = RconClient::connect(server.local_addr())?;
+/// client.log_in("anything")?;
+/// println!("{}", client.send_command("seed")?);
+/// drop(client);
+/// server.join()?;
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReplayServer {
+  
+  addr: SocketAddr,
+  handle: JoinHandle<io::Result<()>>
+  
+}
+
+impl ReplayServer {
+  
+  /// Reads a transcript from the given reader and starts serving it.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while reading the transcript or binding the server's socket.
+  pub fn from_transcript<R: Read>(reader: R) -> io::Result<ReplayServer> {
+    let records = TranscriptReader::new(reader).collect::<io::Result<Vec<_>>>()?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let handle = thread::spawn(move || replay(listener, records));
+    Ok(ReplayServer { addr, handle })
+  }
+  
+  /// Returns the address that this server is listening on.
+  pub fn local_addr(&self) -> SocketAddr {
+    self.addr
+  }
+  
+  /// Waits for this server to finish replaying its transcript.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occurred while serving the client.
+  /// If the client sent a packet of a different type than the transcript expected, the error is [`InvalidData`](ErrorKind::InvalidData).
+  pub fn join(self) -> io::Result<()> {
+    self.handle.join().unwrap_or_else(|_| Err(io::Error::other("replay server panicked")))
+  }
+  
+}
+
+fn replay(listener: TcpListener, records: Vec<TranscriptRecord>) -> io::Result<()> {
+  let (mut stream, _) = listener.accept()?;
+  let mut ids = HashMap::new();
+  for record in records {
+    match record.direction {
+      Direction::Outgoing => {
+        let (header, _) = match read_raw_packet(&mut stream) {
+          Ok(packet) => packet,
+          Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+          Err(e) => Err(e)?
+        };
+        if header.packet_type != record.header.packet_type {
+          Err(io::Error::new(ErrorKind::InvalidData, "client sent a packet of a different type than the transcript"))?
+        }
+        ids.insert(record.header.id, header.id);
+      }
+      Direction::Incoming => {
+        let id = ids.get(&record.header.id).copied().unwrap_or(record.header.id);
+        write_raw_packet(&mut stream, &RawPacketHeader { id, ..record.header }, &record.payload)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// A server that answers a single client's commands with a function, with as little latency as possible.
+/// 
+/// The server listens on an unused local port and accepts one connection.
+/// It accepts logins with the given password (and rejects others, as Minecraft does),
+/// and answers each command with the response returned by the script, split into [fragments](https://wiki.vg/RCON#Fragmentation)
+/// of [`MAX_INCOMING_PAYLOAD_LEN`] bytes like Minecraft does.
+/// Every response is written at once, and Nagle's algorithm is disabled, so that the server adds as little delay as possible;
+/// this makes it suitable for benchmarking the client.
+/// The server answers commands even if the client has not logged in, and stops once the client disconnects.
+/// 
+/// Example:
+/// ```
+/// # use std::error::Error;
+/// # use mc_rcon::{RconClient, testing::ScriptedServer};
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let server = ScriptedServer::new("password", |command| format!("you said {}", command))?;
+/// let client = RconClient::connect(server.local_addr())?;
+/// client.log_in("password")?;
+/// assert_eq!(client.send_command("hello")?, "you said hello");
+/// drop(client);
+/// server.join()?;
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScriptedServer {
+  
+  addr: SocketAddr,
+  handle: JoinHandle<io::Result<()>>
+  
+}
+
+impl ScriptedServer {
+  
+  /// Starts a server that accepts `password` and answers each command with the response `script` returns for it.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while binding the server's socket.
+  pub fn new<F: FnMut(&str) -> String + Send + 'static>(password: &str, script: F) -> io::Result<ScriptedServer> {
+    ScriptedServer::with_dialect(password, Dialect::Minecraft, script)
+  }
+  
+  /// Starts a server like [`new`](ScriptedServer::new) that speaks the given [`Dialect`].
+  /// 
+  /// A server speaking the [Source dialect](Dialect::Source) sends an empty response packet before each login response,
+  /// and answers each empty response packet from the client with an empty response packet and then another packet with the same id,
+  /// as Source servers do.
+  /// ```
+  /// # use std::error::Error;
+  /// # use mc_rcon::{Dialect, RconClient, testing::ScriptedServer};
+  /// # 
+  /// # fn main() -> Result<(), Box<dyn Error>> {
+  /// for dialect in [Dialect::Minecraft, Dialect::Source] {
+  ///   let server = ScriptedServer::with_dialect("password", dialect, |command| match command.strip_prefix("repeat ") {
+  ///     Some(text) => text.repeat(3000),
+  ///     None => format!("you said {}", command)
+  ///   })?;
+  ///   let client = RconClient::connect_with_dialect(server.local_addr(), dialect)?;
+  ///   client.log_in("password")?;
+  ///   assert_eq!(client.send_command("repeat ab")?, "ab".repeat(3000)); // fragmented
+  ///   assert_eq!(client.send_command("hello")?, "you said hello");
+  ///   drop(client);
+  ///   server.join()?;
+  /// }
+  /// #   Ok(())
+  /// # }
+  /// ```
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while binding the server's socket.
+  pub fn with_dialect<F: FnMut(&str) -> String + Send + 'static>(password: &str, dialect: Dialect, script: F) -> io::Result<ScriptedServer> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let password = password.as_bytes().to_vec();
+    let handle = thread::spawn(move || serve_script(listener, password, dialect, script));
+    Ok(ScriptedServer { addr, handle })
+  }
+  
+  /// Returns the address that this server is listening on.
+  pub fn local_addr(&self) -> SocketAddr {
+    self.addr
+  }
+  
+  /// Waits for the client to disconnect.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occurred while serving the client.
+  /// If the client sent a packet that the server's dialect does not expect, the error is [`InvalidData`](ErrorKind::InvalidData).
+  pub fn join(self) -> io::Result<()> {
+    self.handle.join().unwrap_or_else(|_| Err(io::Error::other("scripted server panicked")))
+  }
+  
+}
+
+fn serve_script(listener: TcpListener, password: Vec<u8>, dialect: Dialect, mut script: impl FnMut(&str) -> String) -> io::Result<()> {
+  let (mut stream, _) = listener.accept()?;
+  stream.set_nodelay(true)?;
+  let mut out = Vec::new();
+  loop {
+    let (header, payload) = match read_raw_packet(&mut stream) {
+      Ok(packet) => packet,
+      // a client that disconnects without reading everything (like the end of a Source fence) resets the connection
+      Err(e) if matches!(e.kind(), ErrorKind::UnexpectedEof | ErrorKind::ConnectionReset) => return Ok(()),
+      Err(e) => Err(e)?
+    };
+    out.clear();
+    match header.packet_type {
+      LOGIN_TYPE => {
+        if dialect == Dialect::Source {
+          write_response(&mut out, header.id, COMMAND_RESPONSE_TYPE, b"")?;
+        }
+        let id = if payload == password { header.id } else { -1 };
+        write_response(&mut out, id, LOGIN_RESPONSE_TYPE, b"")?;
+      }
+      COMMAND_TYPE => {
+        let response = script(&String::from_utf8_lossy(&payload));
+        let mut fragments = response.as_bytes().chunks(MAX_INCOMING_PAYLOAD_LEN).peekable();
+        if fragments.peek().is_none() {
+          write_response(&mut out, header.id, COMMAND_RESPONSE_TYPE, b"")?;
+        }
+        for fragment in fragments {
+          write_response(&mut out, header.id, COMMAND_RESPONSE_TYPE, fragment)?;
+        }
+      }
+      COMMAND_RESPONSE_TYPE if dialect == Dialect::Source => {
+        write_response(&mut out, header.id, COMMAND_RESPONSE_TYPE, b"")?;
+        write_response(&mut out, header.id, COMMAND_RESPONSE_TYPE, b"\0\0\0\x01\0\0")?;
+      }
+      _ => Err(io::Error::new(ErrorKind::InvalidData, "client sent a packet that the server's dialect does not expect"))?
+    }
+    stream.write_all(&out)?;
+  }
+}
+
+fn write_response(out: &mut Vec<u8>, id: i32, packet_type: i32, payload: &[u8]) -> io::Result<()> {
+  let length = i32::try_from(HEADER_LEN + payload.len()).expect("fragment is short");
+  write_raw_packet(out, &RawPacketHeader { length, id, packet_type }, payload)
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::io;
+
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+use crate::AsyncRconClient;
+
+/// An [`AsyncRconClient`] for [`tokio`](https://docs.rs/tokio), available with the `tokio` feature.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::error::Error;
+/// # use mc_rcon::TokioRconClient;
+/// # 
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let mut client = TokioRconClient::connect("localhost:25575").await?;
+/// client.log_in("SuperSecurePassword").await?;
+/// println!("{}", client.send_command("seed").await?);
+/// #   Ok(())
+/// # }
+/// ```
+/// 
+/// Like any `AsyncRconClient` with the `tokio` feature, it also implements [`tokio::io::AsyncRead`] and [`tokio::io::AsyncWrite`],
+/// so it can be framed with [`RconCodec`](crate::RconCodec).
+pub type TokioRconClient = AsyncRconClient<Compat<TcpStream>>;
+
+impl AsyncRconClient<Compat<TcpStream>> {
+  
+  /// Construct a `TokioRconClient` and connect to a server at the given address.
+  /// 
+  /// # Errors
+  /// 
+  /// Errors in the same cases as [`RconClient::connect`](crate::RconClient::connect).
+  pub async fn connect<A: ToSocketAddrs>(server_addr: A) -> io::Result<TokioRconClient> {
+    Ok(AsyncRconClient::new(TcpStream::connect(server_addr).await?.compat()))
+  }
+  
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+//! A simple binary format for recording RCON sessions.
+//! 
+//! A transcript is a sequence of records, one per packet, each laid out as:
+//! 
+//! * the time since recording started, in microseconds, as a little-endian `u64`;
+//! * the direction of the packet, `0` for [outgoing](Direction::Outgoing) or `1` for [incoming](Direction::Incoming);
+//! * the packet itself, exactly as it appears on the wire (including its length field, null terminator, and padding).
+//! 
+//! Transcripts are produced by [`RconClient::start_recording`](crate::RconClient::start_recording)
+//! and can be read back with [`TranscriptReader`].
+
+use std::{io::{self, ErrorKind, Read, Write}, time::Duration};
+
+use crate::{read_raw_packet, write_raw_packet, Direction, RawPacketHeader, HEADER_LEN, I32_LEN};
+
+/// A single packet in a transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptRecord {
+  
+  /// The time between the start of the recording and this packet.
+  pub elapsed: Duration,
+  /// Whether the client sent or received this packet.
+  pub direction: Direction,
+  /// The header of this packet.
+  pub header: RawPacketHeader,
+  /// The payload of this packet, without its null terminator and padding.
+  pub payload: Vec<u8>
+  
+}
+
+impl TranscriptRecord {
+  
+  /// Writes this record to the given writer in the transcript format.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while writing.
+  pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+    let micros = u64::try_from(self.elapsed.as_micros()).unwrap_or(u64::MAX);
+    let direction = match self.direction {
+      Direction::Outgoing => 0u8,
+      Direction::Incoming => 1u8
+    };
+    let mut buf = Vec::with_capacity(size_of::<u64>() + 1 + I32_LEN + HEADER_LEN + self.payload.len());
+    buf.extend_from_slice(&micros.to_le_bytes());
+    buf.push(direction);
+    write_raw_packet(&mut buf, &self.header, &self.payload)?;
+    writer.write_all(&buf)
+  }
+  
+  /// Reads a single record in the transcript format from the given reader.
+  /// 
+  /// Returns `None` if the reader is already at its end.
+  /// 
+  /// # Errors
+  /// 
+  /// Returns any I/O errors that occur while reading.
+  /// If the reader ends part way through a record, the error is [`UnexpectedEof`](ErrorKind::UnexpectedEof),
+  /// and if the record is malformed, the error is [`InvalidData`](ErrorKind::InvalidData).
+  pub fn read_from<R: Read>(mut reader: R) -> io::Result<Option<TranscriptRecord>> {
+    let mut micros_bytes = [0; size_of::<u64>()];
+    let read = reader.read(&mut micros_bytes[..1])?;
+    if read == 0 {
+      return Ok(None)
+    }
+    reader.read_exact(&mut micros_bytes[1..])?;
+    let elapsed = Duration::from_micros(u64::from_le_bytes(micros_bytes));
+    let mut direction_byte = [0];
+    reader.read_exact(&mut direction_byte)?;
+    let direction = match direction_byte[0] {
+      0 => Direction::Outgoing,
+      1 => Direction::Incoming,
+      _ => Err(io::Error::new(ErrorKind::InvalidData, "transcript record has an invalid direction"))?
+    };
+    let (header, payload) = read_raw_packet(reader)?;
+    Ok(Some(TranscriptRecord { elapsed, direction, header, payload }))
+  }
+  
+}
+
+/// An iterator over the records of a transcript.
+/// 
+/// Example:
+/// ```no_run
+/// # use std::{error::Error, fs::File};
+/// # use mc_rcon::transcript::TranscriptReader;
+/// # 
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// for record in TranscriptReader::new(File::open("session.rcon")?) {
+///   let record = record?;
+///   println!("{:?} {:?} {}", record.direction, record.header, String::from_utf8_lossy(&record.payload));
+/// }
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TranscriptReader<R> {
+  
+  reader: R
+  
+}
+
+impl<R: Read> TranscriptReader<R> {
+  
+  /// Constructs a `TranscriptReader` that reads records from the given reader.
+  pub fn new(reader: R) -> TranscriptReader<R> {
+    TranscriptReader { reader }
+  }
+  
+  /// Returns the underlying reader.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+  
+}
+
+impl<R: Read> Iterator for TranscriptReader<R> {
+  
+  type Item = io::Result<TranscriptRecord>;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    TranscriptRecord::read_from(&mut self.reader).transpose()
+  }
+  
+}
\ No newline at end of file
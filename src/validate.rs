@@ -0,0 +1,53 @@
+use std::{error::Error, fmt::{self, Display, Formatter}};
+
+/// A problem found with a command by [`validate_minecraft_command`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommandValidationError {
+  
+  /// The command starts with `/`.
+  /// 
+  /// Commands sent over RCON must not start with a slash in vanilla Minecraft,
+  /// which responds to them with an empty response instead of an error.
+  /// This contains the command with the slash removed.
+  LeadingSlash {
+    
+    /// The command without its leading slash.
+    without_slash: String
+    
+  }
+  
+}
+
+impl Display for CommandValidationError {
+  
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      CommandValidationError::LeadingSlash { without_slash } => write!(f, "commands sent over RCON must not start with '/' (did you mean {:?}?)", without_slash)
+    }
+  }
+  
+}
+
+impl Error for CommandValidationError {}
+
+/// Checks that a command follows the conventions of vanilla Minecraft's RCON server, without sending it.
+/// 
+/// Many plugins accept commands either with or without a leading `/`, but vanilla Minecraft does not,
+/// and silently responds to commands starting with `/` with an empty response.
+/// This function can be used to catch such commands before sending them with [`RconClient::send_command`](crate::RconClient::send_command).
+/// 
+/// ```
+/// # use mc_rcon::{validate_minecraft_command, CommandValidationError};
+/// assert_eq!(validate_minecraft_command("seed"), Ok(()));
+/// assert_eq!(validate_minecraft_command("/seed"), Err(CommandValidationError::LeadingSlash { without_slash: "seed".into() }));
+/// ```
+/// 
+/// # Errors
+/// 
+/// Returns [`CommandValidationError::LeadingSlash`] if the command starts with `/` (ignoring leading whitespace).
+pub fn validate_minecraft_command(cmd: &str) -> Result<(), CommandValidationError> {
+  match cmd.trim_start().strip_prefix('/') {
+    Some(without_slash) => Err(CommandValidationError::LeadingSlash { without_slash: without_slash.to_owned() }),
+    None => Ok(())
+  }
+}
\ No newline at end of file